@@ -0,0 +1,168 @@
+use crate::cli::config::Config;
+use crate::infra::downloader::Downloader;
+use crate::shared::{constants::*, format_bytes, Result, YtdlError};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// One timed trial: the bytes fetched by all of its parallel downloads
+#[derive(Debug, Clone, Copy)]
+pub struct BenchTrial {
+    pub bytes: u64,
+    pub secs: f64,
+}
+
+impl BenchTrial {
+    pub fn throughput(&self) -> f64 {
+        if self.secs > 0.0 {
+            self.bytes as f64 / self.secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Mean per-trial throughput in bytes/sec, or `None` if no trial succeeded
+pub fn average_throughput(trials: &[BenchTrial]) -> Option<f64> {
+    if trials.is_empty() {
+        return None;
+    }
+
+    Some(trials.iter().map(BenchTrial::throughput).sum::<f64>() / trials.len() as f64)
+}
+
+/// One output directory per parallel download, so concurrent runs never share a file
+fn download_dirs(bench_dir: &Path, downloads: usize) -> Vec<PathBuf> {
+    (1..=downloads).map(|i| bench_dir.join(i.to_string())).collect()
+}
+
+/// Run `downloads` parallel downloads of `url` and time them together
+async fn run_trial(
+    url: &str,
+    downloads: usize,
+    fragment_count: usize,
+    bench_dir: &Path,
+    config: &Config,
+) -> Result<BenchTrial> {
+    let runs = download_dirs(bench_dir, downloads).into_iter().map(|dir| async move {
+        let downloader = Downloader::from_config(config)
+            .with_output_dir(dir)
+            .with_concurrent_fragments(fragment_count);
+        downloader.download_with_progress(url, false, |_| {}).await
+    });
+
+    let start = Instant::now();
+    let results = futures::future::join_all(runs).await;
+    let secs = start.elapsed().as_secs_f64();
+
+    let mut bytes = 0;
+    for path in results {
+        bytes += tokio::fs::metadata(path?).await.map(|m| m.len()).unwrap_or(0);
+    }
+
+    Ok(BenchTrial { bytes, secs })
+}
+
+pub async fn handle_bench_command(
+    url: &str,
+    trials: usize,
+    fragments: &[usize],
+    concurrent: &[usize],
+    config: &Config,
+) -> Result<()> {
+    if trials == 0 {
+        return Err(YtdlError::Config("--trials must be at least 1".to_string()));
+    }
+    if concurrent.contains(&0) {
+        return Err(YtdlError::Config("--concurrent values must be at least 1".to_string()));
+    }
+
+    let bench_dir = config.output_dir.join(".ytdl-bench");
+    let mut results = Vec::new();
+
+    for &downloads in concurrent {
+        for &fragment_count in fragments {
+            println!(
+                "\n{} {} parallel downloads, {} concurrent fragments, {} trials",
+                "Benchmarking:".green().bold(),
+                downloads,
+                fragment_count,
+                trials
+            );
+
+            let mut runs = Vec::new();
+            for trial in 1..=trials {
+                match run_trial(url, downloads, fragment_count, &bench_dir, config).await {
+                    Ok(run) => {
+                        info!(
+                            "Trial {} ({} downloads, {} fragments): {} in {:.1}s",
+                            trial, downloads, fragment_count, run.bytes, run.secs
+                        );
+                        println!("  Trial {}: {}/s", trial, format_bytes(run.throughput() as u64));
+                        runs.push(run);
+                    }
+                    Err(e) => {
+                        warn!("Trial {} ({} downloads, {} fragments) failed: {}", trial, downloads, fragment_count, e);
+                        println!("  Trial {}: {} {}", trial, "failed".red(), e);
+                    }
+                }
+
+                // Start every trial from an empty directory so nothing is resumed
+                let _ = tokio::fs::remove_dir_all(&bench_dir).await;
+            }
+
+            results.push((downloads, fragment_count, runs));
+        }
+    }
+
+    println!("\n{}", "Benchmark Results:".green().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    println!("{:<12} {:<12} {:<10} {:<15}", "Downloads", "Fragments", "Trials", "Avg Throughput");
+    println!("{}", SUBSEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+
+    for (downloads, fragment_count, runs) in &results {
+        let avg = average_throughput(runs)
+            .map(|t| format!("{}/s", format_bytes(t as u64)))
+            .unwrap_or_else(|| "n/a".to_string());
+        println!(
+            "{:<12} {:<12} {:<10} {:<15}",
+            downloads,
+            fragment_count,
+            format!("{}/{}", runs.len(), trials),
+            avg
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_throughput() {
+        assert_eq!(average_throughput(&[]), None);
+
+        let trials = [
+            BenchTrial { bytes: 1000, secs: 1.0 },
+            BenchTrial { bytes: 3000, secs: 1.0 },
+            BenchTrial { bytes: 500, secs: 0.0 },
+        ];
+        assert_eq!(average_throughput(&trials[..2]), Some(2000.0));
+        // A zero-length trial counts as zero throughput rather than dividing by zero
+        assert_eq!(average_throughput(&trials), Some(4000.0 / 3.0));
+    }
+
+    #[test]
+    fn test_download_dirs_are_distinct() {
+        let bench_dir = Path::new("/tmp/.ytdl-bench");
+        let dirs = download_dirs(bench_dir, 3);
+
+        assert_eq!(dirs.len(), 3);
+        assert!(dirs.iter().all(|d| d.parent() == Some(bench_dir)));
+        assert_ne!(dirs[0], dirs[1]);
+        assert_ne!(dirs[1], dirs[2]);
+    }
+}
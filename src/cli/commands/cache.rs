@@ -0,0 +1,13 @@
+use crate::cli::config::Config;
+use crate::infra::downloader::{clear_ytdlp_cache, Downloader};
+use crate::shared::Result;
+use colored::Colorize;
+
+pub async fn handle_cache_clear_command(config: &Config) -> Result<()> {
+    println!("\n{}", "Clearing yt-dlp cache...".green().bold());
+
+    let downloader = Downloader::from_config(config);
+    let summary = clear_ytdlp_cache(downloader.runner(), config.cache_dir.as_deref()).await?;
+    println!("{} {}", "✓".green().bold(), summary);
+    Ok(())
+}
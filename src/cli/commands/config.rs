@@ -15,7 +15,54 @@ pub async fn handle_config_command() -> Result<()> {
     println!("File Logging: {}", config.enable_file_logging);
     println!("JSON Logging: {}", config.enable_json_logging);
     println!("Concurrent Downloads: {:?}", config.concurrent_downloads);
+    println!("Stop On Error: {:?}", config.stop_on_error);
     println!("Skip Duplicates: {:?}", config.skip_duplicates);
+    println!("Audio Format: {:?}", config.audio_format);
+    println!("Audio Quality: {:?}", config.audio_quality);
+    println!("Audio Copy: {:?}", config.audio_copy);
+    println!("Notifications: {:?}", config.notifications);
+    println!("Mirror Directories: {:?}", config.mirror_dirs);
+    println!("Match Filter: {:?}", config.match_filter);
+    println!("Age Limit: {:?}", config.age_limit);
+    println!("Min Duration: {:?}", config.min_duration);
+    println!("Max Duration: {:?}", config.max_duration);
+    println!("Min Views: {:?}", config.min_views);
+    println!("Max Views: {:?}", config.max_views);
+    println!("Min File Size: {:?}", config.min_filesize);
+    println!("Max File Size: {:?}", config.max_filesize);
+    println!("Extra yt-dlp Args: {:?}", config.extra_ytdlp_args);
+    println!("Cleanup Partials: {:?}", config.cleanup_partials);
+    println!("Quality Subdirectories: {:?}", config.quality_subdirs);
+    println!("Download Sections: {:?}", config.download_sections);
+    println!("Prefer Free Formats: {:?}", config.prefer_free_formats);
+    println!("ID Filename: {:?}", config.id_filename);
+    println!("Write Description: {:?}", config.write_description);
+    println!("Write Comments: {:?}", config.write_comments);
+    println!("Embed Chapters: {:?}", config.embed_chapters);
+    println!("Parse Metadata: {:?}", config.parse_metadata);
+    println!("Recode Video: {:?}", config.recode_video);
+    println!("Subtitle Languages: {:?}", config.sub_langs);
+    println!("Cache Directory: {:?}", config.cache_dir);
+    println!("Temp Directory: {:?}", config.temp_dir);
+    println!("Sleep Interval: {:?}..{:?}", config.sleep_interval, config.max_sleep_interval);
+    println!("Sleep Between Requests: {:?}", config.sleep_requests);
+    println!("Cookies File: {:?}", config.cookies_file);
+    println!("Cookies From Browser: {:?}", config.cookies_from_browser);
+    println!("Skip Metadata: {:?}", config.skip_metadata);
+    println!("Hash Downloads: {:?}", config.hash_downloads);
+    println!("Min Free Space: {:?}", config.min_free_space);
+    println!("Player Client Fallbacks: {:?}", config.player_client_fallbacks);
+    println!("Completion Sound: {:?}", config.completion_sound);
+    println!("Playlist Index Prefix: {:?}", config.index_prefix);
+    println!("Write Playlist Metafiles: {:?}", config.write_playlist_metafiles);
+    println!("No .part Files: {:?}", config.no_part);
+    println!("Overwrites: {:?}", config.overwrites);
+    println!("No Title Progress: {:?}", config.no_title);
+    println!("JSON Progress: {:?}", config.progress_json);
+    println!("Webhook URL: {:?}", config.webhook_url);
+    println!("Events File: {:?}", config.events_file);
+    println!("Spinner Style: {:?}", config.spinner_style);
+    println!("Spinner Speed (ms): {:?}", config.spinner_speed_ms);
 
     if let Some(path) = Config::get_default_config_path() {
         println!("\nDefault config path: {:?}", path);
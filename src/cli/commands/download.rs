@@ -1,46 +1,197 @@
 use crate::cli::config::Config;
-use crate::infra::downloader::Downloader;
-use crate::shared::Result;
+use crate::infra::downloader::{title_from_path, Downloader};
+use crate::infra::mirror::mirror_file;
+use crate::infra::{export_comments, export_sponsor_segments};
+use crate::shared::utils::{available_space, expand_template, format_duration, free_space_shortfall};
+use crate::shared::{constants::*, format_bytes, osc8_link, Result, YtdlError};
 use crate::core::history::{History, HistoryEntry};
 use colored::Colorize;
+use std::path::Path;
+use std::time::{Duration, Instant};
 use tracing::{info, warn};
 
-pub async fn download_single_url(url: &str, config: &Config, history: &mut History, resume: bool) -> Result<()> {
-    let downloader = Downloader::new(config.output_dir.clone(), config.quality.clone());
+/// `println!` for human-readable output, sent to stderr instead when `$json`
+/// (`--progress-json`) so stdout carries only the JSON progress lines
+macro_rules! say {
+    ($json:expr, $($arg:tt)*) => {
+        if $json {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+pub async fn download_single_url(
+    url: &str,
+    config: &Config,
+    history: &mut History,
+    resume: bool,
+    show_summary: bool,
+) -> Result<()> {
+    let json = config.progress_json.unwrap_or(false);
+    let downloader = Downloader::from_config(config)
+        .with_term_progress(config.term_progress.unwrap_or(false))
+        .with_title_progress(!config.no_title.unwrap_or(false))
+        .with_progress_json(json);
 
     if history.contains_url(url) {
         warn!("URL already downloaded");
         if let Some(entry) = history.get_entry_by_url(url) {
-            println!("\n{} This URL was already downloaded:", "⚠".yellow().bold());
-            println!("  Title: {}", entry.title);
-            println!("  Date: {}", entry.timestamp.format("%Y-%m-%d %H:%M:%S"));
-            println!("  Path: {:?}", entry.file_path);
+            say!(json, "\n{} This URL was already downloaded:", "⚠".yellow().bold());
+            say!(json, "  Title: {}", entry.title);
+            say!(json, "  Date: {}", entry.timestamp.format("%Y-%m-%d %H:%M:%S"));
+            say!(json, "  Path: {:?}", entry.file_path);
         }
 
-        if !resume {
-            println!("\nUse --resume to re-download");
+        if !resume && config.overwrites != Some(true) {
+            say!(json, "\nUse --resume or --force-download to re-download");
             return Ok(());
         }
     }
 
-    println!("\n{}", "Starting download...".green().bold());
+    // --print is filled from the metadata, so it's fetched even with --no-metadata
+    let video_info = if config.skip_metadata.unwrap_or(false) && config.print_template.is_none() {
+        None
+    } else {
+        downloader.fetch_video_info(url).await.ok()
+    };
 
-    let output_path = if resume {
-        downloader.resume_download(url, config.audio_only).await?
+    if let Some(duration) = video_info.as_ref().and_then(|v| v.duration) {
+        if !config.fits_duration_limits(Some(duration)) {
+            say!(
+                json,
+                "{} Skipping: duration {} is outside the --min-duration/--max-duration limits",
+                "⚠".yellow().bold(),
+                format_duration(duration)
+            );
+            return Ok(());
+        }
+    }
+
+    if let Some(views) = video_info.as_ref().and_then(|v| v.view_count) {
+        if !config.fits_view_limits(Some(views)) {
+            say!(
+                json,
+                "{} Skipping: {} views is outside the --min-views/--max-views limits",
+                "⚠".yellow().bold(),
+                views
+            );
+            return Ok(());
+        }
+    }
+
+    if !config.audio_only {
+        if let Some(actual) = video_info.as_ref().and_then(|v| v.quality_fallback(&config.quality)) {
+            warn!("Requested quality {} not available, falling back to {}p", config.quality, actual);
+            say!(
+                json,
+                "{} Requested {}p not available, downloading {}p",
+                "⚠".yellow().bold(),
+                config.quality.trim_end_matches('p'),
+                actual
+            );
+        }
+    }
+
+    if let Some(info) = video_info.as_ref() {
+        info.ensure_downloadable()?;
+    }
+
+    if let Some(estimated) = video_info.as_ref().and_then(|v| v.estimated_size(&config.quality, config.audio_only)) {
+        if !config.fits_filesize_limits(Some(estimated)) {
+            say!(
+                json,
+                "{} Skipping: estimated size {} is outside the --min-filesize/--max-filesize limits",
+                "⚠".yellow().bold(),
+                format_bytes(estimated)
+            );
+            return Ok(());
+        }
+        check_free_space(config, estimated)?;
+    }
+
+    say!(json, "\n{}", "Starting download...".green().bold());
+
+    let partial = match (resume, video_info.as_ref()) {
+        (true, Some(info)) => {
+            downloader.find_partial_for(url, downloader.file_stem_for(&info.title, &info.id))
+        }
+        _ => None,
+    };
+
+    let started = Instant::now();
+    let result = if let Some(partial) = partial {
+        say!(json, "Resuming partial download: {:?}", partial);
+        downloader.resume_download(url, config.audio_only).await
     } else {
-        downloader.download(url, config.audio_only).await?
+        downloader.download(url, config.audio_only).await
     };
+    let output_path = match result {
+        Err(YtdlError::Skipped(reason)) => {
+            say!(json, "{} Skipping: {}", "⚠".yellow().bold(), reason);
+            return Ok(());
+        }
+        result => result?,
+    };
+    // Taken before any rename so it matches what yt-dlp wrote
+    let file_title = title_from_path(&output_path);
+
+    // Before the SponsorBlock export, which removes the shared info json
+    if config.write_comments.unwrap_or(false) {
+        match export_comments(&output_path, config.sponsorblock_export.is_some()).await {
+            Ok(Some(count)) => say!(json, "{} Saved {} comments", "✓".green().bold(), count),
+            Ok(None) => say!(json, "{} Comments are disabled for this video", "⚠".yellow().bold()),
+            Err(e) => warn!("Failed to export comments: {}", e),
+        }
+    }
+
+    if let Some(ref export_path) = config.sponsorblock_export {
+        match export_sponsor_segments(&output_path, export_path).await {
+            Ok(count) => say!(
+                json,
+                "{} Exported {} sponsor segments to {:?}",
+                "✓".green().bold(),
+                count,
+                export_path
+            ),
+            Err(e) => warn!("Failed to export sponsor segments: {}", e),
+        }
+    }
 
-    println!(
+    let output_path = config.apply_filename_policy(output_path).await;
+    let elapsed = started.elapsed();
+
+    config.play_completion_sound();
+
+    say!(
+        json,
         "\n{} Downloaded to: {}",
         "✓".green().bold(),
-        output_path.display()
+        osc8_link(&output_path)
     );
 
-    let video_info = downloader.fetch_video_info(url).await.ok();
+    if !config.mirror_dirs.is_empty() {
+        let copied = mirror_file(&output_path, &config.mirror_dirs).await;
+        say!(
+            json,
+            "{} Mirrored {} to {} directories",
+            "✓".green().bold(),
+            format_bytes(copied),
+            config.mirror_dirs.len()
+        );
+    }
+
+    if let Some(ref template) = config.print_template {
+        match video_info.as_ref() {
+            Some(info) => say!(json, "{}", expand_template(template, info)),
+            None => warn!("Video metadata unavailable, skipping --print"),
+        }
+    }
+
     let title = video_info
-        .as_ref()
-        .map(|v| v.title.clone())
+        .map(|v| v.title)
+        .or(file_title)
         .unwrap_or_else(|| url.to_string());
 
     let file_size = tokio::fs::metadata(&output_path)
@@ -48,21 +199,102 @@ pub async fn download_single_url(url: &str, config: &Config, history: &mut Histo
         .map(|m| m.len())
         .unwrap_or(0);
 
+    if show_summary {
+        print_download_summary(&output_path, file_size, elapsed);
+    }
+
+    let sha256 = config.content_hash(&output_path).await;
+    let format = config.history_format(&output_path);
     let entry = HistoryEntry::new(
         url.to_string(),
         title,
         output_path,
         file_size,
         config.quality.clone(),
-        if config.audio_only {
-            "mp3".to_string()
-        } else {
-            "mp4".to_string()
-        },
-    );
+        format,
+    )
+    .with_sha256(sha256);
 
     history.add_entry(entry);
     history.save()?;
 
     Ok(())
 }
+
+/// `--subs-only`: save the subtitles in `langs` without the video. History records
+/// the first subtitle file, with its extension as the format.
+pub async fn download_subtitles_only(url: &str, config: &Config, history: &mut History, langs: &str) -> Result<()> {
+    let downloader = Downloader::from_config(config);
+
+    println!("\n{}", format!("Downloading {} subtitles...", langs).green().bold());
+
+    let files = downloader.download_subtitles(url, langs).await?;
+    for file in &files {
+        println!("{} Saved subtitles to: {}", "✓".green().bold(), osc8_link(file));
+    }
+
+    let path = files[0].clone();
+    // `Title.en.vtt`: drop the language as well as the extension
+    let title = title_from_path(&path)
+        .map(|stem| title_from_path(Path::new(&stem)).unwrap_or(stem))
+        .unwrap_or_else(|| url.to_string());
+    let format = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "vtt".to_string());
+    let file_size = tokio::fs::metadata(&path).await.map(|m| m.len()).unwrap_or(0);
+
+    history.add_entry(HistoryEntry::new(url.to_string(), title, path, file_size, langs.to_string(), format));
+    history.save()?;
+
+    Ok(())
+}
+
+fn print_download_summary(path: &Path, file_size: u64, elapsed: Duration) {
+    let secs = elapsed.as_secs_f64();
+
+    println!("\n{}", "Download Summary:".green().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    if let Some(name) = path.file_name() {
+        println!("File: {}", name.to_string_lossy());
+    }
+    println!("Size: {}", format_bytes(file_size));
+    println!("Time: {}", format_duration(elapsed.as_secs()));
+    if secs > 0.0 {
+        println!("Average Speed: {}/s", format_bytes((file_size as f64 / secs) as u64));
+    }
+}
+
+/// Refuse a download that would cut into `min_free_space`; without a reserve
+/// configured, only warn when the estimate exceeds the free space, since yt-dlp's
+/// sizes are sometimes approximate
+fn check_free_space(config: &Config, estimated: u64) -> Result<()> {
+    let Some(available) = available_space(&config.download_dir()) else {
+        return Ok(());
+    };
+
+    match config.min_free_space {
+        Some(reserve) => {
+            if free_space_shortfall(estimated, available, reserve).is_some() {
+                return Err(YtdlError::InsufficientDiskSpace {
+                    required: estimated.saturating_add(reserve),
+                    available,
+                });
+            }
+        }
+        None => {
+            if free_space_shortfall(estimated, available, 0).is_some() {
+                warn!("Estimated size {} exceeds free space {}", estimated, available);
+                say!(
+                    config.progress_json.unwrap_or(false),
+                    "{} This download needs about {} but only {} is free",
+                    "⚠".yellow().bold(),
+                    format_bytes(estimated),
+                    format_bytes(available)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
-use crate::shared::{constants::*, Result};
-use crate::core::history::History;
+use crate::shared::{constants::*, format_bytes, Result};
+use crate::core::history::{History, UsagePeriod, VerificationIssue};
 use colored::Colorize;
+use std::io::Write;
+use std::path::Path;
 
 pub async fn handle_history_command(
     history: &History,
@@ -42,7 +44,33 @@ pub async fn handle_history_command(
     Ok(())
 }
 
-pub async fn handle_clear_history_command(history: &mut History, older_than: Option<i64>) -> Result<()> {
+pub async fn handle_clear_history_command(
+    history: &mut History,
+    older_than: Option<i64>,
+    max_size: Option<u64>,
+    yes: bool,
+) -> Result<()> {
+    if history.is_empty() {
+        println!("\n{}", "No history entries found".yellow());
+        return Ok(());
+    }
+
+    let prompt = match (older_than, max_size) {
+        (Some(days), _) => format!("Clear history entries older than {} days?", days),
+        (None, Some(bytes)) => format!(
+            "Remove the oldest history entries until the total is under {}?",
+            format_bytes(bytes)
+        ),
+        (None, None) => format!("Clear all {} history entries?", history.len()),
+    };
+
+    if !yes && !confirm(&prompt)? {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    let backup_path = history.backup()?;
+
     if let Some(days) = older_than {
         history.clear_older_than(days);
         println!(
@@ -50,11 +78,194 @@ pub async fn handle_clear_history_command(history: &mut History, older_than: Opt
             "✓".green().bold(),
             days
         );
+    } else if let Some(bytes) = max_size {
+        let removed = history.prune_to_size(bytes);
+        println!(
+            "{} Removed {} oldest history entries to fit {}",
+            "✓".green().bold(),
+            removed,
+            format_bytes(bytes)
+        );
     } else {
         history.clear();
         println!("{} Cleared all history", "✓".green().bold());
     }
 
     history.save()?;
+    println!(
+        "Backup saved to {:?} (undo with: ytdl history --restore)",
+        backup_path
+    );
+    Ok(())
+}
+
+pub async fn handle_remove_history_command(history: &mut History, url: &str) -> Result<()> {
+    if !history.remove_by_url(url) {
+        println!("\n{} No history entry found for: {}", "⚠".yellow().bold(), url);
+        return Ok(());
+    }
+
+    history.save()?;
+    println!("{} Removed history entry for: {}", "✓".green().bold(), url);
+    Ok(())
+}
+
+pub async fn handle_verify_history_command(history: &mut History, prune_missing: bool) -> Result<()> {
+    let issues = history.verify_files();
+
+    if issues.is_empty() {
+        println!("{} All {} history entries verified", "✓".green().bold(), history.len());
+        return Ok(());
+    }
+
+    println!("\n{}", "History Verification Issues:".yellow().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+
+    for issue in &issues {
+        match issue {
+            VerificationIssue::Missing { url, file_path } => {
+                println!("\n{} Missing: {:?}", "✗".red(), file_path);
+                println!("  URL: {}", url);
+            }
+            VerificationIssue::SizeMismatch { url, file_path, expected, actual } => {
+                println!("\n{} Size mismatch: {:?}", "⚠".yellow(), file_path);
+                println!("  URL: {}", url);
+                println!("  Expected {} bytes, found {} bytes", expected, actual);
+            }
+        }
+    }
+
+    println!("\n{} of {} entries have issues", issues.len(), history.len());
+
+    if prune_missing {
+        let removed = history.remove_unverified(&issues);
+        history.save()?;
+        println!("{} Removed {} history entries", "✓".green().bold(), removed);
+    } else {
+        println!("Run with --prune-missing to remove them");
+    }
+
+    Ok(())
+}
+
+pub async fn handle_restore_history_command(history: &mut History) -> Result<()> {
+    *history = History::restore()?;
+    history.save()?;
+
+    println!(
+        "{} Restored {} history entries from backup",
+        "✓".green().bold(),
+        history.len()
+    );
+    Ok(())
+}
+
+pub async fn handle_merge_history_command(history: &mut History, path: &Path) -> Result<()> {
+    let other = History::load_from(path)?;
+    let incoming = other.len();
+
+    let before = history.clone();
+    let changed = history.merge(other);
+    let backup_path = if changed > 0 {
+        let backup_path = before.backup_before_edit()?;
+        history.save()?;
+        Some(backup_path)
+    } else {
+        None
+    };
+
+    println!(
+        "{} Merged {} of {} entries from {:?} ({} total)",
+        "✓".green().bold(),
+        changed,
+        incoming,
+        path,
+        history.len()
+    );
+    if let Some(backup_path) = backup_path {
+        println!("Backup saved to {:?}", backup_path);
+    }
+    Ok(())
+}
+
+pub async fn handle_dedupe_history_command(history: &mut History) -> Result<()> {
+    let before = history.clone();
+    let removed = history.deduplicate();
+
+    if removed == 0 {
+        println!("{} No duplicate history entries", "✓".green().bold());
+        return Ok(());
+    }
+
+    let backup_path = before.backup_before_edit()?;
+    history.save()?;
+    println!(
+        "{} Removed {} duplicate history entries ({} remaining)",
+        "✓".green().bold(),
+        removed,
+        history.len()
+    );
+    println!("Backup saved to {:?}", backup_path);
+    Ok(())
+}
+
+pub async fn handle_find_dupes_command(history: &History) -> Result<()> {
+    let groups = history.find_duplicates();
+
+    if groups.is_empty() {
+        println!("{} No duplicate files found", "✓".green().bold());
+        println!("Only downloads recorded with --hash can be compared");
+        return Ok(());
+    }
+
+    println!("\n{}", "Duplicate Files:".yellow().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+
+    for group in &groups {
+        let hash = group[0].sha256.as_deref().unwrap_or_default();
+        println!("\nSHA-256 {} ({})", &hash[..hash.len().min(16)], format_bytes(group[0].file_size));
+        for entry in group {
+            println!("  {} {:?}", "•".yellow(), entry.file_path);
+            println!("    {}", entry.url);
+        }
+    }
+
+    let extra: usize = groups.iter().map(|g| g.len() - 1).sum();
+    println!("\n{} groups, {} redundant files", groups.len(), extra);
     Ok(())
 }
+
+pub async fn handle_usage_command(history: &History, by_month: bool) -> Result<()> {
+    let period = if by_month { UsagePeriod::Month } else { UsagePeriod::Day };
+    let buckets = history.usage_by(period);
+
+    if buckets.is_empty() {
+        println!("{}", "No download history".yellow());
+        return Ok(());
+    }
+
+    println!("\n{}", "Bandwidth Usage:".green().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+
+    for (label, bytes) in &buckets {
+        println!("{:<12} {:>12}", label, format_bytes(*bytes));
+    }
+
+    let now = chrono::Utc::now();
+    let last_30_days = history.bytes_downloaded_between(now - chrono::Duration::days(30), now);
+    let total: u64 = buckets.iter().map(|(_, bytes)| bytes).sum();
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    println!("Last 30 days: {}", format_bytes(last_30_days));
+    println!("Total: {}", format_bytes(total));
+    Ok(())
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} {} ", prompt, "[y/N]".dimmed());
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -1,10 +1,44 @@
 use crate::cli::config::Config;
-use crate::infra::downloader::Downloader;
-use crate::shared::{constants::*, Result};
+use crate::infra::downloader::{sort_formats, Downloader, SortKey};
+use crate::shared::utils::expand_template;
+use crate::shared::{constants::*, Result, YtdlError};
 use colored::Colorize;
 
-pub async fn show_video_info(url: &str, config: &Config) -> Result<()> {
-    let downloader = Downloader::new(config.output_dir.clone(), config.quality.clone());
+/// Metadata fields `--print FIELD` can output on their own
+pub const PRINT_FIELDS: &[&str] = &["title", "uploader", "duration", "id", "upload_date", "view_count", "thumbnail"];
+
+/// `--print FIELD`: print one metadata field with no decoration and exit, for scripts.
+/// Missing values print as `NA`, as in yt-dlp.
+pub async fn print_video_field(url: &str, field: &str, config: &Config) -> Result<()> {
+    if !PRINT_FIELDS.contains(&field) {
+        return Err(YtdlError::Config(format!(
+            "Unknown field '{}' for --print. Valid fields: {}",
+            field,
+            PRINT_FIELDS.join(", ")
+        )));
+    }
+
+    let downloader = Downloader::new(config.output_dir.clone(), config.quality.clone())
+        .with_extra_args(config.extra_ytdlp_args.clone())
+        .with_cache_dir(config.cache_dir.clone())
+        .with_cookies(config.cookies_file.clone(), config.cookies_from_browser.clone());
+
+    let video_info = downloader.fetch_video_info(url).await?;
+    println!("{}", expand_template(&format!("%({})s", field), &video_info));
+
+    Ok(())
+}
+
+pub async fn show_video_info(
+    url: &str,
+    config: &Config,
+    sort_key: SortKey,
+    full_description: bool,
+) -> Result<()> {
+    let downloader = Downloader::new(config.output_dir.clone(), config.quality.clone())
+        .with_extra_args(config.extra_ytdlp_args.clone())
+        .with_cache_dir(config.cache_dir.clone())
+        .with_cookies(config.cookies_file.clone(), config.cookies_from_browser.clone());
 
     println!("\n{}", "Fetching video information...".green().bold());
     let video_info = downloader.fetch_video_info(url).await?;
@@ -40,7 +74,7 @@ pub async fn show_video_info(url: &str, config: &Config) -> Result<()> {
     }
 
     if let Some(description) = video_info.description {
-        let desc_preview = if description.len() > 200 {
+        let desc_preview = if !full_description && description.len() > 200 {
             format!("{}...", &description[..200])
         } else {
             description
@@ -49,22 +83,18 @@ pub async fn show_video_info(url: &str, config: &Config) -> Result<()> {
         println!("{}", desc_preview);
     }
 
+    if video_info.formats.is_empty() {
+        return Err(YtdlError::NoFormats(video_info.id));
+    }
+
     println!("\n{}", "Available Formats:".cyan().bold());
     println!("{}", SUBSEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
 
     let mut video_formats: Vec<_> = video_info.formats.iter()
         .filter(|f| f.vcodec.as_ref().map(|v| v != "none").unwrap_or(false))
+        .cloned()
         .collect();
-    video_formats.sort_by(|a, b| {
-        b.resolution.as_ref()
-            .and_then(|r| r.split('x').nth(1))
-            .and_then(|h| h.parse::<u32>().ok())
-            .unwrap_or(0)
-            .cmp(&a.resolution.as_ref()
-                .and_then(|r| r.split('x').nth(1))
-                .and_then(|h| h.parse::<u32>().ok())
-                .unwrap_or(0))
-    });
+    sort_formats(&mut video_formats, sort_key);
 
     for (i, format) in video_formats.iter().take(10).enumerate() {
         let res = format.resolution.as_ref().map(|s| s.as_str()).unwrap_or("unknown");
@@ -79,3 +109,19 @@ pub async fn show_video_info(url: &str, config: &Config) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_print_rejects_unknown_field() {
+        let err = print_video_field("https://youtu.be/abc", "likes", &Config::default())
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("'likes'"));
+        assert!(err.contains("title, uploader, duration, id, upload_date, view_count, thumbnail"));
+    }
+}
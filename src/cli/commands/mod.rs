@@ -1,11 +1,24 @@
+pub mod bench;
+pub mod cache;
 pub mod config;
 pub mod download;
 pub mod history;
 pub mod info;
 pub mod playlist;
+pub mod theme;
+pub mod validate;
 
+pub use bench::handle_bench_command;
+pub use cache::handle_cache_clear_command;
 pub use config::handle_config_command;
-pub use download::download_single_url;
-pub use history::{handle_clear_history_command, handle_history_command};
-pub use info::show_video_info;
-pub use playlist::handle_playlist_download;
+pub use download::{download_single_url, download_subtitles_only};
+pub use history::{
+    handle_clear_history_command, handle_dedupe_history_command, handle_find_dupes_command,
+    handle_history_command, handle_merge_history_command,
+    handle_remove_history_command, handle_restore_history_command, handle_usage_command,
+    handle_verify_history_command,
+};
+pub use info::{print_video_field, show_video_info};
+pub use playlist::{handle_archive_command, handle_list_command, handle_playlist_download};
+pub use theme::handle_theme_preview_command;
+pub use validate::handle_validate_command;
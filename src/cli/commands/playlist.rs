@@ -1,7 +1,12 @@
 use crate::cli::config::Config;
-use crate::core::playlist::PlaylistDownloader;
+use crate::core::playlist::{
+    channel_uploads_url, default_progress_dir, write_m3u, PlaylistDownloader, PlaylistInfo, PlaylistProgress, PlaylistResult,
+};
+use crate::shared::utils::{format_duration, sanitize_filename, truncate_to_width};
 use crate::shared::{constants::*, Result, YtdlError};
 use colored::Colorize;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 pub async fn handle_playlist_download(
@@ -9,13 +14,24 @@ pub async fn handle_playlist_download(
     config: &Config,
     range: Option<&str>,
     folder: Option<&str>,
+    metafile: Option<&Path>,
+    m3u: Option<Option<&Path>>,
+    stop_on_error: bool,
 ) -> Result<()> {
-    let mut playlist_downloader = PlaylistDownloader::new(config.output_dir.clone(), config.quality.clone());
+    let mut playlist_downloader = PlaylistDownloader::from_config(config)
+        .with_stop_on_error(stop_on_error)
+        .with_progress_dir(default_progress_dir());
 
     if let Some(folder_name) = folder {
         playlist_downloader = playlist_downloader.with_folder(folder_name.to_string());
     }
 
+    if config.write_playlist_metafiles.unwrap_or(false) {
+        if let Err(e) = playlist_downloader.write_playlist_metafiles(url).await {
+            println!("{} Failed to save playlist metafiles: {}", "⚠".yellow(), e);
+        }
+    }
+
     if let Some(range_str) = range {
         let parts: Vec<&str> = range_str.split('-').collect();
         if parts.len() == 2 {
@@ -27,15 +43,15 @@ pub async fn handle_playlist_download(
             })?;
 
             println!("\n{}", "Downloading playlist videos...".green().bold());
-            let paths = playlist_downloader
-                .download_range(url, start, end, config.audio_only)
-                .await?;
-
-            println!(
-                "\n{} Downloaded {} videos from playlist",
-                "✓".green().bold(),
-                paths.len()
-            );
+            let progress = PlaylistProgressView::new();
+            let result = playlist_downloader
+                .download_range(url, start, end, config.audio_only, progress.reporter())
+                .await;
+            progress.finish();
+            let result = result?;
+
+            print_playlist_summary(&result, metafile)?;
+            write_playlist_m3u(&result, m3u, config);
         } else {
             return Err(YtdlError::Config(
                 "Invalid range format. Use: --range 1-10".to_string(),
@@ -53,17 +69,267 @@ pub async fn handle_playlist_download(
         println!("Videos: {}", playlist_info.video_count);
 
         println!("\n{}", "Downloading all videos...".green().bold());
-        let paths = playlist_downloader
-            .download_playlist(&playlist_info, config.audio_only)
-            .await?;
+        let progress = PlaylistProgressView::new();
+        let result = playlist_downloader
+            .download_playlist(&playlist_info, config.audio_only, progress.reporter())
+            .await;
+        progress.finish();
+        let result = result?;
+
+        print_playlist_summary(&result, metafile)?;
+        write_playlist_m3u(&result, m3u, config);
+    }
+
+    Ok(())
+}
+
+/// `list`: print a playlist's videos without downloading, optionally exporting them
+pub async fn handle_list_command(url: &str, export: Option<&Path>, config: &Config) -> Result<()> {
+    let playlist_downloader = PlaylistDownloader::from_config(config);
+
+    println!("\n{}", "Fetching playlist...".green().bold());
+    let playlist_info = playlist_downloader.fetch_playlist_info(url).await?;
+
+    println!("\n{}", playlist_info.title.green().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    print!("{}", playlist_table(&playlist_info));
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    println!("{} videos", playlist_info.videos.len());
+
+    if let Some(path) = export {
+        playlist_info.export(path)?;
+        println!("{} Exported to {:?}", "✓".green().bold(), path);
+    }
+
+    Ok(())
+}
+
+/// Indexed table of title, duration and URL, one line per video
+fn playlist_table(info: &PlaylistInfo) -> String {
+    const TITLE_WIDTH: usize = 50;
+
+    let mut table = String::new();
+    for video in &info.videos {
+        let title = truncate_to_width(&video.title, TITLE_WIDTH);
+        let duration = video.duration.map(format_duration).unwrap_or_else(|| "--:--".to_string());
+        table.push_str(&format!(
+            "{:>4}  {:<width$}  {:>8}  {}\n",
+            video.index,
+            title,
+            duration,
+            video.url,
+            width = TITLE_WIDTH
+        ));
+    }
+    table
+}
+
+/// Archive a channel's uploads into a folder named after the channel. Downloads are
+/// recorded in a download archive there, so reruns only fetch new videos, and each
+/// video gets its info json and thumbnail.
+pub async fn handle_archive_command(url: &str, output: Option<&Path>, config: &Config) -> Result<()> {
+    let uploads_url = channel_uploads_url(url).ok_or_else(|| {
+        YtdlError::InvalidUrl(format!(
+            "Not a channel URL: {}\nExpected e.g. https://youtube.com/@name or https://youtube.com/channel/ID",
+            url
+        ))
+    })?;
+
+    let base_dir = output.map(Path::to_path_buf).unwrap_or_else(|| config.download_dir());
+    let channel_downloader = |dir: PathBuf| PlaylistDownloader::from_config(config).with_output_dir(dir);
+
+    println!("\n{}", "Fetching channel uploads...".green().bold());
+    let playlist_info = channel_downloader(base_dir.clone())
+        .fetch_playlist_info(&uploads_url)
+        .await?;
+
+    let channel = playlist_info
+        .uploader
+        .clone()
+        .unwrap_or_else(|| playlist_info.title.trim_end_matches(" - Videos").to_string());
+    let channel_dir = base_dir.join(sanitize_filename(&channel, false));
+    let archive = channel_dir.join(DOWNLOAD_ARCHIVE_FILE);
+    info!("Archiving {} into {:?}", channel, channel_dir);
+
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    println!("Channel: {}", channel);
+    println!("Videos: {}", playlist_info.video_count);
+    println!("Folder: {:?}", channel_dir);
+
+    let progress = PlaylistProgressView::new();
+    let result = channel_downloader(channel_dir)
+        .with_download_archive(archive)
+        .with_video_metafiles(true)
+        .download_playlist(&playlist_info, config.audio_only, progress.reporter())
+        .await;
+    progress.finish();
+    let result = result?;
+
+    print_playlist_summary(&result, None)
+}
+
+/// Live `[3/50] Downloading: Title (42%)` view: the playlist position on one
+/// bar and the current video's progress on another
+struct PlaylistProgressView {
+    overall: ProgressBar,
+    current: ProgressBar,
+}
+
+impl PlaylistProgressView {
+    fn new() -> Self {
+        let bars = MultiProgress::new();
+
+        let overall = bars.add(ProgressBar::new(0));
+        overall.set_style(
+            ProgressStyle::default_bar()
+                .template("{spinner:.green} Playlist [{bar:40.cyan/blue}] {pos}/{len}")
+                .unwrap()
+                .progress_chars("#>-"),
+        );
+        overall.enable_steady_tick(std::time::Duration::from_millis(100));
+
+        let current = bars.add(ProgressBar::new(100));
+        current.set_style(
+            ProgressStyle::default_bar()
+                .template("  {msg} ({pos}%)")
+                .unwrap(),
+        );
+
+        Self { overall, current }
+    }
 
+    /// Callback for `download_playlist`
+    fn reporter(&self) -> impl Fn(PlaylistProgress) + Send + Sync + 'static {
+        let overall = self.overall.clone();
+        let current = self.current.clone();
+        move |progress| {
+            overall.set_length(progress.total as u64);
+            overall.set_position(progress.position.saturating_sub(1) as u64);
+            current.set_message(format!(
+                "[{}/{}] Downloading: {}",
+                progress.position,
+                progress.total,
+                truncate_to_width(&progress.title, 50)
+            ));
+            current.set_position(progress.download.percentage.clamp(0.0, 100.0) as u64);
+        }
+    }
+
+    fn finish(&self) {
+        self.current.finish_and_clear();
+        self.overall.finish_and_clear();
+    }
+}
+
+/// `--write-m3u`: write the downloaded files to `path`, or to `<playlist title>.m3u`
+/// in the download directory when no path was given
+fn write_playlist_m3u(result: &PlaylistResult, m3u: Option<Option<&Path>>, config: &Config) {
+    let Some(path) = m3u else {
+        return;
+    };
+    if result.succeeded.is_empty() {
+        return;
+    }
+
+    let path = path.map(Path::to_path_buf).unwrap_or_else(|| {
+        let name = sanitize_filename(&result.playlist_title, config.ascii_names.unwrap_or(false));
+        config.download_dir().join(format!("{}.m3u", name))
+    });
+
+    match write_m3u(&result.succeeded, &result.succeeded_titles, &path) {
+        Ok(()) => println!("{} M3U playlist written to {:?}", "✓".green().bold(), path),
+        Err(e) => println!("{} Failed to write M3U playlist: {}", "⚠".yellow(), e),
+    }
+}
+
+fn print_playlist_summary(result: &PlaylistResult, metafile: Option<&Path>) -> Result<()> {
+    println!(
+        "\n{} Downloaded {} of {} videos",
+        "✓".green().bold(),
+        result.succeeded.len(),
+        result.total()
+    );
+
+    if result.skipped > 0 {
+        println!("  Skipped {} already archived", result.skipped);
+    }
+
+    if result.filtered > 0 {
+        println!("  Skipped {} outside the duration limits or yt-dlp filters", result.filtered);
+    }
+
+    if result.resumed > 0 {
         println!(
-            "\n{} Downloaded {} of {} videos",
+            "  Skipped {} finished by an earlier run (--force-download downloads them again)",
+            result.resumed
+        );
+    }
+
+    if result.aborted {
+        println!(
+            "{} Stopped at the first failure (--stop-on-error); remaining videos were not downloaded",
+            "⚠".yellow().bold()
+        );
+    }
+
+    if result.failed.is_empty() {
+        return Ok(());
+    }
+
+    println!("\n{}", "Failed Downloads:".red().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    for (video, error) in &result.failed {
+        println!("{}. {}", video.index, video.title);
+        println!("   URL: {}", video.url);
+        println!("   Error: {}", error);
+    }
+
+    if let Some(path) = metafile {
+        result.write_failed_urls(path)?;
+        info!("Wrote {} failed URLs to {:?}", result.failed.len(), path);
+        println!(
+            "\n{} Failed URLs written to {:?} (retry with: ytdl --batch {})",
             "✓".green().bold(),
-            paths.len(),
-            playlist_info.video_count
+            path,
+            path.display()
         );
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::playlist::PlaylistVideo;
+
+    #[test]
+    fn test_playlist_table() {
+        let video = |index: usize, title: &str, duration: Option<u64>| PlaylistVideo {
+            url: format!("https://youtube.com/watch?v={}", index),
+            title: title.to_string(),
+            duration,
+            view_count: None,
+            index,
+            selected: true,
+        };
+        let info = PlaylistInfo {
+            id: "PLtest".to_string(),
+            title: "Mix".to_string(),
+            uploader: None,
+            video_count: 2,
+            videos: vec![video(1, "Intro", Some(3725)), video(12, &"x".repeat(60), None)],
+        };
+
+        let table = playlist_table(&info);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            format!("   1  {:<50}  01:02:05  https://youtube.com/watch?v=1", "Intro")
+        );
+        assert_eq!(
+            lines[1],
+            format!("  12  {}…     --:--  https://youtube.com/watch?v=12", "x".repeat(49))
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::shared::{constants::*, Result};
+use crate::tui::{terminal_supports_unicode, Theme};
+use colored::{Color as TermColor, Colorize};
+use ratatui::style::Color;
+use std::path::Path;
+
+pub async fn handle_theme_preview_command(theme_path: Option<&Path>) -> Result<()> {
+    let theme = match theme_path {
+        Some(path) => Theme::load(path)?,
+        None => Theme::default(),
+    };
+    let ascii = theme.ascii || !terminal_supports_unicode();
+    let theme = theme.with_ascii(ascii);
+
+    print!("{}", render_theme_preview(&theme));
+    Ok(())
+}
+
+/// A static sample of how each TUI screen uses the theme's colors
+fn render_theme_preview(theme: &Theme) -> String {
+    let color = term_color(theme.color);
+    let paint = |text: &str| text.color(color);
+    let heading = |text: &str| text.color(color).bold();
+    let label = |name: &str, value: &str| format!("  {}{}\n", heading(name), paint(value));
+    let rule = paint(&theme.glyph("─", "-").repeat(SEPARATOR_WIDTH)).to_string();
+
+    let mut preview = String::new();
+    let mut section = |title: &str, body: String| {
+        preview.push_str(&format!("{}\n{}\n{}\n\n", heading(title), rule, body));
+    };
+
+    section(
+        "URL Input",
+        format!(
+            "  {}\n  {}\n",
+            paint("Enter YouTube URL:"),
+            paint(&format!("{} https://youtube.com/watch?v=dQw4w9WgXcQ", theme.glyph("›", ">")))
+        ),
+    );
+
+    let selected = format!(" {} 1080p  mp4  ~120 MB ", theme.glyph("▶", ">"))
+        .color(color)
+        .bold()
+        .on_truecolor(160, 160, 160);
+    section(
+        "Select Format",
+        format!(
+            "{}{}{}  {}\n    {}\n",
+            label("Title: ", "Sample Video"),
+            label("Uploader: ", "Sample Channel"),
+            label("Duration: ", "03:32"),
+            selected,
+            paint("720p  mp4  ~60 MB"),
+        ),
+    );
+
+    let (full, empty) = (theme.glyph("█", "#"), theme.glyph("░", "-"));
+    section(
+        "Downloading",
+        format!(
+            "  {}{} {}\n{}",
+            paint(&full.repeat(17)),
+            paint(&empty.repeat(23)),
+            heading("42%"),
+            label("Speed: ", "2.4 MB/s"),
+        ),
+    );
+
+    section(
+        "Download Complete!",
+        format!("  {}\n{}", heading(theme.glyph("✓", "[OK]")), label("Filename: ", "Sample Video.mp4")),
+    );
+
+    section(
+        "Error Occurred",
+        format!("  {}\n{}", heading(theme.glyph("✗", "[X]")), label("Type: ", "Network error")),
+    );
+
+    preview
+}
+
+/// The `colored` equivalent of a ratatui color
+fn term_color(color: Color) -> TermColor {
+    match color {
+        Color::Rgb(r, g, b) => TermColor::TrueColor { r, g, b },
+        Color::Black => TermColor::Black,
+        Color::Red => TermColor::Red,
+        Color::Green => TermColor::Green,
+        Color::Yellow => TermColor::Yellow,
+        Color::Blue => TermColor::Blue,
+        Color::Magenta => TermColor::Magenta,
+        Color::Cyan => TermColor::Cyan,
+        Color::DarkGray => TermColor::BrightBlack,
+        Color::LightRed => TermColor::BrightRed,
+        Color::LightGreen => TermColor::BrightGreen,
+        Color::LightYellow => TermColor::BrightYellow,
+        Color::LightBlue => TermColor::BrightBlue,
+        Color::LightMagenta => TermColor::BrightMagenta,
+        Color::LightCyan => TermColor::BrightCyan,
+        Color::White => TermColor::BrightWhite,
+        Color::Gray | Color::Indexed(_) | Color::Reset => TermColor::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_default_theme() {
+        for theme in [Theme::default(), Theme::default().with_ascii(true)] {
+            let preview = render_theme_preview(&theme);
+            for screen in ["URL Input", "Select Format", "Downloading", "Download Complete!", "Error Occurred"] {
+                assert!(preview.contains(screen), "missing {}", screen);
+            }
+        }
+        assert!(render_theme_preview(&Theme::default().with_ascii(true)).is_ascii());
+    }
+}
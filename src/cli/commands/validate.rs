@@ -0,0 +1,59 @@
+use crate::core::{validate_batch_file, BatchFormat, EntryStatus, History};
+use crate::shared::constants::{SEPARATOR_LINE, SEPARATOR_WIDTH};
+use crate::shared::{Result, YtdlError};
+use colored::Colorize;
+use std::path::Path;
+
+/// Check a batch file without downloading anything. Fails when any URL is invalid,
+/// so it can gate a CI job.
+pub async fn handle_validate_command(path: &Path, format: Option<BatchFormat>, history: &History) -> Result<()> {
+    let checks = validate_batch_file(path, format, history).await?;
+
+    println!("\n{} {}", "Validating".green().bold(), path.display());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+
+    let (mut valid, mut invalid, mut duplicates, mut in_history) = (0, 0, 0, 0);
+    for check in &checks {
+        match check.status {
+            EntryStatus::Valid => {
+                valid += 1;
+                println!("{} {:>4}  {}", "✓".green(), check.entry, check.url);
+            }
+            EntryStatus::Invalid(ref reason) => {
+                invalid += 1;
+                println!("{} {:>4}  {}", "✗".red(), check.entry, check.url);
+                println!("        {}", reason.red());
+            }
+            EntryStatus::Duplicate(first) => {
+                duplicates += 1;
+                println!("{} {:>4}  {}", "⚠".yellow(), check.entry, check.url);
+                println!("        {}", format!("Duplicate of entry {}", first).yellow());
+            }
+            EntryStatus::InHistory => {
+                in_history += 1;
+                println!("{} {:>4}  {}", "↺".cyan(), check.entry, check.url);
+                println!("        {}", "Already in history".cyan());
+            }
+        }
+    }
+
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    println!(
+        "{} valid, {} invalid, {} duplicated, {} already in history",
+        valid.to_string().green(),
+        invalid.to_string().red(),
+        duplicates.to_string().yellow(),
+        in_history.to_string().cyan()
+    );
+
+    if invalid > 0 {
+        return Err(YtdlError::InvalidUrl(format!(
+            "{} of {} entries in {}",
+            invalid,
+            checks.len(),
+            path.display()
+        )));
+    }
+
+    Ok(())
+}
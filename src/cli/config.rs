@@ -1,8 +1,11 @@
+use crate::infra::{play_completion_sound, sanitize_downloaded_file};
+use crate::shared::utils::{combine_match_filter, expand_path, sanitize_filename, sha256_file, within_duration_limits, within_filesize_limits, within_view_limits};
+use crate::shared::constants::DEFAULT_PLAYER_CLIENT_FALLBACKS;
 use crate::shared::{Result, YtdlError};
 use directories::ProjectDirs;
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use tracing::{debug, info};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -13,8 +16,16 @@ pub struct Config {
     pub enable_file_logging: bool,
     pub enable_json_logging: bool,
     pub concurrent_downloads: Option<usize>,
+    /// Stop a batch or playlist at the first failed video; `--stop-on-error` and
+    /// `--ignore-errors` override it
+    pub stop_on_error: Option<bool>,
     pub audio_format: Option<String>,
-    pub audio_quality: Option<u32>,
+    /// yt-dlp `--audio-quality` for audio-only downloads: 0 (best) to 10 (worst) VBR,
+    /// or a constant bitrate such as "192K"
+    #[serde(default, deserialize_with = "deserialize_audio_quality")]
+    pub audio_quality: Option<String>,
+    /// Keep the original audio codec instead of converting to `audio_format`
+    pub audio_copy: Option<bool>,
     pub theme: Option<String>,
     pub show_animations: Option<bool>,
     pub confirm_before_download: Option<bool>,
@@ -23,6 +34,89 @@ pub struct Config {
     pub timeout: Option<u64>,
     pub use_cookies: Option<bool>,
     pub skip_duplicates: Option<bool>,
+    pub notifications: Option<bool>,
+    #[serde(default)]
+    pub mirror_dirs: Vec<PathBuf>,
+    pub sanitize_names: Option<bool>,
+    pub ascii_names: Option<bool>,
+    pub match_filter: Option<String>,
+    pub age_limit: Option<u32>,
+    /// Skip videos shorter/longer than this many seconds
+    pub min_duration: Option<u64>,
+    pub max_duration: Option<u64>,
+    /// Skip videos with fewer/more views than this
+    pub min_views: Option<u64>,
+    pub max_views: Option<u64>,
+    /// Skip files smaller/larger than this many bytes
+    pub min_filesize: Option<u64>,
+    pub max_filesize: Option<u64>,
+    pub term_progress: Option<bool>,
+    #[serde(default)]
+    pub extra_ytdlp_args: Vec<String>,
+    pub cleanup_partials: Option<bool>,
+    pub quality_subdirs: Option<bool>,
+    pub download_sections: Option<String>,
+    pub prefer_free_formats: Option<bool>,
+    /// Name files `<id>.<ext>` instead of `<title>.<ext>`
+    pub id_filename: Option<bool>,
+    pub write_description: Option<bool>,
+    pub write_comments: Option<bool>,
+    pub embed_chapters: Option<bool>,
+    /// yt-dlp `--parse-metadata` pattern used to tag downloads
+    pub parse_metadata: Option<String>,
+    /// Container video downloads are transcoded to (yt-dlp `--recode-video`)
+    pub recode_video: Option<String>,
+    /// Subtitle languages for `--subs-only` (yt-dlp `--sub-langs`), e.g. "en,de"
+    pub sub_langs: Option<String>,
+    /// yt-dlp `--cache-dir`; `None` uses yt-dlp's default cache location
+    pub cache_dir: Option<PathBuf>,
+    /// Where partial downloads are written before moving to `output_dir`
+    pub temp_dir: Option<PathBuf>,
+    /// yt-dlp `--sleep-interval`/`--max-sleep-interval`, seconds before each download
+    pub sleep_interval: Option<f64>,
+    pub max_sleep_interval: Option<f64>,
+    /// yt-dlp `--sleep-requests`, seconds between metadata requests
+    pub sleep_requests: Option<f64>,
+    /// Netscape-format cookies file passed to yt-dlp `--cookies`
+    pub cookies_file: Option<PathBuf>,
+    /// Browser to read cookies from (yt-dlp `--cookies-from-browser`), e.g. `firefox`
+    pub cookies_from_browser: Option<String>,
+    pub skip_metadata: Option<bool>,
+    /// Record a SHA-256 of each download in history (reads the whole file)
+    pub hash_downloads: Option<bool>,
+    /// Bytes that must stay free after a download; downloads whose estimated size
+    /// would cut into this are refused. Unset only warns when the disk looks too full.
+    pub min_free_space: Option<u64>,
+    /// YouTube player clients to retry with after an HTTP 403; empty disables the retry
+    pub player_client_fallbacks: Option<Vec<String>>,
+    pub completion_sound: Option<PathBuf>,
+    pub bell: Option<bool>,
+    pub index_prefix: Option<bool>,
+    pub write_playlist_metafiles: Option<bool>,
+    pub no_part: Option<bool>,
+    /// `Some(true)`: yt-dlp `--force-overwrites`, `Some(false)`: `--no-overwrites`
+    pub overwrites: Option<bool>,
+    /// Don't show download progress in the terminal window title
+    pub no_title: Option<bool>,
+    /// JSON progress lines on stdout instead of the progress bar
+    pub progress_json: Option<bool>,
+    /// Endpoint that receives a JSON POST on download start, progress, completion and failure
+    pub webhook_url: Option<String>,
+    /// NDJSON file that download started/progress/completed/failed events are appended to
+    pub events_file: Option<PathBuf>,
+    /// TUI spinner: "braille", "dots" or "line"
+    pub spinner_style: Option<String>,
+    /// Spinner frame duration; the style's own speed when unset
+    pub spinner_speed_ms: Option<u64>,
+    /// Per-invocation `--sponsorblock-export` target, never read from or saved to the config file
+    #[serde(skip)]
+    pub sponsorblock_export: Option<PathBuf>,
+    /// Per-invocation `--print` template, never read from or saved to the config file
+    #[serde(skip)]
+    pub print_template: Option<String>,
+    /// `--simulate-slow` test mode, never read from or saved to the config file
+    #[serde(skip)]
+    pub simulate_slow: bool,
 }
 
 impl Default for Config {
@@ -35,8 +129,10 @@ impl Default for Config {
             enable_file_logging: false,
             enable_json_logging: false,
             concurrent_downloads: Some(3),
+            stop_on_error: Some(false),
             audio_format: Some("mp3".to_string()),
-            audio_quality: Some(192),
+            audio_quality: None,
+            audio_copy: Some(false),
             theme: Some("dark".to_string()),
             show_animations: Some(true),
             confirm_before_download: Some(false),
@@ -45,6 +141,59 @@ impl Default for Config {
             timeout: Some(300),
             use_cookies: Some(false),
             skip_duplicates: Some(true),
+            notifications: Some(true),
+            mirror_dirs: Vec::new(),
+            sanitize_names: Some(false),
+            ascii_names: Some(false),
+            match_filter: None,
+            age_limit: None,
+            min_duration: None,
+            max_duration: None,
+            min_views: None,
+            max_views: None,
+            min_filesize: None,
+            max_filesize: None,
+            term_progress: Some(false),
+            extra_ytdlp_args: Vec::new(),
+            cleanup_partials: Some(false),
+            quality_subdirs: Some(false),
+            download_sections: None,
+            prefer_free_formats: Some(false),
+            id_filename: Some(false),
+            write_description: Some(false),
+            write_comments: Some(false),
+            embed_chapters: Some(false),
+            parse_metadata: None,
+            recode_video: None,
+            sub_langs: None,
+            cache_dir: None,
+            temp_dir: None,
+            sleep_interval: None,
+            max_sleep_interval: None,
+            sleep_requests: None,
+            cookies_file: None,
+            cookies_from_browser: None,
+            skip_metadata: Some(false),
+            hash_downloads: Some(false),
+            min_free_space: None,
+            player_client_fallbacks: Some(
+                DEFAULT_PLAYER_CLIENT_FALLBACKS.iter().map(|c| c.to_string()).collect(),
+            ),
+            completion_sound: None,
+            bell: Some(false),
+            index_prefix: Some(false),
+            write_playlist_metafiles: Some(false),
+            no_part: Some(false),
+            overwrites: None,
+            no_title: Some(false),
+            progress_json: Some(false),
+            webhook_url: None,
+            events_file: None,
+            spinner_style: Some("braille".to_string()),
+            spinner_speed_ms: None,
+            sponsorblock_export: None,
+            print_template: None,
+            simulate_slow: false,
         }
     }
 }
@@ -59,19 +208,61 @@ impl Config {
             YtdlError::Config(format!("Failed to read config file: {}", e))
         })?;
 
-        let config: Config = toml::from_str(&content).map_err(|e| {
+        let mut config: Config = toml::from_str(&content).map_err(|e| {
             YtdlError::Config(format!("Failed to parse config file: {}", e))
         })?;
+        config.expand_paths();
 
         debug!("Loaded configuration from file: {:?}", path);
         Ok(config)
     }
 
+    /// Expand `~` and `$VAR` in the paths read from the config file
+    fn expand_paths(&mut self) {
+        let expand = |path: &Path| expand_path(&path.to_string_lossy());
+
+        self.output_dir = expand(&self.output_dir);
+        self.mirror_dirs = self.mirror_dirs.iter().map(|dir| expand(dir)).collect();
+        for path in [&mut self.cache_dir, &mut self.temp_dir, &mut self.cookies_file, &mut self.completion_sound]
+            .into_iter()
+            .flatten()
+        {
+            *path = expand(path);
+        }
+    }
+
+    /// Load from the default config path if it exists, otherwise defaults with env overrides
+    pub fn load_default() -> Result<Self> {
+        match Self::get_default_config_path() {
+            Some(path) if path.exists() => Self::load_from_file(&path),
+            _ => Ok(Self::load_with_env_overrides()),
+        }
+    }
+
+    pub fn save_to_file(&self, path: &PathBuf) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                YtdlError::Config(format!("Failed to create config directory: {}", e))
+            })?;
+        }
+
+        let content = toml::to_string_pretty(self).map_err(|e| {
+            YtdlError::Config(format!("Failed to serialize config: {}", e))
+        })?;
+
+        std::fs::write(path, content).map_err(|e| {
+            YtdlError::Config(format!("Failed to write config file: {}", e))
+        })?;
+
+        info!("Saved configuration to: {:?}", path);
+        Ok(())
+    }
+
     pub fn load_with_env_overrides() -> Self {
         let mut config = Self::default();
 
         if let Ok(dir) = std::env::var("YTDL_OUTPUT_DIR") {
-            config.output_dir = PathBuf::from(dir);
+            config.output_dir = expand_path(&dir);
             debug!("Overriding output_dir from YTDL_OUTPUT_DIR");
         }
 
@@ -106,6 +297,15 @@ impl Config {
             self.audio_only = true;
         }
 
+        if let Some(audio_quality) = cli_config.audio_quality {
+            self.audio_quality = Some(audio_quality);
+        }
+
+        if cli_config.audio_copy {
+            self.audio_only = true;
+            self.audio_copy = Some(true);
+        }
+
         if cli_config.enable_file_logging {
             self.enable_file_logging = true;
         }
@@ -113,6 +313,291 @@ impl Config {
         if cli_config.enable_json_logging {
             self.enable_json_logging = true;
         }
+
+        self.mirror_dirs.extend(cli_config.mirror_dirs);
+
+        if cli_config.sanitize_names {
+            self.sanitize_names = Some(true);
+        }
+
+        if cli_config.ascii_names {
+            self.sanitize_names = Some(true);
+            self.ascii_names = Some(true);
+        }
+
+        if let Some(filter) = cli_config.match_filter {
+            self.match_filter = Some(filter);
+        }
+
+        if let Some(age) = cli_config.age_limit {
+            self.age_limit = Some(age);
+        }
+
+        if let Some(min) = cli_config.min_duration {
+            self.min_duration = Some(min);
+        }
+
+        if let Some(max) = cli_config.max_duration {
+            self.max_duration = Some(max);
+        }
+
+        if let Some(min) = cli_config.min_views {
+            self.min_views = Some(min);
+        }
+
+        if let Some(max) = cli_config.max_views {
+            self.max_views = Some(max);
+        }
+
+        if let Some(min) = cli_config.min_filesize {
+            self.min_filesize = Some(min);
+        }
+
+        if let Some(max) = cli_config.max_filesize {
+            self.max_filesize = Some(max);
+        }
+
+        if cli_config.term_progress {
+            self.term_progress = Some(true);
+        }
+
+        self.extra_ytdlp_args.extend(cli_config.extra_ytdlp_args);
+
+        if cli_config.cleanup_partials {
+            self.cleanup_partials = Some(true);
+        }
+
+        if cli_config.quality_subdirs {
+            self.quality_subdirs = Some(true);
+        }
+
+        if let Some(section) = cli_config.download_sections {
+            self.download_sections = Some(section);
+        }
+
+        if cli_config.prefer_free_formats {
+            self.prefer_free_formats = Some(true);
+        }
+
+        if cli_config.id_filename {
+            self.id_filename = Some(true);
+        }
+
+        if cli_config.write_description {
+            self.write_description = Some(true);
+        }
+
+        if cli_config.write_comments {
+            self.write_comments = Some(true);
+        }
+
+        if cli_config.embed_chapters {
+            self.embed_chapters = Some(true);
+        }
+
+        if let Some(pattern) = cli_config.parse_metadata {
+            self.parse_metadata = Some(pattern);
+        }
+
+        if let Some(format) = cli_config.recode_video {
+            self.recode_video = Some(format);
+        }
+
+        if let Some(langs) = cli_config.sub_langs {
+            self.sub_langs = Some(langs);
+        }
+
+        if let Some(path) = cli_config.events_file {
+            self.events_file = Some(path);
+        }
+
+        if let Some(dir) = cli_config.cache_dir {
+            self.cache_dir = Some(dir);
+        }
+
+        if let Some(dir) = cli_config.temp_dir {
+            self.temp_dir = Some(dir);
+        }
+
+        if let Some(file) = cli_config.cookies_file {
+            self.cookies_file = Some(file);
+        }
+
+        if let Some(browser) = cli_config.cookies_from_browser {
+            self.cookies_from_browser = Some(browser);
+        }
+
+        if let Some(secs) = cli_config.sleep_interval {
+            self.sleep_interval = Some(secs);
+        }
+
+        if let Some(secs) = cli_config.max_sleep_interval {
+            self.max_sleep_interval = Some(secs);
+        }
+
+        if let Some(secs) = cli_config.sleep_requests {
+            self.sleep_requests = Some(secs);
+        }
+
+        if cli_config.skip_metadata {
+            self.skip_metadata = Some(true);
+        }
+
+        if cli_config.hash_downloads {
+            self.hash_downloads = Some(true);
+        }
+
+        if let Some(clients) = cli_config.player_client_fallbacks {
+            self.player_client_fallbacks = Some(clients);
+        }
+
+        if cli_config.bell {
+            self.bell = Some(true);
+        }
+
+        if cli_config.index_prefix {
+            self.index_prefix = Some(true);
+        }
+
+        if cli_config.write_playlist_metafiles {
+            self.write_playlist_metafiles = Some(true);
+        }
+
+        if cli_config.no_part {
+            self.no_part = Some(true);
+        }
+
+        if cli_config.force_download {
+            self.overwrites = Some(true);
+        } else if cli_config.no_overwrites {
+            self.overwrites = Some(false);
+        }
+
+        if cli_config.no_title {
+            self.no_title = Some(true);
+        }
+
+        if cli_config.progress_json {
+            self.progress_json = Some(true);
+        }
+
+        if let Some(path) = cli_config.sponsorblock_export {
+            self.sponsorblock_export = Some(path);
+        }
+
+        if let Some(template) = cli_config.print_template {
+            self.print_template = Some(template);
+        }
+
+        if cli_config.simulate_slow {
+            self.simulate_slow = true;
+        }
+
+        if let Some(stop) = cli_config.stop_on_error {
+            self.stop_on_error = Some(stop);
+        }
+    }
+
+    /// Directory downloads are written to: `output_dir`, plus a per-quality
+    /// subdirectory (`1080`, `best`, `audio`) when `quality_subdirs` is set
+    pub fn download_dir(&self) -> PathBuf {
+        if !self.quality_subdirs.unwrap_or(false) {
+            return self.output_dir.clone();
+        }
+
+        let subdir = if self.audio_only { "audio" } else { self.quality.as_str() };
+        self.output_dir.join(sanitize_filename(subdir, false))
+    }
+
+    /// Whether FFmpeg is required rather than recommended: cutting sections and
+    /// recoding video are done by it. Audio-only downloads don't recode.
+    pub fn requires_ffmpeg(&self) -> bool {
+        self.download_sections.is_some() || (self.recode_video.is_some() && !self.audio_only)
+    }
+
+    /// Format recorded in history: the audio format (or, with `audio_copy`, the
+    /// extension yt-dlp kept), the recode target, or mp4
+    pub fn history_format(&self, path: &Path) -> String {
+        if self.audio_only && self.audio_copy.unwrap_or(false) {
+            path.extension()
+                .map(|ext| ext.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "best".to_string())
+        } else if self.audio_only {
+            self.audio_format.clone().unwrap_or_else(|| "mp3".to_string())
+        } else {
+            self.recode_video.clone().unwrap_or_else(|| "mp4".to_string())
+        }
+    }
+
+    /// `embed_chapters` can't be combined with yt-dlp's `--split-chapters` (passed
+    /// through `extra_ytdlp_args`): split files no longer match the chapter list
+    pub fn validate_chapter_options(&self) -> Result<()> {
+        let splits = self.extra_ytdlp_args.iter().any(|arg| arg == "--split-chapters");
+        if self.embed_chapters.unwrap_or(false) && splits {
+            return Err(YtdlError::Config(
+                "--embed-chapters can't be combined with --split-chapters; use one or the other".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// `match_filter` extended with the duration and view count limits, for yt-dlp
+    pub fn effective_match_filter(&self) -> Option<String> {
+        combine_match_filter(
+            self.match_filter.as_deref(),
+            (self.min_duration, self.max_duration),
+            (self.min_views, self.max_views),
+        )
+    }
+
+    /// Local check of a known duration against `min_duration`/`max_duration`
+    pub fn fits_duration_limits(&self, duration: Option<u64>) -> bool {
+        within_duration_limits(duration, self.min_duration, self.max_duration)
+    }
+
+    /// Local check of a known view count against `min_views`/`max_views`
+    pub fn fits_view_limits(&self, views: Option<u64>) -> bool {
+        within_view_limits(views, self.min_views, self.max_views)
+    }
+
+    /// Local check of an estimated file size against `min_filesize`/`max_filesize`
+    pub fn fits_filesize_limits(&self, size: Option<u64>) -> bool {
+        within_filesize_limits(size, self.min_filesize, self.max_filesize)
+    }
+
+    /// SHA-256 of a finished download when `hash_downloads` is set
+    pub async fn content_hash(&self, path: &Path) -> Option<String> {
+        if !self.hash_downloads.unwrap_or(false) {
+            return None;
+        }
+
+        match sha256_file(path).await {
+            Ok(hash) => Some(hash),
+            Err(e) => {
+                warn!("Failed to hash {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Completion sound or terminal bell, per `completion_sound`/`bell`
+    pub fn play_completion_sound(&self) {
+        play_completion_sound(self.completion_sound.as_deref(), self.bell.unwrap_or(false));
+    }
+
+    /// Rename a finished download per `sanitize_names`/`ascii_names`, returning the final path
+    pub async fn apply_filename_policy(&self, path: PathBuf) -> PathBuf {
+        if !self.sanitize_names.unwrap_or(false) {
+            return path;
+        }
+
+        match sanitize_downloaded_file(&path, self.ascii_names.unwrap_or(false)).await {
+            Ok(renamed) => renamed,
+            Err(e) => {
+                warn!("Failed to sanitize filename {:?}: {}", path, e);
+                path
+            }
+        }
     }
 
     pub fn get_default_config_path() -> Option<PathBuf> {
@@ -129,8 +614,33 @@ impl Config {
         info!("  File logging: {}", self.enable_file_logging);
         info!("  JSON logging: {}", self.enable_json_logging);
         info!("  Concurrent downloads: {:?}", self.concurrent_downloads);
+        info!("  Stop on error: {:?}", self.stop_on_error);
         info!("  Skip duplicates: {:?}", self.skip_duplicates);
+        info!("  Mirror directories: {:?}", self.mirror_dirs);
+        info!("  Match filter: {:?}", self.match_filter);
+        info!("  Age limit: {:?}", self.age_limit);
+        info!("  Duration limits: {:?}..{:?}", self.min_duration, self.max_duration);
+        info!("  View limits: {:?}..{:?}", self.min_views, self.max_views);
+        info!("  File size limits: {:?}..{:?}", self.min_filesize, self.max_filesize);
+    }
+}
+
+/// Accept `audio_quality` as a string or, as older config files stored it, a number
+fn deserialize_audio_quality<'de, D>(deserializer: D) -> std::result::Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AudioQuality {
+        Number(u32),
+        Text(String),
     }
+
+    Ok(Option::<AudioQuality>::deserialize(deserializer)?.map(|quality| match quality {
+        AudioQuality::Number(n) => n.to_string(),
+        AudioQuality::Text(s) => s,
+    }))
 }
 
 #[derive(Debug, Default)]
@@ -140,6 +650,56 @@ pub struct CliConfig {
     pub audio_only: bool,
     pub enable_file_logging: bool,
     pub enable_json_logging: bool,
+    pub mirror_dirs: Vec<PathBuf>,
+    pub sanitize_names: bool,
+    pub ascii_names: bool,
+    pub match_filter: Option<String>,
+    pub age_limit: Option<u32>,
+    pub min_duration: Option<u64>,
+    pub max_duration: Option<u64>,
+    pub min_views: Option<u64>,
+    pub max_views: Option<u64>,
+    pub min_filesize: Option<u64>,
+    pub max_filesize: Option<u64>,
+    pub term_progress: bool,
+    pub extra_ytdlp_args: Vec<String>,
+    pub cleanup_partials: bool,
+    pub quality_subdirs: bool,
+    pub download_sections: Option<String>,
+    pub prefer_free_formats: bool,
+    pub id_filename: bool,
+    pub write_description: bool,
+    pub write_comments: bool,
+    pub embed_chapters: bool,
+    pub parse_metadata: Option<String>,
+    pub recode_video: Option<String>,
+    pub sub_langs: Option<String>,
+    pub events_file: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+    pub temp_dir: Option<PathBuf>,
+    pub sleep_interval: Option<f64>,
+    pub max_sleep_interval: Option<f64>,
+    pub sleep_requests: Option<f64>,
+    pub cookies_file: Option<PathBuf>,
+    pub cookies_from_browser: Option<String>,
+    pub skip_metadata: bool,
+    pub hash_downloads: bool,
+    pub player_client_fallbacks: Option<Vec<String>>,
+    pub bell: bool,
+    pub index_prefix: bool,
+    pub write_playlist_metafiles: bool,
+    pub no_part: bool,
+    pub force_download: bool,
+    pub no_overwrites: bool,
+    pub no_title: bool,
+    pub progress_json: bool,
+    pub audio_quality: Option<String>,
+    pub audio_copy: bool,
+    pub sponsorblock_export: Option<PathBuf>,
+    pub print_template: Option<String>,
+    pub simulate_slow: bool,
+    /// `Some(true)` for --stop-on-error, `Some(false)` for --ignore-errors
+    pub stop_on_error: Option<bool>,
 }
 
 #[cfg(test)]
@@ -154,6 +714,19 @@ mod tests {
         assert!(!config.audio_only);
     }
 
+    #[test]
+    fn test_download_dir() {
+        let mut config = Config::default();
+        assert_eq!(config.download_dir(), PathBuf::from("./downloads"));
+
+        config.quality_subdirs = Some(true);
+        config.quality = "1080".to_string();
+        assert_eq!(config.download_dir(), PathBuf::from("./downloads/1080"));
+
+        config.audio_only = true;
+        assert_eq!(config.download_dir(), PathBuf::from("./downloads/audio"));
+    }
+
     #[test]
     fn test_merge_with_cli() {
         let mut config = Config::default();
@@ -163,11 +736,143 @@ mod tests {
             audio_only: true,
             enable_file_logging: true,
             enable_json_logging: false,
+            mirror_dirs: vec![PathBuf::from("/mnt/nas")],
+            sanitize_names: false,
+            ascii_names: false,
+            match_filter: Some("duration>60".to_string()),
+            age_limit: Some(18),
+            min_duration: None,
+            max_duration: Some(600),
+            min_views: Some(1000),
+            max_views: None,
+            min_filesize: None,
+            max_filesize: Some(2 * 1_073_741_824),
+            term_progress: false,
+            extra_ytdlp_args: vec!["--no-mtime".to_string()],
+            cleanup_partials: false,
+            quality_subdirs: false,
+            download_sections: None,
+            prefer_free_formats: true,
+            id_filename: true,
+            write_description: false,
+            write_comments: false,
+            embed_chapters: false,
+            parse_metadata: Some("%(title)s:%(artist)s".to_string()),
+            recode_video: Some("mkv".to_string()),
+            sub_langs: Some("en,de".to_string()),
+            events_file: Some(PathBuf::from("/tmp/events.ndjson")),
+            cache_dir: None,
+            temp_dir: None,
+            sleep_interval: None,
+            max_sleep_interval: None,
+            sleep_requests: None,
+            cookies_file: None,
+            cookies_from_browser: None,
+            skip_metadata: false,
+            hash_downloads: false,
+            player_client_fallbacks: None,
+            bell: false,
+            index_prefix: true,
+            write_playlist_metafiles: false,
+            no_part: false,
+            force_download: false,
+            no_overwrites: false,
+            no_title: false,
+            progress_json: false,
+            audio_quality: Some("192K".to_string()),
+            audio_copy: false,
+            sponsorblock_export: None,
+            print_template: None,
+            simulate_slow: false,
+            stop_on_error: Some(true),
         };
 
         config.merge_with_cli(cli_config);
         assert_eq!(config.output_dir, PathBuf::from("/tmp/videos"));
         assert_eq!(config.quality, "720p");
         assert!(config.audio_only);
+        assert_eq!(config.mirror_dirs, vec![PathBuf::from("/mnt/nas")]);
+        assert_eq!(config.match_filter.as_deref(), Some("duration>60"));
+        assert_eq!(config.age_limit, Some(18));
+        assert_eq!(config.effective_match_filter().as_deref(), Some("duration>60 & duration<=600 & view_count>=1000"));
+        assert!(!config.fits_view_limits(Some(999)));
+        assert!(config.fits_view_limits(Some(1000)));
+        assert_eq!(config.max_filesize, Some(2 * 1_073_741_824));
+        assert!(!config.fits_filesize_limits(Some(3 * 1_073_741_824)));
+        assert_eq!(config.extra_ytdlp_args, vec!["--no-mtime".to_string()]);
+        assert_eq!(config.prefer_free_formats, Some(true));
+        assert_eq!(config.id_filename, Some(true));
+        assert_eq!(config.parse_metadata.as_deref(), Some("%(title)s:%(artist)s"));
+        assert_eq!(config.recode_video.as_deref(), Some("mkv"));
+        assert_eq!(config.sub_langs.as_deref(), Some("en,de"));
+        assert_eq!(config.events_file, Some(PathBuf::from("/tmp/events.ndjson")));
+        assert_eq!(config.stop_on_error, Some(true));
+        assert_eq!(config.index_prefix, Some(true));
+        assert_eq!(config.audio_quality.as_deref(), Some("192K"));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let config = Config {
+            audio_format: Some("flac".to_string()),
+            notifications: Some(false),
+            ..Config::default()
+        };
+        config.save_to_file(&path).unwrap();
+
+        let loaded = Config::load_from_file(&path).unwrap();
+        assert_eq!(loaded.audio_format.as_deref(), Some("flac"));
+        assert_eq!(loaded.notifications, Some(false));
+    }
+
+    #[test]
+    fn test_embed_chapters_conflicts_with_split_chapters() {
+        let mut config = Config {
+            embed_chapters: Some(true),
+            ..Config::default()
+        };
+        assert!(config.validate_chapter_options().is_ok());
+
+        config.extra_ytdlp_args = vec!["--split-chapters".to_string()];
+        assert!(matches!(config.validate_chapter_options(), Err(YtdlError::Config(_))));
+
+        config.embed_chapters = Some(false);
+        assert!(config.validate_chapter_options().is_ok());
+    }
+
+    #[test]
+    fn test_recode_video_requires_ffmpeg() {
+        let path = Path::new("downloads/Song.opus");
+        let mut config = Config::default();
+        assert!(!config.requires_ffmpeg());
+        assert_eq!(config.history_format(path), "mp4");
+
+        config.recode_video = Some("mkv".to_string());
+        assert!(config.requires_ffmpeg());
+        assert_eq!(config.history_format(path), "mkv");
+
+        // Audio extraction ignores the recode target
+        config.audio_only = true;
+        assert!(!config.requires_ffmpeg());
+        assert_eq!(config.history_format(path), "mp3");
+
+        config.audio_copy = Some(true);
+        assert_eq!(config.history_format(path), "opus");
+    }
+
+    #[test]
+    fn test_audio_quality_accepts_legacy_number() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let content = toml::to_string(&Config::default()).unwrap();
+
+        std::fs::write(&path, format!("audio_quality = 192\n{}", content)).unwrap();
+        assert_eq!(Config::load_from_file(&path).unwrap().audio_quality.as_deref(), Some("192"));
+
+        std::fs::write(&path, format!("audio_quality = \"128K\"\n{}", content)).unwrap();
+        assert_eq!(Config::load_from_file(&path).unwrap().audio_quality.as_deref(), Some("128K"));
     }
 }
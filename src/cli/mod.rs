@@ -8,8 +8,12 @@ pub mod config;
 pub mod parser;
 
 pub use commands::{
-    download_single_url, handle_clear_history_command, handle_config_command,
-    handle_history_command, handle_playlist_download, show_video_info,
+    download_single_url, download_subtitles_only, handle_archive_command, handle_bench_command, handle_cache_clear_command,
+    handle_clear_history_command, handle_list_command, handle_config_command, handle_dedupe_history_command, handle_find_dupes_command,
+    handle_history_command, handle_merge_history_command, handle_playlist_download,
+    handle_remove_history_command, handle_restore_history_command, handle_usage_command,
+    handle_theme_preview_command, handle_validate_command, handle_verify_history_command, print_video_field,
+    show_video_info,
 };
 pub use config::{CliConfig, Config};
-pub use parser::{Cli, Commands};
+pub use parser::{parse_section, CacheAction, Cli, Commands};
@@ -1,5 +1,8 @@
 use clap::{Parser, Subcommand};
+use std::convert::Infallible;
 use std::path::PathBuf;
+use crate::shared::constants::DEFAULT_LOG_RETENTION_DAYS;
+use crate::shared::utils::{expand_path, parse_size};
 
 #[derive(Parser, Debug)]
 #[command(name = "ytdl")]
@@ -10,9 +13,214 @@ pub struct Cli {
     pub url: Option<String>,
 
     /// Output directory
-    #[arg(short, long, env = "YTDL_OUTPUT_DIR")]
+    #[arg(short, long, env = "YTDL_OUTPUT_DIR", value_parser = parse_path)]
     pub output: Option<PathBuf>,
 
+    /// Also copy finished downloads to this directory (repeatable)
+    #[arg(long = "mirror", value_name = "DIR", value_parser = parse_path)]
+    pub mirror: Vec<PathBuf>,
+
+    /// Rename downloaded files to strip characters that break other tools
+    #[arg(long)]
+    pub sanitize_names: bool,
+
+    /// Like --sanitize-names, and also drop non-ASCII characters (emoji, accents)
+    #[arg(long)]
+    pub ascii_names: bool,
+
+    /// Only download videos matching a yt-dlp filter (e.g. "view_count>1000").
+    /// Filtering is done by yt-dlp, mainly useful for playlist and batch downloads
+    #[arg(long, value_name = "EXPR")]
+    pub match_filter: Option<String>,
+
+    /// Skip videos unsuitable for viewers of this age (passed to yt-dlp)
+    #[arg(long, value_name = "YEARS")]
+    pub age_limit: Option<u32>,
+
+    /// Skip videos shorter than this many seconds
+    #[arg(long, value_name = "SECS")]
+    pub min_duration: Option<u64>,
+
+    /// Skip videos longer than this many seconds
+    #[arg(long, value_name = "SECS")]
+    pub max_duration: Option<u64>,
+
+    /// Skip videos with fewer views than this. Filtered by yt-dlp for channels
+    /// and playlists, and checked before downloading single and batch videos
+    #[arg(long, value_name = "N", value_parser = parse_view_count, allow_negative_numbers = true)]
+    pub min_views: Option<u64>,
+
+    /// Skip videos with more views than this
+    #[arg(long, value_name = "N", value_parser = parse_view_count, allow_negative_numbers = true)]
+    pub max_views: Option<u64>,
+
+    /// Skip files smaller than SIZE, e.g. 50M (passed to yt-dlp)
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    pub min_filesize: Option<u64>,
+
+    /// Skip files larger than SIZE, e.g. 500M or 2G (passed to yt-dlp). Useful in
+    /// batch and playlist mode to skip oversized videos
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    pub max_filesize: Option<u64>,
+
+    /// Report download progress in the terminal taskbar (OSC 9;4, e.g. WezTerm, ConEmu)
+    #[arg(long)]
+    pub term_progress: bool,
+
+    /// Print progress as one JSON object per line on stdout instead of a progress bar,
+    /// for GUI wrappers: {"percent", "downloaded", "total", "speed", "eta"}. Logs and
+    /// messages go to stderr
+    #[arg(long)]
+    pub progress_json: bool,
+
+    /// Test mode: fake every yt-dlp call with slow, made-up progress and placeholder
+    /// files instead of downloading. yt-dlp doesn't need to be installed
+    #[arg(long)]
+    pub simulate_slow: bool,
+
+    /// Don't show download progress in the terminal window title
+    #[arg(long)]
+    pub no_title: bool,
+
+    /// Extra arguments appended to every yt-dlp call, split like a shell would
+    /// (e.g. "--cookies-from-browser firefox"). These can conflict with options ytdl
+    /// manages itself (-o, -f, --progress), so use with care
+    #[arg(long, value_name = "STRING", allow_hyphen_values = true)]
+    pub ytdlp_args: Option<String>,
+
+    /// Delete partial files (.part/.ytdl) left behind when a download fails
+    #[arg(long)]
+    pub cleanup_partials: bool,
+
+    /// Save into a subdirectory of the output directory named after the quality
+    /// (e.g. 1080, best, audio)
+    #[arg(long)]
+    pub quality_subdirs: bool,
+
+    /// Export SponsorBlock segments to FILE (.json or .csv) without cutting the video.
+    /// Batch and playlist items each get their own file, e.g. "Title.segments.json"
+    #[arg(long, value_name = "FILE", value_parser = parse_path)]
+    pub sponsorblock_export: Option<PathBuf>,
+
+    /// After a successful download, print this template filled from the video's
+    /// metadata, e.g. "%(title)s %(id)s" (like yt-dlp's --print). A bare field
+    /// name (title, uploader, duration, id, upload_date, view_count, thumbnail)
+    /// prints just that value and exits without downloading
+    #[arg(long = "print", value_name = "TEMPLATE|FIELD")]
+    pub print_template: Option<String>,
+
+    /// Only download a time range, e.g. "*01:30-02:00" (requires FFmpeg)
+    #[arg(long, value_name = "*START-END")]
+    pub section: Option<String>,
+
+    /// Prefer free formats (webm/vp9/opus) over mp4/h264/aac at the same quality.
+    /// Changes which files yt-dlp picks by default; the result may need a webm-capable player
+    #[arg(long)]
+    pub prefer_free_formats: bool,
+
+    /// Name files after the video id (`<id>.<ext>`) instead of the title.
+    /// Useful for archives where titles change or collide
+    #[arg(long)]
+    pub id_filename: bool,
+
+    /// Audio quality for audio-only downloads: 0 (best) to 10 (worst) for VBR,
+    /// or a constant bitrate like "192K". Ignored without --audio-only
+    #[arg(long, value_name = "N")]
+    pub audio_quality: Option<String>,
+
+    /// Save the video description to a .description file next to the download
+    #[arg(long)]
+    pub write_description: bool,
+
+    /// Save the video's comments to a .comments.json file next to the download
+    #[arg(long)]
+    pub write_comments: bool,
+
+    /// Embed the video's chapters in the file so players can navigate them (requires FFmpeg)
+    #[arg(long)]
+    pub embed_chapters: bool,
+
+    /// Tag files with artist and track parsed from "Artist - Song" titles
+    /// (yt-dlp --parse-metadata "title:%(artist)s - %(title)s" with --embed-metadata)
+    #[arg(long, conflicts_with = "parse_metadata")]
+    pub parse_metadata_title: bool,
+
+    /// yt-dlp --parse-metadata pattern (FROM:TO) for other title formats, passed verbatim.
+    /// The parsed fields are embedded in the file as tags
+    #[arg(long, value_name = "PATTERN")]
+    pub parse_metadata: Option<String>,
+
+    /// Transcode video downloads to this container, e.g. mp4 for devices that
+    /// need H.264 (yt-dlp --recode-video, requires FFmpeg). Ignored with --audio-only
+    #[arg(long, value_name = "FORMAT", value_parser = ["mp4", "mkv", "webm", "mov", "avi", "flv"])]
+    pub recode_video: Option<String>,
+
+    /// Save only the subtitles, not the video (yt-dlp --skip-download --write-subs).
+    /// Needs --sub-langs or sub_langs in the config file
+    #[arg(long, conflicts_with_all = ["batch_source", "resume_batch", "interactive", "audio_only", "info"])]
+    pub subs_only: bool,
+
+    /// Subtitle languages for --subs-only, comma-separated, e.g. "en,de" or "en.*"
+    #[arg(long, value_name = "LANGS")]
+    pub sub_langs: Option<String>,
+
+    /// Directory yt-dlp caches player code and signatures in (yt-dlp --cache-dir)
+    #[arg(long, value_name = "DIR", value_parser = parse_path)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Download into this directory and move finished files to the output
+    /// directory (yt-dlp -P temp:), e.g. a fast local disk in front of a NAS
+    #[arg(long, value_name = "DIR", value_parser = parse_path)]
+    pub temp_dir: Option<PathBuf>,
+
+    /// Seconds to wait before each download, to avoid rate-limiting. For large
+    /// playlists and channel archives 5-10 is a good start
+    #[arg(long, value_name = "SECS")]
+    pub sleep_interval: Option<f64>,
+
+    /// Wait a random time between --sleep-interval and this many seconds instead
+    /// (e.g. --sleep-interval 5 --max-sleep-interval 30)
+    #[arg(long, value_name = "SECS", requires = "sleep_interval")]
+    pub max_sleep_interval: Option<f64>,
+
+    /// Seconds to wait between metadata requests (playlist listing, video info).
+    /// 1-2 helps with channels of thousands of videos
+    #[arg(long, value_name = "SECS")]
+    pub sleep_requests: Option<f64>,
+
+    /// Netscape-format cookies file to authenticate with (yt-dlp --cookies).
+    /// Private and unlisted playlists can't be listed without cookies
+    #[arg(long = "cookies", value_name = "FILE", value_parser = parse_path)]
+    pub cookies_file: Option<PathBuf>,
+
+    /// Read cookies from a local browser profile, e.g. firefox or chrome
+    /// (yt-dlp --cookies-from-browser)
+    #[arg(long, value_name = "BROWSER")]
+    pub cookies_from_browser: Option<String>,
+
+    /// Print the full description with --info instead of a preview
+    #[arg(long, requires = "info")]
+    pub full_description: bool,
+
+    /// Skip the metadata fetch before downloading; the title is taken from the filename.
+    /// Disables the quality fallback warning and matching --resume to a partial file
+    #[arg(long)]
+    pub no_metadata: bool,
+
+    /// Record a SHA-256 of each downloaded file in history, for `history --find-dupes`.
+    /// Reads the whole file after downloading
+    #[arg(long)]
+    pub hash: bool,
+
+    /// YouTube player clients to retry with, in order, when a download fails with
+    /// HTTP 403 (default: android). Pass "" to disable the retry
+    #[arg(long, value_name = "CLIENTS", value_delimiter = ',')]
+    pub player_client_fallbacks: Option<Vec<String>>,
+
+    /// Ring the terminal bell when a download finishes (if no completion_sound is configured)
+    #[arg(long)]
+    pub bell: bool,
+
     /// Video quality (e.g., 1080, 720, 480, or 'best')
     #[arg(short, long, default_value = "best")]
     pub quality: Option<String>,
@@ -21,6 +229,11 @@ pub struct Cli {
     #[arg(short, long)]
     pub audio_only: bool,
 
+    /// Download audio only, keeping the original codec (usually opus or m4a) instead
+    /// of re-encoding to MP3. Faster and lossless
+    #[arg(long, conflicts_with = "audio_quality")]
+    pub audio_copy: bool,
+
     /// Download as playlist
     #[arg(short, long)]
     pub playlist: bool,
@@ -29,22 +242,63 @@ pub struct Cli {
     #[arg(long)]
     pub info: bool,
 
+    /// Sort formats in --info output by resolution, file size, or fps
+    #[arg(long, value_parser = ["res", "size", "fps"], default_value = "res")]
+    pub sort_by: String,
+
     /// Launch TUI mode
     #[arg(short, long)]
     pub interactive: bool,
 
+    /// Use ASCII-only spinners and symbols in the TUI (default when the locale isn't UTF-8)
+    #[arg(long)]
+    pub ascii: bool,
+
     /// Batch download from file
-    #[arg(short, long)]
+    #[arg(short, long, value_parser = parse_path, group = "batch_source")]
     pub batch: Option<PathBuf>,
 
+    /// Batch file format (detected from the file extension if omitted)
+    #[arg(long, value_parser = ["txt", "json", "csv"])]
+    pub batch_format: Option<String>,
+
+    /// Batch download every YouTube link found on a web page (HTML or plain text)
+    #[arg(long, value_name = "PAGE_URL", group = "batch_source", conflicts_with = "resume_batch")]
+    pub batch_from_url: Option<String>,
+
+    /// With --batch or --batch-from-url, fetch every video's metadata and print the
+    /// download plan (titles, unavailable videos) without downloading anything
+    #[arg(long, requires = "batch_source")]
+    pub dry_run: bool,
+
+    /// Resume a batch from a saved state file (e.g. .batch-state.json)
+    #[arg(long, value_name = "STATEFILE", value_parser = parse_path)]
+    pub resume_batch: Option<PathBuf>,
+
+    /// Where to write the URLs of failed batch items (defaults to failed.txt in
+    /// the download directory)
+    #[arg(long, value_name = "FILE", value_parser = parse_path)]
+    pub write_failures: Option<PathBuf>,
+
     /// Number of concurrent downloads for batch mode
     #[arg(long, default_value = "3")]
     pub concurrent: usize,
 
-    /// Stop batch download on first error
-    #[arg(long)]
+    /// Stop a batch or playlist download at the first failed video
+    /// (overrides stop_on_error in the config file)
+    #[arg(long, visible_alias = "abort-on-error")]
     pub stop_on_error: bool,
 
+    /// Stop a batch after this many failures in a row (e.g. yt-dlp broken), leaving
+    /// the remaining items for --resume-batch
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_consecutive_failures: Option<u64>,
+
+    /// Skip failed videos and keep going in a batch or playlist (the default unless
+    /// stop_on_error is set in the config file)
+    #[arg(long, conflicts_with = "stop_on_error")]
+    pub ignore_errors: bool,
+
     /// Use URL from clipboard
     #[arg(long)]
     pub clipboard: bool,
@@ -61,10 +315,41 @@ pub struct Cli {
     #[arg(long)]
     pub folder: Option<String>,
 
+    /// Write URLs of failed playlist videos to FILE (retry later with --batch FILE)
+    #[arg(long, value_name = "FILE", value_parser = parse_path)]
+    pub write_playlist_metafile: Option<PathBuf>,
+
+    /// After a playlist download, write an M3U of the downloaded files, to
+    /// --write-m3u=FILE or "<playlist title>.m3u" in the download directory
+    #[arg(long, value_name = "FILE", num_args = 0..=1, require_equals = true, value_parser = parse_path)]
+    pub write_m3u: Option<Option<PathBuf>>,
+
+    /// Prefix playlist downloads with their playlist index, e.g. "01 - Title.mp4"
+    #[arg(long)]
+    pub index_prefix: bool,
+
+    /// Save the playlist's description and thumbnail next to its videos
+    #[arg(long)]
+    pub write_playlist_metafiles: bool,
+
     /// Resume incomplete downloads
     #[arg(long)]
     pub resume: bool,
 
+    /// Write directly to the final file instead of a .part file (disables resuming).
+    /// Useful on network filesystems that handle the .part rename badly
+    #[arg(long)]
+    pub no_part: bool,
+
+    /// Re-download even if the file already exists, the URL is in the history or
+    /// an earlier run of the playlist finished it (yt-dlp --force-overwrites)
+    #[arg(long)]
+    pub force_download: bool,
+
+    /// Never overwrite existing files (yt-dlp --no-overwrites)
+    #[arg(long, conflicts_with = "force_download")]
+    pub no_overwrites: bool,
+
     /// Verbose logging (-v: DEBUG, -vv: TRACE)
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
@@ -73,6 +358,10 @@ pub struct Cli {
     #[arg(short = 'Q', long)]
     pub quiet: bool,
 
+    /// Disable colored output (also enabled by the NO_COLOR environment variable)
+    #[arg(long)]
+    pub no_color: bool,
+
     /// Enable file logging
     #[arg(long, env = "YTDL_LOG_FILE")]
     pub log_file: bool,
@@ -81,8 +370,22 @@ pub struct Cli {
     #[arg(long, env = "YTDL_LOG_JSON")]
     pub log_json: bool,
 
+    /// Append download lifecycle events (started, progress, completed, failed) to FILE
+    /// as one JSON object per line, separate from the regular logs
+    #[arg(long, value_name = "FILE", value_parser = parse_path)]
+    pub events_file: Option<PathBuf>,
+
+    /// Per-module log levels, e.g. "ytdl::infra::downloader=debug,info".
+    /// Overrides RUST_LOG, which in turn overrides -v/--quiet
+    #[arg(long, value_name = "SPEC")]
+    pub log_filter: Option<String>,
+
+    /// Delete log files older than this many days on startup (0 keeps them all)
+    #[arg(long, value_name = "DAYS", default_value_t = DEFAULT_LOG_RETENTION_DAYS)]
+    pub log_retention_days: usize,
+
     /// Custom config file path
-    #[arg(long)]
+    #[arg(long, value_parser = parse_path)]
     pub config: Option<PathBuf>,
 
     /// Show common usage examples
@@ -109,8 +412,40 @@ pub enum Commands {
         search: Option<String>,
 
         /// Export history to CSV file
-        #[arg(short, long)]
+        #[arg(short, long, value_parser = parse_path)]
         export: Option<PathBuf>,
+
+        /// Restore history from the backup made by the last clear-history
+        #[arg(long)]
+        restore: bool,
+
+        /// Remove the entry for this URL
+        #[arg(long, value_name = "URL")]
+        remove: Option<String>,
+
+        /// Check that recorded files still exist with the recorded size
+        #[arg(long)]
+        verify: bool,
+
+        /// With --verify, remove entries whose files are missing or changed
+        #[arg(long, requires = "verify")]
+        prune_missing: bool,
+
+        /// List entries whose files are identical (same SHA-256, see --hash)
+        #[arg(long)]
+        find_dupes: bool,
+
+        /// Show how much data was downloaded per day
+        #[arg(long)]
+        usage: bool,
+
+        /// With --usage, group by month instead of day
+        #[arg(long, requires = "usage")]
+        month: bool,
+
+        /// Merge entries from another history.json (e.g. from another machine)
+        #[arg(long, value_name = "FILE", value_parser = parse_path)]
+        merge: Option<PathBuf>,
     },
 
     /// Clear download history
@@ -118,6 +453,82 @@ pub enum Commands {
         /// Clear entries older than N days
         #[arg(long)]
         older_than: Option<i64>,
+
+        /// Remove the oldest entries until the total recorded size is under this many bytes
+        #[arg(long, value_name = "BYTES", conflicts_with = "older_than")]
+        max_size: Option<u64>,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Merge history entries recorded more than once for the same URL
+    DedupeHistory,
+
+    /// Archive a channel's uploads into a folder named after it. Reruns only fetch
+    /// new videos; each video gets its info json and thumbnail
+    Archive {
+        /// Channel URL, e.g. https://youtube.com/@name
+        url: String,
+
+        /// Directory to create the channel folder in (default: the download directory)
+        #[arg(short, long, value_parser = parse_path)]
+        output: Option<PathBuf>,
+    },
+
+    /// List a playlist's videos (index, title, duration, URL) without downloading
+    List {
+        /// Playlist or channel URL
+        url: String,
+
+        /// Also write the list to a file: JSON for .json, otherwise CSV
+        #[arg(short, long, value_name = "FILE", value_parser = parse_path)]
+        export: Option<PathBuf>,
+    },
+
+    /// Manage yt-dlp's cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Measure download throughput at different concurrency settings
+    Bench {
+        /// Video to download repeatedly (keep it small)
+        #[arg(long)]
+        url: String,
+
+        /// Downloads per configuration
+        #[arg(long, default_value = "3")]
+        trials: usize,
+
+        /// Concurrent fragment counts to compare
+        #[arg(long, value_delimiter = ',', default_value = "1,4,8")]
+        fragments: Vec<usize>,
+
+        /// Parallel downloads per trial to compare
+        #[arg(long, value_delimiter = ',', default_value = "1")]
+        concurrent: Vec<usize>,
+    },
+
+    /// Print a static sample of the TUI screens in a theme's colors
+    ThemePreview {
+        /// TOML theme file, e.g. `color = "#7aa2f7"` (default: the built-in theme)
+        #[arg(long, value_name = "PATH", value_parser = parse_path)]
+        theme: Option<PathBuf>,
+    },
+
+    /// Check a batch file without downloading: reports invalid URLs, duplicates
+    /// within the file and URLs already in history. Exits non-zero if any URL is invalid
+    Validate {
+        /// Batch file, as passed to --batch
+        #[arg(value_parser = parse_path)]
+        file: PathBuf,
+
+        /// Batch file format (detected from the file extension if omitted)
+        #[arg(long, value_parser = ["txt", "json", "csv"])]
+        format: Option<String>,
     },
 
     /// Generate shell completions
@@ -128,9 +539,22 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum CacheAction {
+    /// Delete yt-dlp's cache (yt-dlp --rm-cache-dir). Fixes failures caused by
+    /// stale or corrupted cached player code
+    Clear,
+}
+
 impl Cli {
     pub fn validate(&self) -> Result<(), String> {
-        if self.url.is_none() && self.batch.is_none() && self.command.is_none() && !self.interactive {
+        if self.url.is_none()
+            && self.batch.is_none()
+            && self.batch_from_url.is_none()
+            && self.resume_batch.is_none()
+            && self.command.is_none()
+            && !self.interactive
+        {
             return Err("No URL provided. Use --help for usage information.".to_string());
         }
 
@@ -138,10 +562,80 @@ impl Cli {
             return Err("Cannot use both --quiet and --verbose flags".to_string());
         }
 
+        if let Some(ref section) = self.section {
+            parse_section(section)?;
+        }
+
+        if self.no_part && self.resume {
+            return Err("Cannot use both --no-part and --resume flags".to_string());
+        }
+
+        // yt-dlp restarts forced downloads from scratch, ignoring --continue
+        if self.force_download && self.resume {
+            return Err("Cannot use both --force-download and --resume flags".to_string());
+        }
+
         Ok(())
     }
 }
 
+/// Clap parser for path arguments: expands `~`, `~user` and `$VAR` like a shell
+/// would, for values that reach ytdl unexpanded (quoted, or after `=`)
+fn parse_path(value: &str) -> Result<PathBuf, Infallible> {
+    Ok(expand_path(value))
+}
+
+/// Parse a view count, rejecting negative numbers with a clearer message than
+/// clap's default for `u64`
+fn parse_view_count(value: &str) -> Result<u64, String> {
+    if value.trim().starts_with('-') {
+        return Err(format!("view count must not be negative, got {}", value));
+    }
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid view count \"{}\", expected a whole number", value))
+}
+
+/// Parse a `[*]START-END` time range (`SS`, `MM:SS` or `HH:MM:SS`) into the
+/// `*START-END` form yt-dlp's `--download-sections` expects
+pub fn parse_section(spec: &str) -> Result<String, String> {
+    let range = spec.trim().trim_start_matches('*');
+    let invalid = || format!("Invalid --section \"{}\". Expected e.g. \"*01:30-02:00\"", spec);
+
+    let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+    let start_secs = parse_timestamp(start).ok_or_else(invalid)?;
+    let end_secs = parse_timestamp(end).ok_or_else(invalid)?;
+
+    if end_secs <= start_secs {
+        return Err(format!("Invalid --section \"{}\": end must be after start", spec));
+    }
+
+    Ok(format!("*{}-{}", start.trim(), end.trim()))
+}
+
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+
+    let mut seconds = 0.0;
+    for (i, part) in parts.iter().enumerate() {
+        if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+        let value: f64 = part.parse().ok()?;
+        // Minutes and seconds after the leading component must stay below 60
+        if i > 0 && value >= 60.0 {
+            return None;
+        }
+        seconds = seconds * 60.0 + value;
+    }
+
+    Some(seconds)
+}
+
 mod clap_complete {
     use clap::ValueEnum;
 
@@ -154,3 +648,52 @@ mod clap_complete {
         Elvish,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_section() {
+        assert_eq!(parse_section("*01:30-02:00"), Ok("*01:30-02:00".to_string()));
+        assert_eq!(parse_section("90-120"), Ok("*90-120".to_string()));
+        assert_eq!(parse_section("*1:00:00-1:00:30.5"), Ok("*1:00:00-1:00:30.5".to_string()));
+
+        assert!(parse_section("*02:00-01:30").is_err());
+        assert!(parse_section("*01:30").is_err());
+        assert!(parse_section("*01:75-02:00").is_err());
+        assert!(parse_section("*aa:bb-cc:dd").is_err());
+        assert!(parse_section("*1:2:3:4-5").is_err());
+    }
+
+    #[test]
+    fn test_parse_view_count() {
+        assert_eq!(parse_view_count("1000"), Ok(1000));
+        assert_eq!(parse_view_count("0"), Ok(0));
+        assert!(parse_view_count("-5").unwrap_err().contains("negative"));
+        assert!(parse_view_count("lots").is_err());
+    }
+
+    #[test]
+    fn test_no_part_conflicts_with_resume() {
+        let url = "https://youtube.com/watch?v=dQw4w9WgXcQ";
+
+        let cli = Cli::try_parse_from(["ytdl", "--no-part", "--resume", url]).unwrap();
+        assert!(cli.validate().is_err());
+
+        let cli = Cli::try_parse_from(["ytdl", "--no-part", url]).unwrap();
+        assert!(cli.validate().is_ok());
+    }
+
+    #[test]
+    fn test_overwrite_flags() {
+        let url = "https://youtube.com/watch?v=dQw4w9WgXcQ";
+
+        let cli = Cli::try_parse_from(["ytdl", "--force-download", "--resume", url]).unwrap();
+        assert!(cli.validate().is_err());
+        assert!(Cli::try_parse_from(["ytdl", "--force-download", "--no-overwrites", url]).is_err());
+
+        let cli = Cli::try_parse_from(["ytdl", "--no-overwrites", "--resume", url]).unwrap();
+        assert!(cli.validate().is_ok());
+    }
+}
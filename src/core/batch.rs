@@ -1,23 +1,96 @@
 use crate::cli::Config;
+use crate::core::history::normalize_url;
 use crate::core::{History, HistoryEntry};
-use crate::infra::Downloader;
+use crate::infra::command::CommandRunner;
+use crate::infra::downloader::title_from_path;
+use crate::infra::{export_comments, export_sponsor_segments, item_segments_path, mirror_file, Downloader, VideoMetadata};
+use crate::shared::constants::PAGE_FETCH_TIMEOUT_SECS;
 use crate::shared::{validate_youtube_url, Result, YtdlError};
 use futures::stream::{self, StreamExt};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Notify};
 use tracing::{debug, error, info, warn};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DownloadStatus {
     Pending,
     Downloading,
     Complete,
     Failed(String),
+    /// Already in the download history
     Skipped,
+    /// Outside the duration, view or file size limits, or skipped by yt-dlp's filters
+    Filtered,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchFormat {
+    Txt,
+    Json,
+    Csv,
+}
+
+impl BatchFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "txt" | "text" => Some(Self::Txt),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// Detect the batch format from the file extension, defaulting to plain text
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::parse)
+            .unwrap_or(Self::Txt)
+    }
+}
+
+/// Per-URL overrides layered over the base `Config`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchItemOptions {
+    pub quality: Option<String>,
+    pub audio_only: Option<bool>,
+    pub output: Option<PathBuf>,
+}
+
+impl BatchItemOptions {
+    pub fn apply_to(&self, base: &Config) -> Config {
+        let mut config = base.clone();
+
+        if let Some(ref quality) = self.quality {
+            config.quality = quality.clone();
+        }
+
+        if let Some(audio_only) = self.audio_only {
+            config.audio_only = audio_only;
+        }
+
+        if let Some(ref output) = self.output {
+            config.output_dir = output.clone();
+        }
+
+        config
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchFileEntry {
+    url: String,
+    #[serde(flatten)]
+    options: BatchItemOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchDownloadItem {
     pub url: String,
     pub status: DownloadStatus,
@@ -25,6 +98,8 @@ pub struct BatchDownloadItem {
     pub output_path: Option<PathBuf>,
     pub progress: f32,
     pub file_size: u64,
+    #[serde(default)]
+    pub options: BatchItemOptions,
 }
 
 impl BatchDownloadItem {
@@ -36,6 +111,206 @@ impl BatchDownloadItem {
             output_path: None,
             progress: 0.0,
             file_size: 0,
+            options: BatchItemOptions::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: BatchItemOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Default name of the file batch progress is persisted to
+pub const BATCH_STATE_FILE: &str = ".batch-state.json";
+
+/// State file for a batch file, named after it so batches in the same directory
+/// keep separate progress, e.g. `urls.txt` -> `.urls.batch-state.json`
+pub fn batch_state_path(batch_file: &Path) -> PathBuf {
+    let stem = batch_file
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    batch_file.with_file_name(format!(".{}{}", stem, BATCH_STATE_FILE))
+}
+
+/// Failures in a row across concurrent downloads, reset by any success
+#[derive(Debug, Default)]
+struct FailureStreak {
+    count: AtomicUsize,
+    limit: Option<usize>,
+}
+
+impl FailureStreak {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            limit,
+        }
+    }
+
+    fn record(&self, status: &DownloadStatus) {
+        match status {
+            DownloadStatus::Complete => self.count.store(0, Ordering::SeqCst),
+            DownloadStatus::Failed(_) => {
+                self.count.fetch_add(1, Ordering::SeqCst);
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the limit was reached and no new items should start
+    fn tripped(&self) -> bool {
+        self.limit
+            .is_some_and(|limit| self.count.load(Ordering::SeqCst) >= limit)
+    }
+}
+
+/// Recent outcomes considered when deciding whether to back off
+const CONCURRENCY_WINDOW: usize = 6;
+/// Outcomes needed before the failure rate is trusted
+const CONCURRENCY_MIN_SAMPLES: usize = 3;
+/// Failure rate over the window above which concurrency is halved
+const BACKOFF_FAILURE_RATE: f64 = 0.5;
+/// Successes in a row before one more download may run at once again
+const RECOVERY_SUCCESSES: usize = 3;
+
+/// Concurrency limit that halves when most recent downloads fail (e.g. when
+/// rate-limited) and grows back by one after a run of successes, up to `max`
+#[derive(Debug)]
+struct AdaptiveConcurrency {
+    max: usize,
+    state: std::sync::Mutex<ConcurrencyState>,
+    changed: Notify,
+}
+
+#[derive(Debug)]
+struct ConcurrencyState {
+    limit: usize,
+    in_flight: usize,
+    /// `true` for each failure among the last `CONCURRENCY_WINDOW` downloads
+    recent: VecDeque<bool>,
+    successes: usize,
+}
+
+/// A running download's share of the concurrency limit, released on drop
+struct ConcurrencySlot(Arc<AdaptiveConcurrency>);
+
+impl Drop for ConcurrencySlot {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().in_flight -= 1;
+        self.0.changed.notify_waiters();
+    }
+}
+
+impl AdaptiveConcurrency {
+    fn new(max: usize) -> Self {
+        Self {
+            max,
+            state: std::sync::Mutex::new(ConcurrencyState {
+                limit: max,
+                in_flight: 0,
+                recent: VecDeque::with_capacity(CONCURRENCY_WINDOW),
+                successes: 0,
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit
+    }
+
+    /// Wait until fewer than the current limit of downloads are running
+    async fn acquire(self: &Arc<Self>) -> ConcurrencySlot {
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.in_flight < state.limit {
+                    state.in_flight += 1;
+                    return ConcurrencySlot(Arc::clone(self));
+                }
+            }
+
+            changed.await;
+        }
+    }
+
+    fn record(&self, status: &DownloadStatus) {
+        let failed = match status {
+            DownloadStatus::Complete => false,
+            DownloadStatus::Failed(_) => true,
+            _ => return,
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if state.recent.len() == CONCURRENCY_WINDOW {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(failed);
+
+        if failed {
+            state.successes = 0;
+            let failures = state.recent.iter().filter(|f| **f).count();
+            let rate = failures as f64 / state.recent.len() as f64;
+            if state.recent.len() >= CONCURRENCY_MIN_SAMPLES && rate > BACKOFF_FAILURE_RATE && state.limit > 1 {
+                state.limit = (state.limit / 2).max(1);
+                // Judge the new limit on fresh outcomes only
+                state.recent.clear();
+                warn!("Many downloads failing, reducing concurrency to {}", state.limit);
+            }
+        } else {
+            state.successes += 1;
+            if state.successes >= RECOVERY_SUCCESSES && state.limit < self.max {
+                state.limit += 1;
+                state.successes = 0;
+                info!("Downloads succeeding again, raising concurrency to {}", state.limit);
+                drop(state);
+                self.changed.notify_waiters();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PauseControl {
+    paused: AtomicBool,
+    resumed: Notify,
+}
+
+/// Pauses and resumes a running batch from outside it, e.g. from a signal handler.
+/// While paused no new items start; downloads already running finish normally.
+#[derive(Debug, Clone, Default)]
+pub struct PauseHandle(Arc<PauseControl>);
+
+impl PauseHandle {
+    pub fn pause(&self) {
+        info!("Pausing batch download");
+        self.0.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        info!("Resuming batch download");
+        self.0.paused.store(false, Ordering::SeqCst);
+        self.0.resumed.notify_waiters();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.0.paused.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the batch is not paused
+    async fn wait_if_paused(&self) {
+        loop {
+            let resumed = self.0.resumed.notified();
+            if !self.is_paused() {
+                return;
+            }
+            resumed.await;
         }
     }
 }
@@ -46,6 +321,14 @@ pub struct BatchDownloader {
     history: Arc<Mutex<History>>,
     stop_on_error: bool,
     concurrent_limit: usize,
+    pause: PauseHandle,
+    concurrency: Arc<AdaptiveConcurrency>,
+    state_file: Option<PathBuf>,
+    failures_file: Option<PathBuf>,
+    max_consecutive_failures: Option<usize>,
+    started_at: Mutex<Option<Instant>>,
+    /// Replaces the config's runner for metadata prefetching, in tests
+    runner: Option<Arc<dyn CommandRunner>>,
 }
 
 impl BatchDownloader {
@@ -62,49 +345,143 @@ impl BatchDownloader {
             history: Arc::new(Mutex::new(history)),
             stop_on_error,
             concurrent_limit,
+            pause: PauseHandle::default(),
+            concurrency: Arc::new(AdaptiveConcurrency::new(concurrent_limit)),
+            state_file: None,
+            failures_file: None,
+            max_consecutive_failures: None,
+            started_at: Mutex::new(None),
+            runner: None,
         }
     }
 
-    pub async fn load_from_file(&mut self, path: &Path) -> Result<()> {
-        info!("Loading batch URLs from file: {:?}", path);
+    /// Persist per-item status to `path` as the batch progresses
+    pub fn with_state_file(mut self, path: PathBuf) -> Self {
+        self.state_file = Some(path);
+        self
+    }
+
+    /// Write the URLs of failed items to `path` when the batch finishes, so
+    /// they can be retried with `--batch`
+    pub fn with_failures_file(mut self, path: PathBuf) -> Self {
+        self.failures_file = Some(path);
+        self
+    }
+
+    /// Stop starting new items after `limit` failures in a row, which usually
+    /// means something systemic (yt-dlp broken, network down) rather than bad URLs.
+    /// Items not yet started stay pending in the state file.
+    pub fn with_max_consecutive_failures(mut self, limit: Option<usize>) -> Self {
+        self.max_consecutive_failures = limit;
+        self
+    }
+
+    /// Run yt-dlp through `runner` for metadata prefetching
+    #[cfg(test)]
+    pub fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = Some(runner);
+        self
+    }
+
+    /// Restore items from a state file written by a previous run.
+    ///
+    /// Items that were mid-download when the process died are reset to pending.
+    pub async fn load_state(&mut self, path: &Path) -> Result<()> {
+        info!("Loading batch state from: {:?}", path);
 
         let content = tokio::fs::read_to_string(path).await.map_err(|e| {
-            error!("Failed to read batch file: {}", e);
+            error!("Failed to read batch state file: {}", e);
             YtdlError::Io(e)
         })?;
 
-        let mut urls = Vec::new();
-        for (line_num, line) in content.lines().enumerate() {
-            let line = line.trim();
+        let mut restored: Vec<BatchDownloadItem> = serde_json::from_str(&content).map_err(|e| {
+            YtdlError::Config(format!("Failed to parse batch state file: {}", e))
+        })?;
 
-            if line.is_empty() || line.starts_with('#') {
-                continue;
+        for item in restored.iter_mut() {
+            if item.status == DownloadStatus::Downloading {
+                item.status = DownloadStatus::Pending;
+                item.progress = 0.0;
             }
+        }
+
+        let pending = restored
+            .iter()
+            .filter(|i| i.status == DownloadStatus::Pending)
+            .count();
+        info!("Restored {} items ({} pending)", restored.len(), pending);
+
+        let mut items = self.items.lock().await;
+        *items = restored;
+        Ok(())
+    }
+
+    /// Handle that pauses and resumes this batch while `download_all` runs
+    pub fn pause_handle(&self) -> PauseHandle {
+        self.pause.clone()
+    }
 
-            match validate_youtube_url(line) {
+    pub async fn load_from_file(&mut self, path: &Path, format: Option<BatchFormat>) -> Result<()> {
+        info!("Loading batch URLs from file: {:?}", path);
+
+        let entries = read_batch_entries(path, format).await?;
+
+        let mut valid = Vec::new();
+        for (line_num, entry) in entries {
+            match validate_youtube_url(&entry.url) {
                 Ok(_) => {
-                    urls.push(line.to_string());
-                    debug!("Added URL from line {}: {}", line_num + 1, line);
+                    debug!("Added URL from entry {}: {}", line_num, entry.url);
+                    valid.push(entry);
                 }
                 Err(e) => {
-                    warn!("Invalid URL on line {}: {} - {}", line_num + 1, line, e);
+                    warn!("Invalid URL on entry {}: {} - {}", line_num, entry.url, e);
                 }
             }
         }
 
-        if urls.is_empty() {
+        if valid.is_empty() {
             return Err(YtdlError::Config("No valid URLs found in batch file".to_string()));
         }
 
         let mut items = self.items.lock().await;
-        for url in urls {
-            items.push(BatchDownloadItem::new(url));
+        for entry in valid {
+            items.push(BatchDownloadItem::new(entry.url).with_options(entry.options));
         }
 
         info!("Loaded {} URLs for batch download", items.len());
         Ok(())
     }
 
+    /// Fetch a web page and queue every YouTube link on it. Returns the number of links found.
+    pub async fn load_from_url(&mut self, page_url: &str) -> Result<usize> {
+        info!("Loading batch URLs from page: {}", page_url);
+
+        let response = reqwest::Client::new()
+            .get(page_url)
+            .timeout(Duration::from_secs(PAGE_FETCH_TIMEOUT_SECS))
+            .send()
+            .await
+            .map_err(|e| YtdlError::Network(format!("Failed to fetch {}: {}", page_url, e)))?;
+
+        if !response.status().is_success() {
+            return Err(YtdlError::Network(format!("{} returned {}", page_url, response.status())));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| YtdlError::Network(format!("Failed to read {}: {}", page_url, e)))?;
+
+        let urls = extract_youtube_urls(&body);
+        if urls.is_empty() {
+            return Err(YtdlError::Config(format!("No YouTube links found on {}", page_url)));
+        }
+
+        let count = urls.len();
+        self.add_urls(urls).await?;
+        Ok(count)
+    }
+
     pub async fn add_urls(&mut self, urls: Vec<String>) -> Result<()> {
         let mut items = self.items.lock().await;
 
@@ -131,18 +508,72 @@ impl BatchDownloader {
         duplicates
     }
 
+    /// Mark pending items whose URL is already in history as skipped. Skipped items
+    /// return from `download_item` before any yt-dlp call, so they cost no network requests.
     pub async fn skip_duplicates(&self) {
         let mut items = self.items.lock().await;
         let history = self.history.lock().await;
 
         for item in items.iter_mut() {
-            if history.contains_url(&item.url) {
+            if item.status == DownloadStatus::Pending && history.contains_url(&item.url) {
                 info!("Skipping duplicate URL: {}", item.url);
                 item.status = DownloadStatus::Skipped;
             }
         }
     }
 
+    /// Fetch the metadata of every pending item up front, at most `concurrent_limit`
+    /// at a time, without downloading. Titles are filled in; items whose metadata
+    /// can't be fetched (private, removed, invalid) are marked failed.
+    pub async fn prefetch_metadata(&self) {
+        let pending: Vec<(usize, String, Config)> = {
+            let items = self.items.lock().await;
+            items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.status == DownloadStatus::Pending)
+                .map(|(index, item)| (index, item.url.clone(), item.options.apply_to(&self.config)))
+                .collect()
+        };
+
+        info!("Prefetching metadata for {} items", pending.len());
+
+        let results: Vec<(usize, Result<VideoMetadata>)> = stream::iter(pending)
+            .map(|(index, url, config)| {
+                let downloader = match self.runner {
+                    Some(ref runner) => Downloader::from_config(&config).with_runner(Arc::clone(runner)),
+                    None => Downloader::from_config(&config),
+                };
+
+                async move { (index, downloader.fetch_video_info(&url).await) }
+            })
+            .buffer_unordered(self.concurrent_limit)
+            .collect()
+            .await;
+
+        let mut items = self.items.lock().await;
+        for (index, result) in results {
+            match result {
+                Ok(metadata) => {
+                    let config = items[index].options.apply_to(&self.config);
+                    let estimated_size = metadata.estimated_size(&config.quality, config.audio_only);
+                    let in_limits = config.fits_duration_limits(metadata.duration)
+                        && config.fits_view_limits(metadata.view_count)
+                        && config.fits_filesize_limits(estimated_size);
+                    if !in_limits {
+                        info!("Outside duration, view or file size limits, skipping {}", items[index].url);
+                        items[index].status = DownloadStatus::Filtered;
+                    }
+                    items[index].title = Some(metadata.title);
+                }
+                Err(e) => {
+                    warn!("Metadata unavailable for {}: {}", items[index].url, e);
+                    items[index].status = DownloadStatus::Failed(e.to_string());
+                }
+            }
+        }
+    }
+
     pub async fn download_all(&self) -> Result<BatchDownloadStats> {
         let total_count = {
             let items = self.items.lock().await;
@@ -154,6 +585,14 @@ impl BatchDownloader {
             total_count, self.concurrent_limit
         );
 
+        *self.started_at.lock().await = Some(Instant::now());
+
+        if let Some(ref path) = self.state_file {
+            let items = self.items.lock().await;
+            save_state(&items, path).await;
+        }
+
+        let streak = Arc::new(FailureStreak::new(self.max_consecutive_failures));
         let indices: Vec<usize> = (0..total_count).collect();
         let stream = stream::iter(indices)
             .map(|index| {
@@ -161,36 +600,67 @@ impl BatchDownloader {
                 let history = Arc::clone(&self.history);
                 let config = self.config.clone();
                 let stop_on_error = self.stop_on_error;
+                let pause = self.pause.clone();
+                let state_file = self.state_file.clone();
+                let streak = Arc::clone(&streak);
+                let concurrency = Arc::clone(&self.concurrency);
 
                 async move {
-                    Self::download_item(index, items, history, config, stop_on_error).await
+                    pause.wait_if_paused().await;
+                    if streak.tripped() {
+                        return Ok(());
+                    }
+
+                    // Skipped and previously finished items don't take a slot
+                    if items.lock().await[index].status != DownloadStatus::Pending {
+                        return Ok(());
+                    }
+                    let _slot = concurrency.acquire().await;
+
+                    let result =
+                        Self::download_item(index, Arc::clone(&items), history, config, stop_on_error, state_file)
+                            .await;
+                    let status = items.lock().await[index].status.clone();
+                    streak.record(&status);
+                    concurrency.record(&status);
+                    result
                 }
             })
             .buffer_unordered(self.concurrent_limit);
 
-        let results: Vec<Result<()>> = stream.collect().await;
+        let _results: Vec<Result<()>> = stream.collect().await;
 
-        let mut stats = BatchDownloadStats::default();
-        stats.total = total_count;
+        // Count from item status so items restored from a state file are included
+        let items = self.items.lock().await;
+        let count = |pred: fn(&DownloadStatus) -> bool| items.iter().filter(|i| pred(&i.status)).count();
+        let mut stats = BatchDownloadStats {
+            total: total_count,
+            successful: count(|s| *s == DownloadStatus::Complete),
+            failed: count(|s| matches!(s, DownloadStatus::Failed(_))),
+            skipped: count(|s| matches!(s, DownloadStatus::Skipped | DownloadStatus::Filtered)),
+            failures_file: None,
+            halted_after_failures: self.max_consecutive_failures.filter(|_| streak.tripped()),
+        };
+        drop(items);
 
-        for result in results {
-            match result {
-                Ok(_) => stats.successful += 1,
-                Err(_) => stats.failed += 1,
-            }
+        if let Some(limit) = stats.halted_after_failures {
+            warn!("Batch stopped after {} consecutive failures", limit);
         }
 
-        let items = self.items.lock().await;
-        stats.skipped = items
-            .iter()
-            .filter(|i| i.status == DownloadStatus::Skipped)
-            .count();
+        if let Some(ref path) = self.state_file {
+            if stats.successful + stats.skipped == stats.total {
+                debug!("Batch finished, removing state file: {:?}", path);
+                let _ = tokio::fs::remove_file(path).await;
+            }
+        }
 
         info!(
             "Batch download complete: {} successful, {} failed, {} skipped",
             stats.successful, stats.failed, stats.skipped
         );
 
+        stats.failures_file = self.write_failures_file().await;
+
         let history = self.history.lock().await;
         history.save()?;
 
@@ -203,13 +673,15 @@ impl BatchDownloader {
         history: Arc<Mutex<History>>,
         config: Config,
         stop_on_error: bool,
+        state_file: Option<PathBuf>,
     ) -> Result<()> {
-        let url = {
+        let (url, config) = {
             let items = items.lock().await;
-            if items[index].status == DownloadStatus::Skipped {
+            // Skipped items and items already finished in a previous run
+            if items[index].status != DownloadStatus::Pending {
                 return Ok(());
             }
-            items[index].url.clone()
+            (items[index].url.clone(), items[index].options.apply_to(&config))
         };
 
         {
@@ -219,12 +691,32 @@ impl BatchDownloader {
 
         info!("Starting download {}: {}", index + 1, url);
 
-        let downloader = Downloader::new(config.output_dir.clone(), config.quality.clone());
-
+        let downloader = Downloader::from_config(&config);
         let result = downloader.download(&url, config.audio_only).await;
 
         match result {
             Ok(output_path) => {
+                // Prefer the prefetched title; otherwise the template names files after the
+                // title (or the id with --id-filename), so no second metadata fetch is needed
+                let prefetched = items.lock().await[index].title.clone();
+                let title = prefetched
+                    .or_else(|| title_from_path(&output_path))
+                    .unwrap_or_else(|| url.clone());
+
+                if config.write_comments.unwrap_or(false) {
+                    if let Err(e) = export_comments(&output_path, config.sponsorblock_export.is_some()).await {
+                        warn!("Failed to export comments for download {}: {}", index + 1, e);
+                    }
+                }
+
+                if let Some(ref export_path) = config.sponsorblock_export {
+                    let dest = item_segments_path(export_path, &output_path);
+                    if let Err(e) = export_sponsor_segments(&output_path, &dest).await {
+                        warn!("Failed to export sponsor segments for download {}: {}", index + 1, e);
+                    }
+                }
+
+                let output_path = config.apply_filename_policy(output_path).await;
                 info!("Download {} complete: {:?}", index + 1, output_path);
 
                 let file_size = tokio::fs::metadata(&output_path)
@@ -232,8 +724,10 @@ impl BatchDownloader {
                     .map(|m| m.len())
                     .unwrap_or(0);
 
-                let video_info = downloader.fetch_video_info(&url).await.ok();
-                let title = video_info.as_ref().map(|v| v.title.clone()).unwrap_or_else(|| url.clone());
+                if !config.mirror_dirs.is_empty() {
+                    let copied = mirror_file(&output_path, &config.mirror_dirs).await;
+                    info!("Download {} mirrored: {} bytes copied", index + 1, copied);
+                }
 
                 {
                     let mut items = items.lock().await;
@@ -242,28 +736,48 @@ impl BatchDownloader {
                     items[index].progress = 100.0;
                     items[index].file_size = file_size;
                     items[index].title = Some(title.clone());
+                    if let Some(ref path) = state_file {
+                        save_state(&items, path).await;
+                    }
                 }
 
+                let sha256 = config.content_hash(&output_path).await;
+                let format = config.history_format(&output_path);
                 let entry = HistoryEntry::new(
                     url.clone(),
                     title,
                     output_path,
                     file_size,
                     config.quality.clone(),
-                    if config.audio_only { "mp3".to_string() } else { "mp4".to_string() },
-                );
+                    format,
+                )
+                .with_sha256(sha256);
 
                 let mut history = history.lock().await;
                 history.add_entry(entry);
 
                 Ok(())
             }
+            Err(YtdlError::Skipped(reason)) => {
+                info!("Download {} skipped by yt-dlp: {}", index + 1, reason);
+
+                let mut items = items.lock().await;
+                items[index].status = DownloadStatus::Filtered;
+                if let Some(ref path) = state_file {
+                    save_state(&items, path).await;
+                }
+
+                Ok(())
+            }
             Err(e) => {
                 error!("Download {} failed: {}", index + 1, e);
 
                 {
                     let mut items = items.lock().await;
                     items[index].status = DownloadStatus::Failed(e.to_string());
+                    if let Some(ref path) = state_file {
+                        save_state(&items, path).await;
+                    }
                 }
 
                 if stop_on_error {
@@ -280,6 +794,40 @@ impl BatchDownloader {
         items.clone()
     }
 
+    /// Write the URL of every failed item to `path`, one per line, so they can be
+    /// re-run with `--batch`. Nothing is written when no item failed.
+    /// Returns the number of failed URLs.
+    pub async fn write_failed_urls(&self, path: &Path) -> Result<usize> {
+        let failed: Vec<String> = self
+            .get_items()
+            .await
+            .into_iter()
+            .filter(|i| matches!(i.status, DownloadStatus::Failed(_)))
+            .map(|i| i.url)
+            .collect();
+
+        if !failed.is_empty() {
+            tokio::fs::write(path, failed.join("\n") + "\n").await?;
+            info!("Wrote {} failed URLs to {:?}", failed.len(), path);
+        }
+
+        Ok(failed.len())
+    }
+
+    /// Write failed URLs to the `with_failures_file` path. Returns the path
+    /// when anything was written.
+    async fn write_failures_file(&self) -> Option<PathBuf> {
+        let path = self.failures_file.as_ref()?;
+        match self.write_failed_urls(path).await {
+            Ok(0) => None,
+            Ok(_) => Some(path.clone()),
+            Err(e) => {
+                warn!("Failed to write failed URLs to {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
     pub async fn get_progress(&self) -> BatchProgress {
         let items = self.items.lock().await;
 
@@ -294,7 +842,7 @@ impl BatchDownloader {
             .count();
         let skipped = items
             .iter()
-            .filter(|i| i.status == DownloadStatus::Skipped)
+            .filter(|i| matches!(i.status, DownloadStatus::Skipped | DownloadStatus::Filtered))
             .count();
         let downloading = items
             .iter()
@@ -302,6 +850,7 @@ impl BatchDownloader {
             .count();
 
         let total_bytes: u64 = items.iter().map(|i| i.file_size).sum();
+        let elapsed = self.started_at.lock().await.map(|start| start.elapsed());
 
         BatchProgress {
             total,
@@ -310,8 +859,255 @@ impl BatchDownloader {
             skipped,
             downloading,
             total_bytes,
+            elapsed,
+            concurrency: self.concurrency.limit(),
+        }
+    }
+}
+
+async fn save_state(items: &[BatchDownloadItem], path: &Path) {
+    let content = match serde_json::to_string_pretty(items) {
+        Ok(content) => content,
+        Err(e) => {
+            warn!("Failed to serialize batch state: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = tokio::fs::write(path, content).await {
+        warn!("Failed to write batch state file {:?}: {}", path, e);
+    }
+}
+
+/// Every YouTube video and playlist link in `text` (plain text or HTML), in order of
+/// first appearance. Links to the same video in different forms are kept once.
+pub fn extract_youtube_urls(text: &str) -> Vec<String> {
+    let re = Regex::new(
+        r"https?://(?:(?:www\.|m\.)?youtube\.com/(?:watch\?(?:[\w=%.-]*&(?:amp;)?)*v=|shorts/)|youtu\.be/)([\w-]{11})|https?://(?:www\.|m\.)?youtube\.com/playlist\?list=([\w-]+)",
+    )
+    .unwrap();
+
+    let mut seen = HashSet::new();
+    re.captures_iter(text)
+        .map(|caps| match caps.get(1) {
+            Some(id) => normalize_url(&format!("https://youtu.be/{}", id.as_str())),
+            None => format!("https://www.youtube.com/playlist?list={}", &caps[2]),
+        })
+        .filter(|url| seen.insert(url.clone()))
+        .collect()
+}
+
+/// Plain text: one URL per line, `#` comments and blank lines ignored
+fn parse_txt_entries(content: &str) -> Vec<(usize, BatchFileEntry)> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(line_num, line)| (line_num + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_num, line)| {
+            (
+                line_num,
+                BatchFileEntry {
+                    url: line.to_string(),
+                    options: BatchItemOptions::default(),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Read and parse a batch file, detecting the format from the extension when not given.
+/// Entries are numbered by line (txt/csv) or array position (json).
+async fn read_batch_entries(path: &Path, format: Option<BatchFormat>) -> Result<Vec<(usize, BatchFileEntry)>> {
+    let content = tokio::fs::read_to_string(path).await.map_err(|e| {
+        error!("Failed to read batch file: {}", e);
+        YtdlError::Io(e)
+    })?;
+
+    let format = format.unwrap_or_else(|| BatchFormat::from_path(path));
+    debug!("Parsing batch file as {:?}", format);
+
+    match format {
+        BatchFormat::Txt => Ok(parse_txt_entries(&content)),
+        BatchFormat::Json => parse_json_entries(&content),
+        BatchFormat::Csv => parse_csv_entries(&content),
+    }
+}
+
+/// What `ytdl validate` found for one batch file entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryStatus {
+    Valid,
+    Invalid(String),
+    /// Same video as the entry with this number
+    Duplicate(usize),
+    /// Would be skipped as a duplicate of a history entry
+    InHistory,
+}
+
+#[derive(Debug, Clone)]
+pub struct EntryCheck {
+    pub entry: usize,
+    pub url: String,
+    pub status: EntryStatus,
+}
+
+/// Parse a batch file the way `--batch` does and check every entry, without any
+/// network calls
+pub async fn validate_batch_file(path: &Path, format: Option<BatchFormat>, history: &History) -> Result<Vec<EntryCheck>> {
+    let entries = read_batch_entries(path, format).await?;
+    let urls: Vec<(usize, String)> = entries.into_iter().map(|(entry, e)| (entry, e.url)).collect();
+    Ok(check_batch_entries(&urls, history))
+}
+
+/// Classify each URL: invalid first, then repeated within the file (by normalized
+/// URL), then already in history
+pub fn check_batch_entries(urls: &[(usize, String)], history: &History) -> Vec<EntryCheck> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    urls.iter()
+        .map(|(entry, url)| {
+            let status = if validate_youtube_url(url).is_err() {
+                EntryStatus::Invalid(invalid_url_reason(url))
+            } else if let Some(&first) = seen.get(&normalize_url(url)) {
+                EntryStatus::Duplicate(first)
+            } else {
+                seen.insert(normalize_url(url), *entry);
+                if history.contains_url(url) {
+                    EntryStatus::InHistory
+                } else {
+                    EntryStatus::Valid
+                }
+            };
+
+            EntryCheck {
+                entry: *entry,
+                url: url.clone(),
+                status,
+            }
+        })
+        .collect()
+}
+
+/// One-line reason a URL failed `validate_youtube_url`, whose error lists every accepted form
+fn invalid_url_reason(url: &str) -> String {
+    match url::Url::parse(url.trim()) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+            let host = parsed.host_str().unwrap_or("");
+            let host = host.strip_prefix("www.").unwrap_or(host);
+            if host == "youtube.com" || host == "youtu.be" {
+                "Not a video, playlist or shorts link".to_string()
+            } else {
+                format!("Not a YouTube link ({})", host)
+            }
+        }
+        _ => "Not an http(s) URL".to_string(),
+    }
+}
+
+/// JSON: an array of `{url, quality, audio_only, output}` objects
+fn parse_json_entries(content: &str) -> Result<Vec<(usize, BatchFileEntry)>> {
+    let entries: Vec<BatchFileEntry> = serde_json::from_str(content).map_err(|e| {
+        YtdlError::Config(format!("Failed to parse JSON batch file: {}", e))
+    })?;
+
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| (i + 1, entry))
+        .collect())
+}
+
+/// CSV: a header row naming the columns (`url` is required), then one item per row
+fn parse_csv_entries(content: &str) -> Result<Vec<(usize, BatchFileEntry)>> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(line_num, line)| (line_num + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let header: Vec<String> = match lines.next() {
+        Some((_, line)) => split_csv_line(line)
+            .into_iter()
+            .map(|h| h.to_lowercase())
+            .collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let url_col = column("url").ok_or_else(|| {
+        YtdlError::Config("CSV batch file is missing a 'url' column".to_string())
+    })?;
+    let quality_col = column("quality");
+    let audio_col = column("audio_only");
+    let output_col = column("output");
+
+    let mut entries = Vec::new();
+    for (line_num, line) in lines {
+        let fields = split_csv_line(line);
+        let field = |col: Option<usize>| {
+            col.and_then(|c| fields.get(c))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+        };
+
+        let url = match field(Some(url_col)) {
+            Some(url) => url.to_string(),
+            None => {
+                warn!("Missing URL on line {}", line_num);
+                continue;
+            }
+        };
+
+        let audio_only = match field(audio_col) {
+            Some(value) => match value.to_lowercase().as_str() {
+                "true" | "1" | "yes" => Some(true),
+                "false" | "0" | "no" => Some(false),
+                other => {
+                    warn!("Invalid audio_only value on line {}: {}", line_num, other);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        entries.push((
+            line_num,
+            BatchFileEntry {
+                url,
+                options: BatchItemOptions {
+                    quality: field(quality_col).map(|q| q.to_string()),
+                    audio_only,
+                    output: field(output_col).map(PathBuf::from),
+                },
+            },
+        ));
+    }
+
+    Ok(entries)
+}
+
+/// Split a CSV line on commas, honouring double-quoted fields and `""` escapes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
         }
     }
+    fields.push(current);
+
+    fields
 }
 
 #[derive(Debug, Default, Clone)]
@@ -320,6 +1116,10 @@ pub struct BatchDownloadStats {
     pub successful: usize,
     pub failed: usize,
     pub skipped: usize,
+    /// Where the failed URLs were written, if any item failed
+    pub failures_file: Option<PathBuf>,
+    /// Set to the limit when `max_consecutive_failures` stopped the batch early
+    pub halted_after_failures: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -330,6 +1130,11 @@ pub struct BatchProgress {
     pub skipped: usize,
     pub downloading: usize,
     pub total_bytes: u64,
+    /// Time since `download_all` started, if it has
+    pub elapsed: Option<Duration>,
+    /// Downloads currently allowed at once; below the configured limit after a
+    /// burst of failures
+    pub concurrency: usize,
 }
 
 impl BatchProgress {
@@ -340,11 +1145,32 @@ impl BatchProgress {
             ((self.complete + self.failed + self.skipped) as f32 / self.total as f32) * 100.0
         }
     }
+
+    /// Estimated seconds until the batch finishes.
+    ///
+    /// The average per-item time is wall-clock time divided by items finished so far,
+    /// so concurrent downloads are accounted for. Returns `None` until the first item
+    /// finishes. Skipped items take no time and are excluded from the average.
+    pub fn eta_secs(&self) -> Option<u64> {
+        let elapsed = self.elapsed?.as_secs_f64();
+        let finished = self.complete + self.failed;
+        if finished == 0 {
+            return None;
+        }
+
+        let remaining = self
+            .total
+            .saturating_sub(self.complete + self.failed + self.skipped);
+        let avg_per_item = elapsed / finished as f64;
+
+        Some((avg_per_item * remaining as f64).round() as u64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infra::command::MockRunner;
 
     #[tokio::test]
     async fn test_batch_download_item() {
@@ -365,4 +1191,393 @@ mod tests {
         let items = batch.get_items().await;
         assert_eq!(items.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_duplicate_url_skips_without_fetch() {
+        let url = "https://youtube.com/watch?v=dQw4w9WgXcQ";
+        let mut history = History::new();
+        history.add_entry(HistoryEntry::new(
+            url.to_string(),
+            "Already here".to_string(),
+            PathBuf::from("/tmp/Already here.mp4"),
+            1024,
+            "best".to_string(),
+            "mp4".to_string(),
+        ));
+
+        let mut batch = BatchDownloader::new(Config::default(), history, true);
+        batch.add_urls(vec![url.to_string()]).await.unwrap();
+        batch.skip_duplicates().await;
+
+        // Any yt-dlp call would move the item to Downloading and then Failed or Complete
+        BatchDownloader::download_item(
+            0,
+            Arc::clone(&batch.items),
+            Arc::clone(&batch.history),
+            batch.config.clone(),
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let items = batch.get_items().await;
+        assert_eq!(items[0].status, DownloadStatus::Skipped);
+        assert!(items[0].title.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        let batch = BatchDownloader::new(Config::default(), History::new(), false);
+        let handle = batch.pause_handle();
+        assert!(!handle.is_paused());
+
+        handle.pause();
+        assert!(batch.pause.is_paused());
+
+        let pause = batch.pause.clone();
+        let waiter = tokio::spawn(async move { pause.wait_if_paused().await });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        handle.resume();
+        waiter.await.unwrap();
+        assert!(!batch.pause.is_paused());
+    }
+
+    #[test]
+    fn test_batch_state_path() {
+        assert_eq!(
+            batch_state_path(Path::new("lists/music.txt")),
+            PathBuf::from("lists/.music.batch-state.json")
+        );
+        assert_ne!(batch_state_path(Path::new("a.txt")), batch_state_path(Path::new("b.txt")));
+    }
+
+    #[tokio::test]
+    async fn test_load_state_resets_in_flight_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(BATCH_STATE_FILE);
+
+        let mut done = BatchDownloadItem::new("https://youtube.com/watch?v=a".to_string());
+        done.status = DownloadStatus::Complete;
+        let mut in_flight = BatchDownloadItem::new("https://youtube.com/watch?v=b".to_string());
+        in_flight.status = DownloadStatus::Downloading;
+        save_state(&[done, in_flight], &path).await;
+
+        let mut batch = BatchDownloader::new(Config::default(), History::new(), false);
+        batch.load_state(&path).await.unwrap();
+
+        let items = batch.get_items().await;
+        assert_eq!(items[0].status, DownloadStatus::Complete);
+        assert_eq!(items[1].status, DownloadStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_write_failed_urls() {
+        let dir = tempfile::tempdir().unwrap();
+        let failed_file = dir.path().join("failed.txt");
+
+        let mut batch = BatchDownloader::new(Config::default(), History::new(), false);
+        batch
+            .add_urls(vec![
+                "https://youtube.com/watch?v=a".to_string(),
+                "https://youtube.com/watch?v=b".to_string(),
+                "https://youtube.com/watch?v=c".to_string(),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(batch.write_failed_urls(&failed_file).await.unwrap(), 0);
+        assert!(!failed_file.exists());
+
+        {
+            let mut items = batch.items.lock().await;
+            items[0].status = DownloadStatus::Failed("Video unavailable".to_string());
+            items[1].status = DownloadStatus::Complete;
+            items[2].status = DownloadStatus::Failed("Network error".to_string());
+        }
+
+        assert_eq!(batch.write_failed_urls(&failed_file).await.unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(&failed_file).unwrap(),
+            "https://youtube.com/watch?v=a\nhttps://youtube.com/watch?v=c\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failures_file_written_only_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let failures_file = dir.path().join("failed.txt");
+
+        let mut batch = BatchDownloader::new(Config::default(), History::new(), false);
+        assert_eq!(batch.write_failures_file().await, None);
+
+        batch = batch.with_failures_file(failures_file.clone());
+        batch
+            .add_urls(vec![
+                "https://youtube.com/watch?v=a".to_string(),
+                "https://youtube.com/watch?v=b".to_string(),
+            ])
+            .await
+            .unwrap();
+        batch.items.lock().await[0].status = DownloadStatus::Complete;
+        batch.items.lock().await[1].status = DownloadStatus::Complete;
+        assert_eq!(batch.write_failures_file().await, None);
+        assert!(!failures_file.exists());
+
+        batch.items.lock().await[1].status = DownloadStatus::Failed("HTTP 403".to_string());
+        assert_eq!(batch.write_failures_file().await, Some(failures_file.clone()));
+        assert_eq!(
+            std::fs::read_to_string(&failures_file).unwrap(),
+            "https://youtube.com/watch?v=b\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_metadata_marks_unavailable_items() {
+        let json = r#"{"id": "good", "title": "Available Video", "uploader": "Someone", "formats": []}"#;
+        let runner = MockRunner::new(json, "", 0)
+            .failing_for("v=gone", "ERROR: [youtube] gone: Video unavailable\n", 1);
+
+        let mut batch = BatchDownloader::new(Config::default(), History::new(), false)
+            .with_runner(Arc::new(runner));
+        batch
+            .add_urls(vec![
+                "https://youtube.com/watch?v=good".to_string(),
+                "https://youtube.com/watch?v=gone".to_string(),
+                "https://youtube.com/watch?v=skip".to_string(),
+            ])
+            .await
+            .unwrap();
+        batch.items.lock().await[2].status = DownloadStatus::Skipped;
+
+        batch.prefetch_metadata().await;
+
+        let items = batch.get_items().await;
+        assert_eq!(items[0].status, DownloadStatus::Pending);
+        assert_eq!(items[0].title.as_deref(), Some("Available Video"));
+        assert!(matches!(items[1].status, DownloadStatus::Failed(ref e) if e.contains("unavailable")));
+        assert!(items[1].title.is_none());
+        assert_eq!(items[2].status, DownloadStatus::Skipped);
+        assert!(items[2].title.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_metadata_filters_items_outside_limits() {
+        let json = r#"{"id": "few", "title": "Few Views", "view_count": 10, "formats": []}"#;
+        let config = Config {
+            min_views: Some(1_000),
+            ..Config::default()
+        };
+
+        let mut batch = BatchDownloader::new(config, History::new(), false)
+            .with_runner(Arc::new(MockRunner::new(json, "", 0)));
+        batch.add_urls(vec!["https://youtube.com/watch?v=few".to_string()]).await.unwrap();
+
+        batch.prefetch_metadata().await;
+
+        let items = batch.get_items().await;
+        assert_eq!(items[0].status, DownloadStatus::Filtered);
+        assert_eq!(batch.get_progress().await.skipped, 1);
+    }
+
+    #[test]
+    fn test_failure_streak_resets_on_success() {
+        let failed = DownloadStatus::Failed("HTTP 403".to_string());
+        let streak = FailureStreak::new(Some(3));
+
+        streak.record(&failed);
+        streak.record(&failed);
+        streak.record(&DownloadStatus::Complete);
+        streak.record(&failed);
+        streak.record(&DownloadStatus::Skipped);
+        streak.record(&failed);
+        assert!(!streak.tripped());
+
+        streak.record(&failed);
+        assert!(streak.tripped());
+
+        let unlimited = FailureStreak::new(None);
+        for _ in 0..10 {
+            unlimited.record(&failed);
+        }
+        assert!(!unlimited.tripped());
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_concurrency_backs_off_and_recovers() {
+        let failed = DownloadStatus::Failed("HTTP 429".to_string());
+        let concurrency = Arc::new(AdaptiveConcurrency::new(4));
+
+        // Too few outcomes to judge, then half failing is still tolerated
+        concurrency.record(&failed);
+        concurrency.record(&failed);
+        assert_eq!(concurrency.limit(), 4);
+        for _ in 0..3 {
+            concurrency.record(&DownloadStatus::Complete);
+        }
+        for _ in 0..3 {
+            concurrency.record(&failed);
+        }
+        assert_eq!(concurrency.limit(), 4);
+
+        // 4 of the last 6 failed
+        concurrency.record(&failed);
+        assert_eq!(concurrency.limit(), 2);
+        concurrency.record(&failed);
+        concurrency.record(&failed);
+        assert_eq!(concurrency.limit(), 2);
+        concurrency.record(&failed);
+        assert_eq!(concurrency.limit(), 1);
+        for _ in 0..5 {
+            concurrency.record(&failed);
+        }
+        assert_eq!(concurrency.limit(), 1);
+
+        // One more slot per run of successes, never above the configured limit
+        for _ in 0..RECOVERY_SUCCESSES {
+            concurrency.record(&DownloadStatus::Complete);
+        }
+        assert_eq!(concurrency.limit(), 2);
+        concurrency.record(&DownloadStatus::Skipped);
+        for _ in 0..RECOVERY_SUCCESSES * 10 {
+            concurrency.record(&DownloadStatus::Complete);
+        }
+        assert_eq!(concurrency.limit(), 4);
+
+        let slots: Vec<_> = futures::future::join_all((0..4).map(|_| concurrency.acquire())).await;
+        assert!(tokio::time::timeout(Duration::from_millis(20), concurrency.acquire()).await.is_err());
+        drop(slots);
+        assert!(tokio::time::timeout(Duration::from_millis(20), concurrency.acquire()).await.is_ok());
+    }
+
+    fn progress(total: usize, complete: usize, skipped: usize, elapsed: Option<u64>) -> BatchProgress {
+        BatchProgress {
+            total,
+            complete,
+            failed: 0,
+            skipped,
+            downloading: 0,
+            total_bytes: 0,
+            elapsed: elapsed.map(Duration::from_secs),
+            concurrency: 3,
+        }
+    }
+
+    #[test]
+    fn test_eta_secs() {
+        assert_eq!(progress(10, 0, 0, None).eta_secs(), None);
+        assert_eq!(progress(10, 0, 0, Some(30)).eta_secs(), None);
+        assert_eq!(progress(10, 2, 0, Some(60)).eta_secs(), Some(240));
+        assert_eq!(progress(10, 2, 4, Some(60)).eta_secs(), Some(120));
+        assert_eq!(progress(4, 4, 0, Some(60)).eta_secs(), Some(0));
+    }
+
+    #[test]
+    fn test_batch_format_from_path() {
+        assert_eq!(BatchFormat::from_path(Path::new("urls.txt")), BatchFormat::Txt);
+        assert_eq!(BatchFormat::from_path(Path::new("urls.JSON")), BatchFormat::Json);
+        assert_eq!(BatchFormat::from_path(Path::new("urls.csv")), BatchFormat::Csv);
+        assert_eq!(BatchFormat::from_path(Path::new("urls")), BatchFormat::Txt);
+    }
+
+    #[test]
+    fn test_parse_json_entries() {
+        let content = r#"[
+            {"url": "https://youtube.com/watch?v=a", "quality": "720", "audio_only": false},
+            {"url": "https://youtube.com/watch?v=b", "audio_only": true, "output": "/tmp/music"}
+        ]"#;
+
+        let entries = parse_json_entries(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].1.options.quality.as_deref(), Some("720"));
+        assert_eq!(entries[1].1.options.audio_only, Some(true));
+        assert_eq!(entries[1].1.options.output, Some(PathBuf::from("/tmp/music")));
+    }
+
+    #[test]
+    fn test_parse_csv_entries() {
+        let content = "url,quality,audio_only,output\n\
+                       https://youtube.com/watch?v=a,1080,,\n\
+                       \"https://youtube.com/watch?v=b\",,yes,\"/tmp/a, b\"\n";
+
+        let entries = parse_csv_entries(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].1.url, "https://youtube.com/watch?v=a");
+        assert_eq!(entries[0].1.options.quality.as_deref(), Some("1080"));
+        assert_eq!(entries[0].1.options.audio_only, None);
+        assert_eq!(entries[1].1.options.audio_only, Some(true));
+        assert_eq!(entries[1].1.options.output, Some(PathBuf::from("/tmp/a, b")));
+    }
+
+    #[test]
+    fn test_item_options_override_config() {
+        let base = Config::default();
+        let options = BatchItemOptions {
+            quality: Some("480".to_string()),
+            audio_only: Some(true),
+            output: None,
+        };
+
+        let config = options.apply_to(&base);
+        assert_eq!(config.quality, "480");
+        assert!(config.audio_only);
+        assert_eq!(config.output_dir, base.output_dir);
+    }
+
+    #[test]
+    fn test_check_batch_entries() {
+        let mut history = History::new();
+        history.add_entry(HistoryEntry::new(
+            "https://youtube.com/watch?v=aaaaaaaaaaa".to_string(),
+            "Old".to_string(),
+            PathBuf::from("/tmp/Old.mp4"),
+            1,
+            "best".to_string(),
+            "mp4".to_string(),
+        ));
+
+        let urls: Vec<(usize, String)> = [
+            (1, "https://youtube.com/watch?v=dQw4w9WgXcQ"),
+            (2, "https://example.com/video"),
+            (4, "https://youtu.be/dQw4w9WgXcQ"),
+            (5, "https://youtube.com/watch?v=aaaaaaaaaaa"),
+        ]
+        .iter()
+        .map(|(entry, url)| (*entry, url.to_string()))
+        .collect();
+
+        let statuses: Vec<EntryStatus> = check_batch_entries(&urls, &history)
+            .into_iter()
+            .map(|check| check.status)
+            .collect();
+        assert_eq!(statuses[0], EntryStatus::Valid);
+        assert_eq!(statuses[1], EntryStatus::Invalid("Not a YouTube link (example.com)".to_string()));
+        assert_eq!(statuses[2], EntryStatus::Duplicate(1));
+        assert_eq!(statuses[3], EntryStatus::InHistory);
+    }
+
+    #[test]
+    fn test_extract_youtube_urls_from_html() {
+        let html = r#"
+            <p>Watch <a href="https://www.youtube.com/watch?v=dQw4w9WgXcQ&amp;t=30s">this</a>
+            or the <a href='https://youtu.be/dQw4w9WgXcQ'>short link</a>.</p>
+            <a href="https://m.youtube.com/watch?feature=share&amp;v=9bZkp7q19f0">mobile</a>
+            <a href="https://youtube.com/shorts/aqz-KE-bpKQ">short</a>
+            <a href="https://vimeo.com/123456">elsewhere</a>
+            Full list: https://www.youtube.com/playlist?list=PLtest123
+        "#;
+
+        assert_eq!(
+            extract_youtube_urls(html),
+            vec![
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                "https://www.youtube.com/watch?v=9bZkp7q19f0",
+                "https://www.youtube.com/watch?v=aqz-KE-bpKQ",
+                "https://www.youtube.com/playlist?list=PLtest123",
+            ]
+        );
+        assert!(extract_youtube_urls("<p>No videos here</p>").is_empty());
+    }
 }
@@ -1,19 +1,31 @@
+use crate::shared::utils::csv_field;
 use crate::shared::{Result, YtdlError};
 use chrono::{DateTime, Utc};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use tracing::{debug, error, info};
 
+/// Fields other than `url` and `timestamp` default when missing so histories
+/// written by older versions still load
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistoryEntry {
     pub url: String,
+    #[serde(default)]
     pub title: String,
+    #[serde(default)]
     pub file_path: PathBuf,
+    #[serde(default)]
     pub file_size: u64,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
     pub quality: String,
+    #[serde(default)]
     pub format: String,
+    /// Hex SHA-256 of the file, only recorded with `--hash`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
 }
 
 impl HistoryEntry {
@@ -33,12 +45,90 @@ impl HistoryEntry {
             timestamp: Utc::now(),
             quality,
             format,
+            sha256: None,
         }
     }
+
+    pub fn with_sha256(mut self, sha256: Option<String>) -> Self {
+        self.sha256 = sha256;
+        self
+    }
+}
+
+/// A history entry whose file no longer matches what was recorded
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationIssue {
+    Missing {
+        url: String,
+        file_path: PathBuf,
+    },
+    SizeMismatch {
+        url: String,
+        file_path: PathBuf,
+        expected: u64,
+        actual: u64,
+    },
+}
+
+impl VerificationIssue {
+    pub fn file_path(&self) -> &Path {
+        match self {
+            Self::Missing { file_path, .. } | Self::SizeMismatch { file_path, .. } => file_path,
+        }
+    }
+}
+
+/// Granularity of a `History::usage_by` report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsagePeriod {
+    Day,
+    Month,
+}
+
+impl UsagePeriod {
+    /// Bucket label for `timestamp`, e.g. `2024-03-15` or `2024-03`
+    pub fn label(&self, timestamp: &DateTime<Utc>) -> String {
+        match self {
+            Self::Day => timestamp.format("%Y-%m-%d").to_string(),
+            Self::Month => timestamp.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// Canonical form of a video URL, so `youtu.be/ID`, `m.youtube.com/watch?v=ID&t=30`
+/// and `youtube.com/shorts/ID` compare equal
+pub fn normalize_url(url: &str) -> String {
+    let trimmed = url.trim();
+
+    if let Ok(parsed) = url::Url::parse(trimmed) {
+        let host = parsed.host_str().unwrap_or("");
+        let host = host.strip_prefix("www.").or_else(|| host.strip_prefix("m.")).unwrap_or(host);
+        let mut segments = parsed.path_segments().into_iter().flatten();
+
+        let id = match host {
+            "youtu.be" => segments.next().map(|s| s.to_string()),
+            "youtube.com" | "music.youtube.com" => match segments.next() {
+                Some("watch") => parsed
+                    .query_pairs()
+                    .find(|(key, _)| key == "v")
+                    .map(|(_, value)| value.into_owned()),
+                Some("shorts" | "live" | "embed") => segments.next().map(|s| s.to_string()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some(id) = id.filter(|id| !id.is_empty()) {
+            return format!("https://www.youtube.com/watch?v={}", id);
+        }
+    }
+
+    trimmed.trim_end_matches('/').to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct History {
+    #[serde(default)]
     entries: Vec<HistoryEntry>,
 }
 
@@ -57,7 +147,12 @@ impl History {
             return Ok(Self::new());
         }
 
-        let content = std::fs::read_to_string(&path).map_err(|e| {
+        Self::load_from(&path)
+    }
+
+    /// Load a history file from `path`, e.g. one copied from another machine
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
             error!("Failed to read history file: {}", e);
             YtdlError::Io(e)
         })?;
@@ -67,13 +162,16 @@ impl History {
             YtdlError::Other(format!("Failed to parse history: {}", e))
         })?;
 
-        info!("Loaded {} history entries", history.entries.len());
+        info!("Loaded {} history entries from {:?}", history.entries.len(), path);
         Ok(history)
     }
 
     pub fn save(&self) -> Result<()> {
         let path = Self::get_history_path()?;
+        self.save_to(&path)
+    }
 
+    fn save_to(&self, path: &Path) -> Result<()> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
                 error!("Failed to create history directory: {}", e);
@@ -86,7 +184,7 @@ impl History {
             YtdlError::Other(format!("Failed to serialize history: {}", e))
         })?;
 
-        std::fs::write(&path, content).map_err(|e| {
+        std::fs::write(path, content).map_err(|e| {
             error!("Failed to write history file: {}", e);
             YtdlError::Io(e)
         })?;
@@ -95,11 +193,149 @@ impl History {
         Ok(())
     }
 
+    /// Write a copy of the current history to `history.bak.json`
+    pub fn backup(&self) -> Result<PathBuf> {
+        let path = Self::get_backup_path()?;
+        self.backup_to(&path)?;
+        Ok(path)
+    }
+
+    /// Write a copy of the current history to `history.edit.bak.json`, before a
+    /// merge or dedupe. `history.bak.json`, the `clear-history` undo, is left alone
+    pub fn backup_before_edit(&self) -> Result<PathBuf> {
+        let path = Self::get_history_path()?.with_file_name("history.edit.bak.json");
+        self.backup_to(&path)?;
+        Ok(path)
+    }
+
+    fn backup_to(&self, path: &Path) -> Result<()> {
+        self.save_to(path)?;
+        info!("Backed up {} history entries to {:?}", self.entries.len(), path);
+        Ok(())
+    }
+
+    /// Load the history saved by the last `backup()`
+    pub fn restore() -> Result<Self> {
+        let path = Self::get_backup_path()?;
+        Self::restore_from(&path)
+    }
+
+    fn restore_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(YtdlError::Other(format!(
+                "No history backup found at {:?}",
+                path
+            )));
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            error!("Failed to read history backup: {}", e);
+            YtdlError::Io(e)
+        })?;
+
+        let history: History = serde_json::from_str(&content).map_err(|e| {
+            error!("Failed to parse history backup: {}", e);
+            YtdlError::Other(format!("Failed to parse history backup: {}", e))
+        })?;
+
+        info!("Restored {} history entries from {:?}", history.entries.len(), path);
+        Ok(history)
+    }
+
     pub fn add_entry(&mut self, entry: HistoryEntry) {
         info!("Adding history entry: {}", entry.title);
         self.entries.push(entry);
     }
 
+    /// Merge entries from `other`, deduplicating by normalized URL and keeping the
+    /// newer entry. Returns the number of entries added or replaced.
+    pub fn merge(&mut self, other: History) -> usize {
+        let mut changed = 0;
+
+        for entry in other.entries {
+            let key = normalize_url(&entry.url);
+            match self.entries.iter_mut().find(|e| normalize_url(&e.url) == key) {
+                Some(existing) => {
+                    if entry.timestamp > existing.timestamp {
+                        *existing = entry;
+                        changed += 1;
+                    }
+                }
+                None => {
+                    self.entries.push(entry);
+                    changed += 1;
+                }
+            }
+        }
+
+        info!("Merged history: {} entries added or updated", changed);
+        changed
+    }
+
+    /// Collapse entries for the same normalized URL (e.g. a download repeated with
+    /// `--resume`) into one, keeping the newest entry with the largest recorded size.
+    /// Returns the number of duplicates removed.
+    pub fn deduplicate(&mut self) -> usize {
+        let before_count = self.entries.len();
+        let mut kept: Vec<HistoryEntry> = Vec::with_capacity(before_count);
+
+        for entry in std::mem::take(&mut self.entries) {
+            let key = normalize_url(&entry.url);
+            match kept.iter_mut().find(|e| normalize_url(&e.url) == key) {
+                Some(existing) => {
+                    let file_size = existing.file_size.max(entry.file_size);
+                    if entry.timestamp > existing.timestamp {
+                        *existing = entry;
+                    }
+                    existing.file_size = file_size;
+                }
+                None => kept.push(entry),
+            }
+        }
+
+        self.entries = kept;
+        let removed = before_count - self.entries.len();
+        info!("Removed {} duplicate history entries", removed);
+        removed
+    }
+
+    /// Entries recorded with the same SHA-256, e.g. a video reuploaded under another
+    /// URL. Each group holds at least two entries; entries without a hash are ignored.
+    pub fn find_duplicates(&self) -> Vec<Vec<&HistoryEntry>> {
+        let mut groups: Vec<Vec<&HistoryEntry>> = Vec::new();
+
+        for entry in &self.entries {
+            let Some(ref hash) = entry.sha256 else {
+                continue;
+            };
+            match groups.iter_mut().find(|g| g[0].sha256.as_ref() == Some(hash)) {
+                Some(group) => group.push(entry),
+                None => groups.push(vec![entry]),
+            }
+        }
+
+        groups.retain(|g| g.len() > 1);
+        groups
+    }
+
+    /// Total recorded size of entries downloaded in `[from, to)`
+    pub fn bytes_downloaded_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> u64 {
+        self.entries
+            .iter()
+            .filter(|e| e.timestamp >= from && e.timestamp < to)
+            .map(|e| e.file_size)
+            .sum()
+    }
+
+    /// Recorded bytes per day or month, oldest first
+    pub fn usage_by(&self, period: UsagePeriod) -> Vec<(String, u64)> {
+        let mut buckets: BTreeMap<String, u64> = BTreeMap::new();
+        for entry in &self.entries {
+            *buckets.entry(period.label(&entry.timestamp)).or_default() += entry.file_size;
+        }
+        buckets.into_iter().collect()
+    }
+
     pub fn contains_url(&self, url: &str) -> bool {
         self.entries.iter().any(|e| e.url == url)
     }
@@ -125,6 +361,15 @@ impl History {
             .collect()
     }
 
+    /// Remove every entry for `url`, returning whether anything was removed
+    pub fn remove_by_url(&mut self, url: &str) -> bool {
+        let before_count = self.entries.len();
+        self.entries.retain(|e| e.url != url);
+        let removed = before_count - self.entries.len();
+        info!("Removed {} history entries for {}", removed, url);
+        removed > 0
+    }
+
     pub fn clear(&mut self) {
         info!("Clearing all history entries");
         self.entries.clear();
@@ -138,19 +383,77 @@ impl History {
         info!("Removed {} entries older than {} days", removed, days);
     }
 
+    /// Remove the oldest entries until the total recorded size fits in `max_bytes`.
+    ///
+    /// Returns the number of entries removed.
+    pub fn prune_to_size(&mut self, max_bytes: u64) -> usize {
+        let mut total: u64 = self.entries.iter().map(|e| e.file_size).sum();
+        let before_count = self.entries.len();
+
+        self.entries.sort_by_key(|e| e.timestamp);
+        let excess = self
+            .entries
+            .iter()
+            .take_while(|e| {
+                if total <= max_bytes {
+                    return false;
+                }
+                total -= e.file_size;
+                true
+            })
+            .count();
+        self.entries.drain(..excess);
+
+        let removed = before_count - self.entries.len();
+        info!("Pruned {} entries to fit {} bytes", removed, max_bytes);
+        removed
+    }
+
+    /// Check that each entry's file still exists with the recorded size
+    pub fn verify_files(&self) -> Vec<VerificationIssue> {
+        let issues: Vec<VerificationIssue> = self
+            .entries
+            .iter()
+            .filter_map(|e| match std::fs::metadata(&e.file_path) {
+                Err(_) => Some(VerificationIssue::Missing {
+                    url: e.url.clone(),
+                    file_path: e.file_path.clone(),
+                }),
+                Ok(m) if m.len() != e.file_size => Some(VerificationIssue::SizeMismatch {
+                    url: e.url.clone(),
+                    file_path: e.file_path.clone(),
+                    expected: e.file_size,
+                    actual: m.len(),
+                }),
+                Ok(_) => None,
+            })
+            .collect();
+
+        info!("Verified {} entries, {} issues", self.entries.len(), issues.len());
+        issues
+    }
+
+    /// Remove the entries referenced by `issues`, returning how many were removed
+    pub fn remove_unverified(&mut self, issues: &[VerificationIssue]) -> usize {
+        let before_count = self.entries.len();
+        self.entries
+            .retain(|e| !issues.iter().any(|i| i.file_path() == e.file_path));
+        before_count - self.entries.len()
+    }
+
     pub fn export_to_csv(&self, path: &Path) -> Result<()> {
         let mut csv_content = String::from("URL,Title,File Path,File Size,Timestamp,Quality,Format\n");
 
         for entry in &self.entries {
             csv_content.push_str(&format!(
-                "\"{}\",\"{}\",\"{}\",{},\"{}\",\"{}\",\"{}\"\n",
-                entry.url.replace("\"", "\"\""),
-                entry.title.replace("\"", "\"\""),
-                entry.file_path.display().to_string().replace("\"", "\"\""),
+                "{},{},{},{},\"{}\",{},{}\n",
+                csv_field(&entry.url),
+                csv_field(&entry.title),
+                csv_field(&entry.file_path.display().to_string()),
                 entry.file_size,
                 entry.timestamp.to_rfc3339(),
-                entry.quality.replace("\"", "\"\""),
-                entry.format.replace("\"", "\"\"")
+                csv_field(&entry.quality),
+                csv_field(&entry.format)
             ));
         }
 
@@ -179,6 +482,10 @@ impl History {
         Ok(proj_dirs.data_local_dir().join("history.json"))
     }
 
+    fn get_backup_path() -> Result<PathBuf> {
+        Ok(Self::get_history_path()?.with_file_name("history.bak.json"))
+    }
+
     pub fn get_history_file_path() -> Option<PathBuf> {
         Self::get_history_path().ok()
     }
@@ -236,4 +543,295 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "Rust Tutorial");
     }
+
+    #[test]
+    fn test_remove_by_url() {
+        let mut history = History::new();
+        for (id, title) in [("1", "Rust Tutorial"), ("2", "Python Guide")] {
+            history.add_entry(HistoryEntry::new(
+                format!("https://youtube.com/watch?v={}", id),
+                title.to_string(),
+                PathBuf::from(format!("/tmp/{}.mp4", id)),
+                1024,
+                "720p".to_string(),
+                "mp4".to_string(),
+            ));
+        }
+
+        assert!(history.remove_by_url("https://youtube.com/watch?v=1"));
+        assert_eq!(history.len(), 1);
+        assert!(!history.contains_url("https://youtube.com/watch?v=1"));
+        assert!(history.contains_url("https://youtube.com/watch?v=2"));
+
+        assert!(!history.remove_by_url("https://youtube.com/watch?v=1"));
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_prune_to_size() {
+        let mut history = History::new();
+        let now = Utc::now();
+        // Added out of order to check pruning goes by timestamp, not insertion order
+        for (id, size, age_days) in [("new", 300, 1), ("oldest", 500, 10), ("old", 400, 5)] {
+            let mut entry = HistoryEntry::new(
+                format!("https://youtube.com/watch?v={}", id),
+                id.to_string(),
+                PathBuf::from(format!("/tmp/{}.mp4", id)),
+                size,
+                "720p".to_string(),
+                "mp4".to_string(),
+            );
+            entry.timestamp = now - chrono::Duration::days(age_days);
+            history.add_entry(entry);
+        }
+
+        assert_eq!(history.prune_to_size(500), 2);
+        assert_eq!(history.len(), 1);
+        assert!(history.contains_url("https://youtube.com/watch?v=new"));
+
+        assert_eq!(history.prune_to_size(300), 0);
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_verify_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let present = dir.path().join("present.mp4");
+        let resized = dir.path().join("resized.mp4");
+        let missing = dir.path().join("missing.mp4");
+        std::fs::write(&present, b"12345").unwrap();
+        std::fs::write(&resized, b"123").unwrap();
+
+        let mut history = History::new();
+        for (id, path) in [("1", &present), ("2", &resized), ("3", &missing)] {
+            history.add_entry(HistoryEntry::new(
+                format!("https://youtube.com/watch?v={}", id),
+                id.to_string(),
+                path.clone(),
+                5,
+                "720p".to_string(),
+                "mp4".to_string(),
+            ));
+        }
+
+        let issues = history.verify_files();
+        assert_eq!(issues.len(), 2);
+        assert!(issues.contains(&VerificationIssue::SizeMismatch {
+            url: "https://youtube.com/watch?v=2".to_string(),
+            file_path: resized,
+            expected: 5,
+            actual: 3,
+        }));
+        assert!(issues.contains(&VerificationIssue::Missing {
+            url: "https://youtube.com/watch?v=3".to_string(),
+            file_path: missing,
+        }));
+
+        assert_eq!(history.remove_unverified(&issues), 2);
+        assert_eq!(history.len(), 1);
+        assert!(history.contains_url("https://youtube.com/watch?v=1"));
+    }
+
+    #[test]
+    fn test_backup_restore_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.bak.json");
+
+        let mut history = History::new();
+        history.add_entry(HistoryEntry::new(
+            "https://youtube.com/watch?v=1".to_string(),
+            "Rust Tutorial".to_string(),
+            PathBuf::from("/tmp/1.mp4"),
+            1024,
+            "720p".to_string(),
+            "mp4".to_string(),
+        ));
+        history.backup_to(&path).unwrap();
+
+        history.clear();
+        assert!(history.is_empty());
+
+        let restored = History::restore_from(&path).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert!(restored.contains_url("https://youtube.com/watch?v=1"));
+    }
+
+    #[test]
+    fn test_merge_keeps_newer_entries() {
+        let now = Utc::now();
+        let entry = |url: &str, title: &str, age_days: i64| {
+            let mut entry = HistoryEntry::new(
+                url.to_string(),
+                title.to_string(),
+                PathBuf::from(format!("/tmp/{}.mp4", title)),
+                1024,
+                "720p".to_string(),
+                "mp4".to_string(),
+            );
+            entry.timestamp = now - chrono::Duration::days(age_days);
+            entry
+        };
+
+        let mut history = History::new();
+        history.add_entry(entry("https://youtube.com/watch?v=a", "a-local", 5));
+        history.add_entry(entry("https://youtube.com/watch?v=b", "b-local", 1));
+
+        let mut other = History::new();
+        other.add_entry(entry("https://youtu.be/a", "a-remote", 2));
+        other.add_entry(entry("https://www.youtube.com/watch?v=b&t=30", "b-remote", 3));
+        other.add_entry(entry("https://youtube.com/shorts/c", "c-remote", 4));
+
+        assert_eq!(history.merge(other), 2);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.get_entry_by_url("https://youtu.be/a").unwrap().title, "a-remote");
+        assert_eq!(
+            history.get_entry_by_url("https://youtube.com/watch?v=b").unwrap().title,
+            "b-local"
+        );
+        assert!(history.contains_url("https://youtube.com/shorts/c"));
+    }
+
+    #[test]
+    fn test_deduplicate_keeps_newest_entry() {
+        let now = Utc::now();
+        let entry = |url: &str, title: &str, size: u64, age_days: i64| {
+            let mut entry = HistoryEntry::new(
+                url.to_string(),
+                title.to_string(),
+                PathBuf::from("/tmp/video.mp4"),
+                size,
+                "720p".to_string(),
+                "mp4".to_string(),
+            );
+            entry.timestamp = now - chrono::Duration::days(age_days);
+            entry
+        };
+
+        let mut history = History::new();
+        history.add_entry(entry("https://youtube.com/watch?v=a", "a-old", 4096, 3));
+        history.add_entry(entry("https://youtube.com/watch?v=b", "b", 100, 2));
+        history.add_entry(entry("https://youtu.be/a", "a-new", 1024, 1));
+        history.add_entry(entry("https://youtube.com/watch?v=a", "a-older", 512, 5));
+        history.add_entry(entry("https://youtube.com/watch?v=c", "c", 100, 1));
+
+        assert_eq!(history.deduplicate(), 2);
+        assert_eq!(history.len(), 3);
+
+        let a = history.get_entry_by_url("https://youtu.be/a").unwrap();
+        assert_eq!(a.title, "a-new");
+        assert_eq!(a.file_size, 4096);
+        assert!(history.contains_url("https://youtube.com/watch?v=b"));
+        assert!(history.contains_url("https://youtube.com/watch?v=c"));
+
+        assert_eq!(history.deduplicate(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_by_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &[u8]| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+        let files = [
+            ("https://youtu.be/a", write("a.mp4", b"same video")),
+            ("https://youtu.be/b", write("b.mp4", b"other video")),
+            ("https://youtu.be/c", write("c.mp4", b"same video")),
+        ];
+
+        let mut history = History::new();
+        for (url, path) in &files {
+            let hash = crate::shared::utils::sha256_file(path).await.unwrap();
+            history.add_entry(
+                HistoryEntry::new(url.to_string(), "t".to_string(), path.clone(), 10, "best".to_string(), "mp4".to_string())
+                    .with_sha256(Some(hash)),
+            );
+        }
+        history.add_entry(HistoryEntry::new(
+            "https://youtu.be/d".to_string(),
+            "unhashed".to_string(),
+            PathBuf::from("/tmp/d.mp4"),
+            10,
+            "best".to_string(),
+            "mp4".to_string(),
+        ));
+
+        let groups = history.find_duplicates();
+        assert_eq!(groups.len(), 1);
+        let urls: Vec<&str> = groups[0].iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, ["https://youtu.be/a", "https://youtu.be/c"]);
+        assert_eq!(
+            groups[0][0].sha256.as_deref(),
+            Some("c74862a4004d665b7fac4d282f1a6b0e955216475e78cb5c8437f2b216eb3030")
+        );
+    }
+
+    #[test]
+    fn test_usage_by_day_and_month() {
+        let mut history = History::new();
+        for (date, size) in [
+            ("2024-01-05T10:00:00Z", 100),
+            ("2024-01-05T23:59:59Z", 50),
+            ("2024-01-20T08:00:00Z", 25),
+            ("2024-02-01T00:00:00Z", 1_000),
+            ("2024-04-30T12:00:00Z", 4_000),
+        ] {
+            let mut entry = HistoryEntry::new(
+                format!("https://youtu.be/{}", date),
+                "t".to_string(),
+                PathBuf::from("/tmp/v.mp4"),
+                size,
+                "best".to_string(),
+                "mp4".to_string(),
+            );
+            entry.timestamp = date.parse().unwrap();
+            history.add_entry(entry);
+        }
+
+        assert_eq!(
+            history.usage_by(UsagePeriod::Month),
+            [
+                ("2024-01".to_string(), 175),
+                ("2024-02".to_string(), 1_000),
+                ("2024-04".to_string(), 4_000)
+            ]
+        );
+        assert_eq!(history.usage_by(UsagePeriod::Day)[0], ("2024-01-05".to_string(), 150));
+        assert_eq!(history.usage_by(UsagePeriod::Day).len(), 4);
+
+        let from = "2024-01-20T00:00:00Z".parse().unwrap();
+        let to = "2024-04-30T12:00:00Z".parse().unwrap();
+        assert_eq!(history.bytes_downloaded_between(from, to), 1_025);
+    }
+
+    #[test]
+    fn test_load_history_with_missing_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        std::fs::write(
+            &path,
+            r#"{"entries": [{"url": "https://youtu.be/a", "timestamp": "2024-01-01T00:00:00Z"}]}"#,
+        )
+        .unwrap();
+
+        let history = History::load_from(&path).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.get_entry_by_url("https://youtu.be/a").unwrap().file_size, 0);
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        let canonical = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+        assert_eq!(normalize_url("https://youtu.be/dQw4w9WgXcQ"), canonical);
+        assert_eq!(normalize_url("https://m.youtube.com/watch?v=dQw4w9WgXcQ&t=30"), canonical);
+        assert_eq!(normalize_url(" https://youtube.com/shorts/dQw4w9WgXcQ "), canonical);
+        assert_eq!(normalize_url("https://example.com/video/"), "https://example.com/video");
+    }
+
+    #[test]
+    fn test_restore_missing_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(History::restore_from(&dir.path().join("missing.json")).is_err());
+    }
 }
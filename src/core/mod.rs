@@ -7,6 +7,10 @@ pub mod batch;
 pub mod history;
 pub mod playlist;
 
-pub use batch::{BatchDownloadItem, BatchDownloader, BatchDownloadStats, BatchProgress, DownloadStatus};
+pub use batch::{
+    BatchDownloadItem, BatchDownloader, BatchDownloadStats, BatchFormat,
+    BatchProgress, DownloadStatus, EntryStatus, PauseHandle, batch_state_path, validate_batch_file,
+    BATCH_STATE_FILE,
+};
 pub use history::{History, HistoryEntry};
 pub use playlist::{PlaylistDownloader, PlaylistInfo};
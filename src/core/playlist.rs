@@ -1,10 +1,17 @@
-use crate::infra::downloader::Downloader;
+use crate::cli::Config;
+use crate::infra::downloader::{ytdlp_error, DownloadProgressInfo, Downloader};
+use crate::infra::{export_comments, export_sponsor_segments, item_segments_path};
+use crate::shared::utils::{csv_field, sanitize_filename, within_duration_limits, within_view_limits};
 use crate::shared::{Result, YtdlError};
 use regex::Regex;
-use std::path::PathBuf;
-use tracing::{debug, error, info};
+use directories::ProjectDirs;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PlaylistInfo {
     pub id: String,
     pub title: String,
@@ -13,30 +20,314 @@ pub struct PlaylistInfo {
     pub videos: Vec<PlaylistVideo>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PlaylistVideo {
     pub url: String,
     pub title: String,
     pub duration: Option<u64>,
+    pub view_count: Option<u64>,
     pub index: usize,
+    #[serde(skip)]
     pub selected: bool,
 }
 
+impl PlaylistInfo {
+    /// One row per video: index, title, duration in seconds and URL
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("Index,Title,Duration,URL\n");
+        for video in &self.videos {
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                video.index,
+                csv_field(&video.title),
+                video.duration.map(|d| d.to_string()).unwrap_or_default(),
+                csv_field(&video.url)
+            ));
+        }
+        csv
+    }
+
+    /// Write the video list as JSON when `path` ends in `.json`, otherwise as CSV
+    pub fn export(&self, path: &Path) -> Result<()> {
+        let is_json = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("json"))
+            .unwrap_or(false);
+
+        let content = if is_json {
+            serde_json::to_string_pretty(self)?
+        } else {
+            self.to_csv()
+        };
+
+        std::fs::write(path, content)?;
+        info!("Exported {} playlist videos to {:?}", self.videos.len(), path);
+        Ok(())
+    }
+}
+
+/// Progress of the video a playlist download is on
+#[derive(Debug, Clone)]
+pub struct PlaylistProgress {
+    /// 1-based position among the selected videos
+    pub position: usize,
+    pub total: usize,
+    pub title: String,
+    pub download: DownloadProgressInfo,
+}
+
+/// Outcome of a playlist download, split into finished files and failed videos
+#[derive(Debug, Default)]
+pub struct PlaylistResult {
+    pub playlist_title: String,
+    pub succeeded: Vec<PathBuf>,
+    /// Video titles, parallel to `succeeded`
+    pub succeeded_titles: Vec<String>,
+    pub failed: Vec<(PlaylistVideo, String)>,
+    /// Videos skipped because the download archive already lists them
+    pub skipped: usize,
+    /// Videos outside `--min-duration`/`--max-duration`, or skipped by yt-dlp's filters
+    pub filtered: usize,
+    /// Videos an earlier run of this playlist already finished
+    pub resumed: usize,
+    /// The download stopped at a failure (`--stop-on-error`)
+    pub aborted: bool,
+}
+
+impl PlaylistResult {
+    pub fn record(&mut self, video: &PlaylistVideo, result: Result<PathBuf>) {
+        match result {
+            Ok(path) => {
+                self.succeeded.push(path);
+                self.succeeded_titles.push(video.title.clone());
+            }
+            Err(YtdlError::Skipped(_)) => self.filtered += 1,
+            Err(e) => self.failed.push((video.clone(), e.to_string())),
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+
+    /// Write failed video URLs one per line, in the plain-text batch file format
+    pub fn write_failed_urls(&self, path: &Path) -> Result<()> {
+        let mut content = String::new();
+        for (video, _) in &self.failed {
+            content.push_str(&video.url);
+            content.push('\n');
+        }
+
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
 pub struct PlaylistDownloader {
     downloader: Downloader,
+    output_dir: PathBuf,
     output_folder: Option<PathBuf>,
+    extra_args: Vec<String>,
+    index_prefix: bool,
+    download_archive: Option<PathBuf>,
+    stop_on_error: bool,
+    min_duration: Option<u64>,
+    max_duration: Option<u64>,
+    min_views: Option<u64>,
+    max_views: Option<u64>,
+    progress_dir: Option<PathBuf>,
+    force: bool,
+    write_comments: bool,
+    sponsorblock_export: Option<PathBuf>,
+}
+
+/// Where finished playlist indices are recorded between runs
+pub fn default_progress_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "ytdl").map(|dirs| dirs.data_local_dir().join("playlists"))
+}
+
+/// Filename prefix for a playlist entry, zero-padded to at least two digits
+/// like yt-dlp's `%(playlist_index)02d`
+pub fn index_prefix(index: usize, video_count: usize) -> String {
+    let width = video_count.to_string().len().max(2);
+    format!("{:0width$} - ", index, width = width)
 }
 
 impl PlaylistDownloader {
     pub fn new(output_dir: PathBuf, quality: String) -> Self {
         Self {
-            downloader: Downloader::new(output_dir, quality),
+            downloader: Downloader::new(output_dir.clone(), quality),
+            output_dir,
             output_folder: None,
+            extra_args: Vec::new(),
+            index_prefix: false,
+            download_archive: None,
+            stop_on_error: false,
+            min_duration: None,
+            max_duration: None,
+            min_views: None,
+            max_views: None,
+            progress_dir: None,
+            force: false,
+            write_comments: false,
+            sponsorblock_export: None,
         }
     }
 
+    /// Playlist downloader into `config`'s download directory: every video is
+    /// downloaded like a single URL (`Downloader::from_config`), with the config's
+    /// playlist options on top
+    pub fn from_config(config: &Config) -> Self {
+        let output_dir = config.download_dir();
+        Self {
+            downloader: Downloader::from_config(config),
+            write_comments: config.write_comments.unwrap_or(false),
+            sponsorblock_export: config.sponsorblock_export.clone(),
+            ..Self::new(output_dir, config.quality.clone())
+        }
+        .with_extra_args(config.extra_ytdlp_args.clone())
+        .with_duration_limits(config.min_duration, config.max_duration)
+        .with_view_limits(config.min_views, config.max_views)
+        .with_index_prefix(config.index_prefix.unwrap_or(false))
+        .with_force(config.overwrites == Some(true))
+    }
+
+    /// Download into `dir` instead, keeping any `with_folder` nesting
+    pub fn with_output_dir(mut self, dir: PathBuf) -> Self {
+        self.output_dir = dir;
+        let target = self.target_dir();
+        self.downloader = self.downloader.with_output_dir(target);
+        self
+    }
+
+    /// Nest downloads under `output_dir/<folder>`, created on the first download
     pub fn with_folder(mut self, folder: String) -> Self {
         self.output_folder = Some(PathBuf::from(folder));
+        let dir = self.target_dir();
+        self.downloader = self.downloader.with_output_dir(dir);
+        self
+    }
+
+    /// Where videos and playlist metafiles are written: the output directory,
+    /// or the folder inside it
+    fn target_dir(&self) -> PathBuf {
+        match self.output_folder {
+            Some(ref folder) => self.output_dir.join(folder),
+            None => self.output_dir.clone(),
+        }
+    }
+
+    /// Skip videos whose listed duration is outside `min`..=`max` seconds before
+    /// downloading them. `Downloader::from_config` passes the same limits to yt-dlp's
+    /// match filter, which rejects videos the listing has no duration for.
+    pub fn with_duration_limits(mut self, min: Option<u64>, max: Option<u64>) -> Self {
+        self.min_duration = min;
+        self.max_duration = max;
+        self
+    }
+
+    /// Skip videos whose listed view count is outside `min`..=`max` before
+    /// downloading them; yt-dlp's match filter covers videos listed without one
+    pub fn with_view_limits(mut self, min: Option<u64>, max: Option<u64>) -> Self {
+        self.min_views = min;
+        self.max_views = max;
+        self
+    }
+
+    /// Raw yt-dlp arguments for both the playlist listing and every video download
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.downloader = self.downloader.with_extra_args(extra_args.clone());
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Prefix each video's filename with its playlist index. Videos are downloaded
+    /// by their own URL, where yt-dlp has no `playlist_index`, so the index comes
+    /// from the playlist listing instead.
+    pub fn with_index_prefix(mut self, enabled: bool) -> Self {
+        self.index_prefix = enabled;
+        self
+    }
+
+    /// Record downloads in a yt-dlp download archive and skip videos already in it,
+    /// so reruns only fetch what's new
+    pub fn with_download_archive(mut self, path: PathBuf) -> Self {
+        self.downloader = self.downloader.with_download_archive(Some(path.clone()));
+        self.download_archive = Some(path);
+        self
+    }
+
+    /// Write a `.info.json` and the thumbnail next to every video
+    pub fn with_video_metafiles(mut self, enabled: bool) -> Self {
+        self.downloader = self
+            .downloader
+            .with_write_info_json(enabled)
+            .with_write_thumbnail(enabled);
+        self
+    }
+
+    /// Stop at the first failed video (yt-dlp's `--abort-on-error`) instead of
+    /// recording the failure and moving on (`--ignore-errors`)
+    pub fn with_stop_on_error(mut self, enabled: bool) -> Self {
+        self.stop_on_error = enabled;
+        self
+    }
+
+    /// Record finished playlist indices in `dir`, one file per playlist id, so a
+    /// re-run skips them
+    pub fn with_progress_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.progress_dir = dir;
+        self
+    }
+
+    /// Download every selected video again, ignoring the recorded progress
+    pub fn with_force(mut self, enabled: bool) -> Self {
+        self.force = enabled;
+        self
+    }
+
+    fn progress_path(&self, playlist_id: &str) -> Option<PathBuf> {
+        if playlist_id.is_empty() {
+            return None;
+        }
+        let dir = self.progress_dir.as_ref()?;
+        Some(dir.join(format!("{}.json", sanitize_filename(playlist_id, true))))
+    }
+
+    /// Indices of `playlist_id` finished by earlier runs. Missing or unreadable
+    /// progress counts as nothing finished.
+    pub fn load_progress(&self, playlist_id: &str) -> HashSet<usize> {
+        let Some(path) = self.progress_path(playlist_id) else {
+            return HashSet::new();
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return HashSet::new();
+        };
+
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Ignoring unreadable playlist progress {:?}: {}", path, e);
+            HashSet::new()
+        })
+    }
+
+    pub fn save_progress(&self, playlist_id: &str, completed: &HashSet<usize>) -> Result<()> {
+        let Some(path) = self.progress_path(playlist_id) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut indices: Vec<usize> = completed.iter().copied().collect();
+        indices.sort_unstable();
+        std::fs::write(&path, serde_json::to_string(&indices)?)?;
+        Ok(())
+    }
+
+    /// Run yt-dlp through `runner` instead of spawning it directly
+    #[cfg(test)]
+    pub fn with_runner(mut self, runner: std::sync::Arc<dyn crate::infra::command::CommandRunner>) -> Self {
+        self.downloader = self.downloader.with_runner(runner);
         self
     }
 
@@ -57,27 +348,25 @@ impl PlaylistDownloader {
     pub async fn fetch_playlist_info(&self, url: &str) -> Result<PlaylistInfo> {
         info!("Fetching playlist information: {}", url);
 
-        if !Self::is_playlist_url(url) {
+        if !Self::is_playlist_url(url) && channel_uploads_url(url).is_none() {
             return Err(YtdlError::Other(
                 "URL is not a valid playlist URL".to_string(),
             ));
         }
 
-        let output = tokio::process::Command::new("yt-dlp")
-            .arg("--flat-playlist")
-            .arg("--dump-json")
-            .arg(url)
-            .output()
-            .await
-            .map_err(|e| {
-                error!("Failed to execute yt-dlp: {}", e);
-                YtdlError::Io(e)
-            })?;
+        let mut args = vec!["--flat-playlist".to_string(), "--dump-json".to_string()];
+        args.extend(self.listing_args());
+        args.push(url.to_string());
+
+        let output = self.downloader.runner().output("yt-dlp", &args).await.map_err(|e| {
+            error!("Failed to execute yt-dlp: {}", e);
+            YtdlError::Io(e)
+        })?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             error!("yt-dlp failed: {}", stderr);
-            return Err(YtdlError::YtdlpFailed(stderr.to_string()));
+            return Err(ytdlp_error(&stderr));
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -95,9 +384,11 @@ impl PlaylistDownloader {
                     if let Some(id) = json.get("playlist_id").and_then(|v| v.as_str()) {
                         playlist_id = id.to_string();
                     }
-                    if let Some(up) = json.get("uploader").and_then(|v| v.as_str()) {
-                        uploader = Some(up.to_string());
-                    }
+                    // Flat channel listings only name the uploader at the playlist level
+                    uploader = ["uploader", "playlist_uploader", "channel"]
+                        .iter()
+                        .find_map(|key| json.get(*key).and_then(|v| v.as_str()))
+                        .map(|up| up.to_string());
                 }
 
                 let video_url = json
@@ -121,11 +412,13 @@ impl PlaylistDownloader {
                     .to_string();
 
                 let duration = json.get("duration").and_then(|v| v.as_u64());
+                let view_count = json.get("view_count").and_then(|v| v.as_u64());
 
                 videos.push(PlaylistVideo {
                     url: final_url,
                     title,
                     duration,
+                    view_count,
                     index: index + 1,
                     selected: true,
                 });
@@ -151,11 +444,17 @@ impl PlaylistDownloader {
         })
     }
 
-    pub async fn download_playlist(
+    /// Download the selected videos in order, reporting each video's progress
+    /// to `on_progress`
+    pub async fn download_playlist<F>(
         &self,
         playlist_info: &PlaylistInfo,
         audio_only: bool,
-    ) -> Result<Vec<PathBuf>> {
+        on_progress: F,
+    ) -> Result<PlaylistResult>
+    where
+        F: Fn(PlaylistProgress) + Send + Sync + 'static,
+    {
         let selected_videos: Vec<&PlaylistVideo> = playlist_info
             .videos
             .iter()
@@ -172,44 +471,254 @@ impl PlaylistDownloader {
             playlist_info.title
         );
 
-        let mut output_paths = Vec::new();
+        let mut result = PlaylistResult {
+            playlist_title: playlist_info.title.clone(),
+            ..Default::default()
+        };
+        let archived = self
+            .download_archive
+            .as_deref()
+            .map(read_archive_ids)
+            .unwrap_or_default();
+
+        let mut completed = if self.force {
+            HashSet::new()
+        } else {
+            self.load_progress(&playlist_info.id)
+        };
+
+        let on_progress = Arc::new(on_progress);
+        let total = selected_videos.len();
+        for (position, video) in (1..).zip(selected_videos) {
+            if completed.contains(&video.index) {
+                debug!("Finished by an earlier run, skipping {}: {}", video.index, video.title);
+                result.resumed += 1;
+                continue;
+            }
+
+            if youtube_video_id(&video.url).is_some_and(|id| archived.contains(id)) {
+                debug!("Already archived, skipping {}: {}", video.index, video.title);
+                result.skipped += 1;
+                continue;
+            }
+
+            if !within_duration_limits(video.duration, self.min_duration, self.max_duration) {
+                debug!("Outside duration limits, skipping {}: {}", video.index, video.title);
+                result.filtered += 1;
+                continue;
+            }
 
-        let selected_count = selected_videos.len();
+            if !within_view_limits(video.view_count, self.min_views, self.max_views) {
+                debug!("Outside view limits, skipping {}: {}", video.index, video.title);
+                result.filtered += 1;
+                continue;
+            }
 
-        for video in selected_videos {
             debug!("Downloading video {}: {}", video.index, video.title);
 
-            match self.downloader.download(&video.url, audio_only).await {
+            let prefixed;
+            let downloader = if self.index_prefix {
+                prefixed = self.downloader.clone().with_filename_prefix(Some(index_prefix(
+                    video.index,
+                    playlist_info.video_count,
+                )));
+                &prefixed
+            } else {
+                &self.downloader
+            };
+
+            let report = {
+                let on_progress = Arc::clone(&on_progress);
+                let title = video.title.clone();
+                move |download: DownloadProgressInfo| {
+                    on_progress(PlaylistProgress {
+                        position,
+                        total,
+                        title: title.clone(),
+                        download,
+                    })
+                }
+            };
+            report(DownloadProgressInfo {
+                percentage: 0.0,
+                downloaded_bytes: 0,
+                total_bytes: 0,
+                speed: 0.0,
+                eta: None,
+            });
+
+            let outcome = downloader.download_with_progress(&video.url, audio_only, report).await;
+            match &outcome {
                 Ok(path) => {
                     info!("Downloaded: {:?}", path);
-                    output_paths.push(path);
+                    if self.write_comments {
+                        if let Err(e) = export_comments(path, self.sponsorblock_export.is_some()).await {
+                            warn!("Failed to export comments for {}: {}", video.title, e);
+                        }
+                    }
+                    if let Some(ref export_path) = self.sponsorblock_export {
+                        let dest = item_segments_path(export_path, path);
+                        if let Err(e) = export_sponsor_segments(path, &dest).await {
+                            warn!("Failed to export sponsor segments for {}: {}", video.title, e);
+                        }
+                    }
                 }
-                Err(e) => {
-                    error!("Failed to download {}: {}", video.title, e);
+                Err(YtdlError::Skipped(reason)) => info!("Skipped {}: {}", video.title, reason),
+                Err(e) => error!("Failed to download {}: {}", video.title, e),
+            }
+            let failed = outcome.as_ref().is_err_and(|e| !matches!(e, YtdlError::Skipped(_)));
+            if outcome.is_ok() {
+                completed.insert(video.index);
+                if let Err(e) = self.save_progress(&playlist_info.id, &completed) {
+                    warn!("Failed to save playlist progress: {}", e);
                 }
             }
+            result.record(video, outcome);
+
+            if failed && self.stop_on_error {
+                info!("Stopping playlist download after failure");
+                result.aborted = true;
+                break;
+            }
         }
 
-        info!("Playlist download complete: {} of {} successful", output_paths.len(), selected_count);
+        info!(
+            "Playlist download complete: {} of {} successful",
+            result.succeeded.len(),
+            result.total()
+        );
+
+        Ok(result)
+    }
 
-        Ok(output_paths)
+    /// Options for the flat-playlist invocations that never download a video
+    fn listing_args(&self) -> Vec<String> {
+        let mut args = self.downloader.cookie_args();
+        args.extend(self.downloader.request_sleep_args());
+        args.extend(self.extra_args.iter().cloned());
+        args
     }
 
-    pub async fn download_range(
+    /// Save the playlist's own description and thumbnail to the output directory
+    pub async fn write_playlist_metafiles(&self, url: &str) -> Result<()> {
+        info!("Writing playlist metafiles: {}", url);
+
+        let mut args = vec![
+            "--flat-playlist".to_string(),
+            "--skip-download".to_string(),
+            "--write-playlist-metafiles".to_string(),
+            "--write-description".to_string(),
+            "--write-thumbnail".to_string(),
+            "-o".to_string(),
+            format!("{}/%(title)s.%(ext)s", self.target_dir().display()),
+        ];
+        args.extend(self.listing_args());
+        args.push(url.to_string());
+
+        let output = self.downloader.runner().output("yt-dlp", &args).await.map_err(|e| {
+            error!("Failed to execute yt-dlp: {}", e);
+            YtdlError::Io(e)
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            error!("yt-dlp failed: {}", stderr);
+            return Err(ytdlp_error(&stderr));
+        }
+
+        Ok(())
+    }
+
+    pub async fn download_range<F>(
         &self,
         url: &str,
         start: usize,
         end: usize,
         audio_only: bool,
-    ) -> Result<Vec<PathBuf>> {
+        on_progress: F,
+    ) -> Result<PlaylistResult>
+    where
+        F: Fn(PlaylistProgress) + Send + Sync + 'static,
+    {
         let mut playlist_info = self.fetch_playlist_info(url).await?;
 
         for video in &mut playlist_info.videos {
             video.selected = video.index >= start && video.index <= end;
         }
 
-        self.download_playlist(&playlist_info, audio_only).await
+        self.download_playlist(&playlist_info, audio_only, on_progress).await
+    }
+}
+
+/// Write an extended M3U listing `paths` in order, each with its title from `titles`.
+/// Paths are written relative to the directory of `out`, so the playlist keeps
+/// working when the folder is moved.
+pub fn write_m3u(paths: &[PathBuf], titles: &[String], out: &Path) -> Result<()> {
+    let base = out.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut content = String::from("#EXTM3U\n");
+    for (path, title) in paths.iter().zip(titles) {
+        content.push_str(&format!("#EXTINF:-1,{}\n", title));
+        content.push_str(&relative_path(path, base).to_string_lossy());
+        content.push('\n');
+    }
+
+    std::fs::write(out, content)?;
+    info!("Wrote M3U playlist of {} files to {:?}", paths.len(), out);
+    Ok(())
+}
+
+/// `path` relative to `base`, climbing out of `base` with `..` where needed
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let path_components: Vec<_> = path.components().collect();
+    let base_components: Vec<_> = base.components().collect();
+    let common = path_components
+        .iter()
+        .zip(&base_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..base_components.len() {
+        relative.push("..");
+    }
+    for component in &path_components[common..] {
+        relative.push(component);
     }
+    relative
+}
+
+/// The uploads (`/videos`) tab of a channel URL such as `https://youtube.com/@name`.
+/// An explicit `/videos`, `/shorts` or `/streams` tab is kept; `None` if `url` isn't a channel.
+pub fn channel_uploads_url(url: &str) -> Option<String> {
+    let re = Regex::new(
+        r"^(https?://(?:www\.|m\.)?youtube\.com/(?:@[\w.-]+|channel/[\w-]+|c/[\w.-]+|user/[\w.-]+))(/videos|/shorts|/streams)?/?(?:[/?#].*)?$",
+    )
+    .unwrap();
+
+    let caps = re.captures(url)?;
+    let tab = caps.get(2).map(|m| m.as_str()).unwrap_or("/videos");
+    Some(format!("{}{}", &caps[1], tab))
+}
+
+/// The 11-character video ID in a watch, youtu.be or shorts URL
+pub fn youtube_video_id(url: &str) -> Option<&str> {
+    let re = Regex::new(r"(?:[?&]v=|youtu\.be/|/shorts/)([\w-]{11})").unwrap();
+    re.captures(url).and_then(|caps| caps.get(1)).map(|m| m.as_str())
+}
+
+/// Video IDs listed in a yt-dlp download archive (`youtube <id>` per line).
+/// A missing archive is empty.
+pub fn read_archive_ids(path: &Path) -> HashSet<String> {
+    std::fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| line.split_whitespace().nth(1))
+                .map(|id| id.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 pub fn extract_playlist_id(url: &str) -> Option<String> {
@@ -234,6 +743,49 @@ pub fn extract_playlist_id(url: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::infra::command::MockRunner;
+
+    #[test]
+    fn test_playlist_csv_and_json_export() {
+        let info = PlaylistInfo {
+            id: "PLtest".to_string(),
+            title: "Mix".to_string(),
+            uploader: None,
+            video_count: 2,
+            videos: vec![
+                PlaylistVideo {
+                    url: "https://youtube.com/watch?v=a".to_string(),
+                    title: "Say \"hi\", world".to_string(),
+                    duration: Some(215),
+                    view_count: None,
+                    index: 1,
+                    selected: true,
+                },
+                PlaylistVideo {
+                    url: "https://youtube.com/watch?v=b".to_string(),
+                    title: "Live".to_string(),
+                    duration: None,
+                    view_count: None,
+                    index: 2,
+                    selected: true,
+                },
+            ],
+        };
+
+        assert_eq!(
+            info.to_csv(),
+            "Index,Title,Duration,URL\n\
+             1,\"Say \"\"hi\"\", world\",215,\"https://youtube.com/watch?v=a\"\n\
+             2,\"Live\",,\"https://youtube.com/watch?v=b\"\n"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("mix.json");
+        info.export(&json_path).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(json["videos"][0]["duration"], 215);
+        assert!(json["videos"][0].get("selected").is_none());
+    }
 
     #[test]
     fn test_is_playlist_url() {
@@ -248,6 +800,339 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_playlist_result_mixed_outcomes() {
+        let video = |index: usize| PlaylistVideo {
+            url: format!("https://youtube.com/watch?v=video{}", index),
+            title: format!("Video {}", index),
+            duration: None,
+            view_count: None,
+            index,
+            selected: true,
+        };
+
+        let mut result = PlaylistResult::default();
+        result.record(&video(1), Ok(PathBuf::from("/tmp/one.mp4")));
+        result.record(&video(2), Err(YtdlError::YtdlpFailed("private video".to_string())));
+        result.record(&video(3), Ok(PathBuf::from("/tmp/three.mp4")));
+        result.record(&video(4), Err(YtdlError::Skipped("does not pass filter".to_string())));
+
+        assert_eq!(result.total(), 3);
+        assert_eq!(result.filtered, 1);
+        assert_eq!(result.succeeded.len(), 2);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0.index, 2);
+        assert!(result.failed[0].1.contains("private video"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("failed.txt");
+        result.write_failed_urls(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "https://youtube.com/watch?v=video2\n"
+        );
+    }
+
+    #[test]
+    fn test_write_m3u_uses_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("Mix.m3u");
+        let paths = [
+            dir.path().join("01 - Intro.mp3"),
+            dir.path().join("extras").join("02 - Outro.mp3"),
+            PathBuf::from("/elsewhere/03 - Bonus.mp3"),
+        ];
+        let titles = ["Intro".to_string(), "Outro".to_string(), "Bonus".to_string()];
+
+        write_m3u(&paths, &titles, &out).unwrap();
+        let content = std::fs::read_to_string(&out).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            lines[..6],
+            [
+                "#EXTM3U",
+                "#EXTINF:-1,Intro",
+                "01 - Intro.mp3",
+                "#EXTINF:-1,Outro",
+                "extras/02 - Outro.mp3",
+                "#EXTINF:-1,Bonus",
+            ]
+        );
+        assert!(lines[6].starts_with("../") && lines[6].ends_with("elsewhere/03 - Bonus.mp3"));
+    }
+
+    #[test]
+    fn test_channel_uploads_url() {
+        assert_eq!(
+            channel_uploads_url("https://www.youtube.com/@SomeChannel").as_deref(),
+            Some("https://www.youtube.com/@SomeChannel/videos")
+        );
+        assert_eq!(
+            channel_uploads_url("https://youtube.com/channel/UC123abc/featured").as_deref(),
+            Some("https://youtube.com/channel/UC123abc/videos")
+        );
+        assert_eq!(
+            channel_uploads_url("https://youtube.com/@SomeChannel/streams?view=0").as_deref(),
+            Some("https://youtube.com/@SomeChannel/streams")
+        );
+        assert_eq!(channel_uploads_url("https://youtube.com/watch?v=dQw4w9WgXcQ"), None);
+        assert_eq!(channel_uploads_url("https://youtube.com/playlist?list=PLtest"), None);
+    }
+
+    #[test]
+    fn test_archive_ids() {
+        assert_eq!(youtube_video_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ"), Some("dQw4w9WgXcQ"));
+        assert_eq!(youtube_video_id("https://youtu.be/dQw4w9WgXcQ?t=3"), Some("dQw4w9WgXcQ"));
+        assert_eq!(youtube_video_id("https://youtube.com/shorts/abcdefghijk"), Some("abcdefghijk"));
+        assert_eq!(youtube_video_id("https://example.com"), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(crate::shared::constants::DOWNLOAD_ARCHIVE_FILE);
+        assert!(read_archive_ids(&path).is_empty());
+
+        std::fs::write(&path, "youtube dQw4w9WgXcQ\nyoutube abcdefghijk\n\n").unwrap();
+        let ids = read_archive_ids(&path);
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains("dQw4w9WgXcQ"));
+    }
+
+    async fn download_mixed_playlist(stop_on_error: bool) -> PlaylistResult {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("video.mp4"), b"data").unwrap();
+
+        let videos = (1..=3)
+            .map(|index| PlaylistVideo {
+                url: format!("https://youtube.com/watch?v=video{}", index),
+                title: format!("Video {}", index),
+                duration: None,
+                view_count: None,
+                index,
+                selected: true,
+            })
+            .collect::<Vec<_>>();
+        let info = PlaylistInfo {
+            id: "PLtest".to_string(),
+            title: "Mixed".to_string(),
+            uploader: None,
+            video_count: videos.len(),
+            videos,
+        };
+
+        PlaylistDownloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_stop_on_error(stop_on_error)
+            .with_runner(std::sync::Arc::new(
+                MockRunner::new("", "", 0).failing_for("video2", "ERROR: [youtube] Private video\n", 1),
+            ))
+            .download_playlist(&info, false, |_| {})
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_folder_nests_downloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("Road Trip");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("video.mp4"), b"data").unwrap();
+
+        let info = PlaylistInfo {
+            id: "PLtest".to_string(),
+            title: "Road Trip".to_string(),
+            uploader: None,
+            video_count: 1,
+            videos: vec![PlaylistVideo {
+                url: "https://youtube.com/watch?v=video1".to_string(),
+                title: "Video 1".to_string(),
+                duration: None,
+                view_count: None,
+                index: 1,
+                selected: true,
+            }],
+        };
+
+        let runner = std::sync::Arc::new(MockRunner::new("", "", 0));
+        let result = PlaylistDownloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_folder("Road Trip".to_string())
+            .with_runner(runner.clone())
+            .download_playlist(&info, false, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(result.succeeded, vec![folder.join("video.mp4")]);
+        let template = format!("{}/%(title)s.%(ext)s", folder.display());
+        assert!(runner.calls()[0].windows(2).any(|w| w[0] == "-o" && w[1] == template));
+    }
+
+    #[tokio::test]
+    async fn test_duration_limits_skip_videos_locally() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("video.mp4"), b"data").unwrap();
+
+        let videos = [Some(30), Some(300), Some(4000), None]
+            .into_iter()
+            .enumerate()
+            .map(|(i, duration)| PlaylistVideo {
+                url: format!("https://youtube.com/watch?v=video{}", i + 1),
+                title: format!("Video {}", i + 1),
+                duration,
+                view_count: None,
+                index: i + 1,
+                selected: true,
+            })
+            .collect::<Vec<_>>();
+        let info = PlaylistInfo {
+            id: "PLtest".to_string(),
+            title: "Durations".to_string(),
+            uploader: None,
+            video_count: videos.len(),
+            videos,
+        };
+
+        let runner = std::sync::Arc::new(MockRunner::new("", "", 0));
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let progress_log = Arc::clone(&reported);
+        let result = PlaylistDownloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_duration_limits(Some(60), Some(600))
+            .with_runner(runner.clone())
+            .download_playlist(&info, false, move |progress: PlaylistProgress| {
+                progress_log.lock().unwrap().push((progress.position, progress.total, progress.title));
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.filtered, 2);
+        assert_eq!(result.succeeded.len(), 2);
+        // Only the 300s video and the one without a listed duration reach yt-dlp
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].iter().any(|arg| arg.contains("video2")));
+        assert!(calls[1].iter().any(|arg| arg.contains("video4")));
+        assert_eq!(
+            *reported.lock().unwrap(),
+            [(2, 4, "Video 2".to_string()), (4, 4, "Video 4".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_view_limits_skip_videos_locally() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("video.mp4"), b"data").unwrap();
+
+        let videos = [Some(10), Some(5_000), Some(2_000_000), None]
+            .into_iter()
+            .enumerate()
+            .map(|(i, view_count)| PlaylistVideo {
+                url: format!("https://youtube.com/watch?v=video{}", i + 1),
+                title: format!("Video {}", i + 1),
+                duration: None,
+                view_count,
+                index: i + 1,
+                selected: true,
+            })
+            .collect::<Vec<_>>();
+        let info = PlaylistInfo {
+            id: "PLtest".to_string(),
+            title: "Views".to_string(),
+            uploader: None,
+            video_count: videos.len(),
+            videos,
+        };
+
+        let runner = std::sync::Arc::new(MockRunner::new("", "", 0));
+        let result = PlaylistDownloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_view_limits(Some(1_000), Some(1_000_000))
+            .with_runner(runner.clone())
+            .download_playlist(&info, false, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(result.filtered, 2);
+        // Only the 5,000 view video and the one without a listed count reach yt-dlp
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].iter().any(|arg| arg.contains("video2")));
+        assert!(calls[1].iter().any(|arg| arg.contains("video4")));
+    }
+
+    #[tokio::test]
+    async fn test_rerun_skips_completed_indices() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("video.mp4"), b"data").unwrap();
+        let progress_dir = dir.path().join("progress");
+
+        let videos = (1..=3)
+            .map(|index| PlaylistVideo {
+                url: format!("https://youtube.com/watch?v=video{}", index),
+                title: format!("Video {}", index),
+                duration: None,
+                view_count: None,
+                index,
+                selected: true,
+            })
+            .collect::<Vec<_>>();
+        let info = PlaylistInfo {
+            id: "PLtest".to_string(),
+            title: "Resumable".to_string(),
+            uploader: None,
+            video_count: videos.len(),
+            videos,
+        };
+
+        let downloader = |runner: Arc<MockRunner>| {
+            PlaylistDownloader::new(dir.path().to_path_buf(), "best".to_string())
+                .with_progress_dir(Some(progress_dir.clone()))
+                .with_runner(runner)
+        };
+
+        let first = downloader(Arc::new(MockRunner::new("", "", 0)));
+        first.save_progress("PLtest", &HashSet::from([1, 3])).unwrap();
+
+        let runner = Arc::new(MockRunner::new("", "", 0));
+        let result = downloader(runner.clone())
+            .download_playlist(&info, false, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(result.resumed, 2);
+        assert_eq!(runner.calls().len(), 1);
+        assert!(runner.calls()[0].iter().any(|arg| arg.contains("video2")));
+        assert_eq!(first.load_progress("PLtest"), HashSet::from([1, 2, 3]));
+        assert!(first.load_progress("PLother").is_empty());
+
+        let runner = Arc::new(MockRunner::new("", "", 0));
+        let result = downloader(runner.clone())
+            .with_force(true)
+            .download_playlist(&info, false, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(result.resumed, 0);
+        assert_eq!(runner.calls().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_ignore_errors_continues_past_failures() {
+        let result = download_mixed_playlist(false).await;
+        assert_eq!(result.succeeded.len(), 2);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0.index, 2);
+        assert!(!result.aborted);
+    }
+
+    #[tokio::test]
+    async fn test_stop_on_error_aborts_playlist() {
+        let result = download_mixed_playlist(true).await;
+        assert_eq!(result.succeeded.len(), 1);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.total(), 2);
+        assert!(result.aborted);
+    }
+
+    #[test]
+    fn test_index_prefix() {
+        assert_eq!(index_prefix(1, 9), "01 - ");
+        assert_eq!(index_prefix(7, 42), "07 - ");
+        assert_eq!(index_prefix(12, 250), "012 - ");
+    }
+
     #[test]
     fn test_extract_playlist_id() {
         let id = extract_playlist_id("https://youtube.com/playlist?list=PLtest123");
@@ -259,4 +1144,229 @@ mod tests {
         let id = extract_playlist_id("https://youtube.com/watch?v=abc");
         assert_eq!(id, None);
     }
+
+    /// yt-dlp arguments of a one-video playlist download into `dir`, built the way
+    /// the playlist and archive commands build it
+    async fn config_download_args(config: &Config, dir: &Path, audio_only: bool) -> Vec<String> {
+        std::fs::write(dir.join("video.mp4"), b"data").unwrap();
+        let info = PlaylistInfo {
+            id: String::new(),
+            title: "Options".to_string(),
+            uploader: None,
+            video_count: 1,
+            videos: vec![PlaylistVideo {
+                url: "https://youtube.com/watch?v=video1".to_string(),
+                title: "Video 1".to_string(),
+                duration: None,
+                view_count: None,
+                index: 1,
+                selected: true,
+            }],
+        };
+
+        let runner = std::sync::Arc::new(MockRunner::new("", "", 0));
+        PlaylistDownloader::from_config(config)
+            .with_output_dir(dir.to_path_buf())
+            .with_runner(runner.clone())
+            .download_playlist(&info, audio_only, |_| {})
+            .await
+            .unwrap();
+        runner.calls().remove(0)
+    }
+
+    #[tokio::test]
+    async fn test_from_config_forwards_download_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            prefer_free_formats: Some(true),
+            write_description: Some(true),
+            no_part: Some(true),
+            audio_format: Some("opus".to_string()),
+            audio_quality: Some("192K".to_string()),
+            embed_chapters: Some(true),
+            parse_metadata: Some("title:%(artist)s - %(title)s".to_string()),
+            recode_video: Some("mp4".to_string()),
+            id_filename: Some(true),
+            ..Config::default()
+        };
+
+        let args = config_download_args(&config, dir.path(), false).await;
+        assert!(args.contains(&"--prefer-free-formats".to_string()));
+        assert!(args.contains(&"--write-description".to_string()));
+        assert!(args.contains(&"--no-part".to_string()));
+        assert!(args.contains(&"--embed-chapters".to_string()));
+        assert!(args.windows(2).any(|w| w[0] == "--parse-metadata" && w[1] == "title:%(artist)s - %(title)s"));
+        assert!(args.windows(2).any(|w| w[0] == "--recode-video" && w[1] == "mp4"));
+        assert!(args.windows(2).any(|w| w[0] == "-o" && w[1].ends_with("%(id)s.%(ext)s")));
+
+        let audio_args = config_download_args(&config, dir.path(), true).await;
+        assert!(audio_args.windows(2).any(|w| w[0] == "--audio-format" && w[1] == "opus"));
+        assert!(audio_args.windows(2).any(|w| w[0] == "--audio-quality" && w[1] == "192K"));
+
+        let copy = Config {
+            audio_copy: Some(true),
+            ..config.clone()
+        };
+        let copy_args = config_download_args(&copy, dir.path(), true).await;
+        assert!(copy_args.windows(2).any(|w| w[0] == "--audio-format" && w[1] == "best"));
+    }
+
+    /// Accept webhook POSTs on a local port, collecting their bodies
+    async fn webhook_listener() -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/hook", listener.local_addr().unwrap());
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received = Arc::clone(&bodies);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let received = Arc::clone(&received);
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    loop {
+                        // Answer every complete request; connections are kept alive
+                        while let Some(header_end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                            let headers = String::from_utf8_lossy(&buf[..header_end]).to_lowercase();
+                            let length = headers
+                                .lines()
+                                .find_map(|line| line.strip_prefix("content-length:"))
+                                .and_then(|value| value.trim().parse::<usize>().ok())
+                                .unwrap_or(0);
+                            let end = header_end + 4 + length;
+                            if buf.len() < end {
+                                break;
+                            }
+                            received
+                                .lock()
+                                .unwrap()
+                                .push(String::from_utf8_lossy(&buf[header_end + 4..end]).into_owned());
+                            buf.drain(..end);
+                            if socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.is_err() {
+                                return;
+                            }
+                        }
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+                });
+            }
+        });
+        (endpoint, bodies)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_notified_for_playlist_items() {
+        let (endpoint, bodies) = webhook_listener().await;
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            webhook_url: Some(endpoint),
+            ..Config::default()
+        };
+
+        config_download_args(&config, dir.path(), false).await;
+        let bodies = bodies.lock().unwrap();
+        assert!(bodies
+            .iter()
+            .any(|body| body.contains("\"status\":\"completed\"") && body.contains("video1")));
+    }
+
+    #[test]
+    fn test_listing_args_include_cookies() {
+        let downloader = PlaylistDownloader::from_config(&Config {
+            cookies_file: Some(PathBuf::from("cookies.txt")),
+            cookies_from_browser: Some("firefox".to_string()),
+            sleep_interval: Some(5.0),
+            sleep_requests: Some(1.5),
+            extra_ytdlp_args: vec!["--proxy".to_string(), "socks5://localhost".to_string()],
+            ..Config::default()
+        });
+
+        assert_eq!(
+            downloader.listing_args(),
+            vec![
+                "--cookies",
+                "cookies.txt",
+                "--cookies-from-browser",
+                "firefox",
+                "--sleep-requests",
+                "1.5",
+                "--proxy",
+                "socks5://localhost"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_playlist_info_parses_listing() {
+        let stdout = concat!(
+            r#"{"id": "aaa", "title": "First", "url": "https://www.youtube.com/watch?v=aaa", "duration": 61, "view_count": 1200, "playlist_title": "Mix", "playlist_id": "PLmix", "playlist_uploader": "Someone"}"#,
+            "\n",
+            r#"{"id": "bbb", "title": "Second"}"#,
+            "\n",
+            r#"{"title": "No id or url"}"#,
+            "\n",
+        );
+        let runner = Arc::new(MockRunner::new(stdout, "", 0));
+        let downloader = PlaylistDownloader::new(PathBuf::from("/tmp"), "best".to_string())
+            .with_runner(runner.clone());
+
+        let url = "https://youtube.com/playlist?list=PLmix";
+        let info = downloader.fetch_playlist_info(url).await.unwrap();
+        assert_eq!(info.id, "PLmix");
+        assert_eq!(info.title, "Mix");
+        assert_eq!(info.uploader.as_deref(), Some("Someone"));
+        assert_eq!(info.video_count, 2);
+        assert_eq!(info.videos[0].url, "https://www.youtube.com/watch?v=aaa");
+        assert_eq!(info.videos[0].duration, Some(61));
+        assert_eq!(info.videos[0].view_count, Some(1200));
+        assert_eq!(info.videos[1].url, "https://www.youtube.com/watch?v=bbb");
+        assert_eq!(info.videos[1].title, "Second");
+        assert_eq!(info.videos[1].index, 2);
+
+        let call = &runner.calls()[0];
+        assert_eq!(call[..3], ["yt-dlp", "--flat-playlist", "--dump-json"]);
+        assert_eq!(call.last().map(String::as_str), Some(url));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_playlist_info_errors() {
+        let url = "https://youtube.com/playlist?list=PLmix";
+        let downloader = |runner: MockRunner| {
+            PlaylistDownloader::new(PathBuf::from("/tmp"), "best".to_string()).with_runner(Arc::new(runner))
+        };
+
+        let stderr = "ERROR: [youtube:tab] PLmix: Sign in to confirm you're not a bot\n";
+        let result = downloader(MockRunner::new("", stderr, 1)).fetch_playlist_info(url).await;
+        assert!(matches!(result, Err(YtdlError::BotCheck(_))));
+
+        let result = downloader(MockRunner::new("", "", 0)).fetch_playlist_info(url).await;
+        assert!(matches!(result, Err(YtdlError::Other(msg)) if msg.contains("No videos")));
+
+        let runner = Arc::new(MockRunner::new("", "", 0));
+        let result = PlaylistDownloader::new(PathBuf::from("/tmp"), "best".to_string())
+            .with_runner(runner.clone())
+            .fetch_playlist_info("https://youtube.com/watch?v=abc")
+            .await;
+        assert!(result.is_err());
+        assert!(runner.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_write_playlist_metafiles_uses_runner() {
+        let runner = Arc::new(MockRunner::new("", "", 0));
+        PlaylistDownloader::new(PathBuf::from("/tmp/videos"), "best".to_string())
+            .with_folder("Mix".to_string())
+            .with_runner(runner.clone())
+            .write_playlist_metafiles("https://youtube.com/playlist?list=PLmix")
+            .await
+            .unwrap();
+
+        let call = &runner.calls()[0];
+        assert!(call.contains(&"--write-playlist-metafiles".to_string()));
+        assert!(call.windows(2).any(|w| w[0] == "-o" && w[1] == "/tmp/videos/Mix/%(title)s.%(ext)s"));
+    }
 }
@@ -0,0 +1,360 @@
+use crate::shared::constants::{SIMULATE_SIZE_MIB, SIMULATE_STEPS, SIMULATE_STEP_MS};
+use futures::future::BoxFuture;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tokio::process::Command as TokioCommand;
+
+/// Exit status of a finished command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommandStatus {
+    code: Option<i32>,
+}
+
+impl CommandStatus {
+    #[cfg(test)]
+    pub fn from_code(code: i32) -> Self {
+        Self { code: Some(code) }
+    }
+
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+
+    /// Exit code, or `None` when the process was killed by a signal
+    pub fn code(&self) -> Option<i32> {
+        self.code
+    }
+}
+
+impl From<ExitStatus> for CommandStatus {
+    fn from(status: ExitStatus) -> Self {
+        Self { code: status.code() }
+    }
+}
+
+impl fmt::Display for CommandStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "exit status: {}", code),
+            None => write!(f, "terminated by signal"),
+        }
+    }
+}
+
+pub type OutputStream = Pin<Box<dyn AsyncRead + Send>>;
+
+/// A running command with piped stdout and stderr
+pub struct SpawnedCommand {
+    pub stdout: OutputStream,
+    pub stderr: OutputStream,
+    pub wait: BoxFuture<'static, std::io::Result<CommandStatus>>,
+}
+
+/// Output of a command run to completion
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    pub status: CommandStatus,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Runs external programs. `Downloader` goes through this instead of spawning
+/// yt-dlp directly, so tests can replay yt-dlp's output without the binary.
+pub trait CommandRunner: Send + Sync {
+    /// Start `program` with piped output, for streaming progress
+    fn spawn(&self, program: &str, args: &[String]) -> std::io::Result<SpawnedCommand>;
+
+    /// Run `program` to completion and collect its output
+    fn output<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [String],
+    ) -> BoxFuture<'a, std::io::Result<CommandOutput>>;
+}
+
+/// Runs commands as real child processes
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ProcessRunner;
+
+impl CommandRunner for ProcessRunner {
+    fn spawn(&self, program: &str, args: &[String]) -> std::io::Result<SpawnedCommand> {
+        // Killed if the download task is aborted, e.g. by Ctrl+N in the TUI
+        let mut child = TokioCommand::new(program)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdout = child.stdout.take().expect("Failed to capture stdout");
+        let stderr = child.stderr.take().expect("Failed to capture stderr");
+
+        Ok(SpawnedCommand {
+            stdout: Box::pin(stdout),
+            stderr: Box::pin(stderr),
+            wait: Box::pin(async move { child.wait().await.map(CommandStatus::from) }),
+        })
+    }
+
+    fn output<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [String],
+    ) -> BoxFuture<'a, std::io::Result<CommandOutput>> {
+        Box::pin(async move {
+            let output = TokioCommand::new(program).args(args).kill_on_drop(true).output().await?;
+            Ok(CommandOutput {
+                status: output.status.into(),
+                stdout: output.stdout,
+                stderr: output.stderr,
+            })
+        })
+    }
+}
+
+/// `--simulate-slow` test mode: stands in for yt-dlp without touching the network.
+/// Downloads stream fake progress for a few seconds and then write a small
+/// placeholder file; metadata and playlist queries answer with made-up videos.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SimulatedRunner;
+
+impl CommandRunner for SimulatedRunner {
+    fn spawn(&self, _program: &str, args: &[String]) -> std::io::Result<SpawnedCommand> {
+        let target = simulated_file(args);
+        let (mut writer, stdout) = tokio::io::duplex(1024);
+
+        let task = tokio::spawn(async move {
+            for step in 0..=SIMULATE_STEPS {
+                let percent = step as f64 * 100.0 / SIMULATE_STEPS as f64;
+                let eta = (SIMULATE_STEPS - step) * SIMULATE_STEP_MS / 1000;
+                let speed = SIMULATE_SIZE_MIB as f64 * 1000.0 / (SIMULATE_STEPS * SIMULATE_STEP_MS) as f64;
+                let line = format!(
+                    "[download] {:5.1}% of {}.00MiB at {:.2}MiB/s ETA {:02}:{:02}\n",
+                    percent,
+                    SIMULATE_SIZE_MIB,
+                    speed,
+                    eta / 60,
+                    eta % 60
+                );
+                writer.write_all(line.as_bytes()).await?;
+                if step < SIMULATE_STEPS {
+                    tokio::time::sleep(Duration::from_millis(SIMULATE_STEP_MS)).await;
+                }
+            }
+            if let Some(path) = target {
+                tokio::fs::write(&path, b"simulated download\n").await?;
+            }
+            Ok::<_, std::io::Error>(())
+        });
+
+        Ok(SpawnedCommand {
+            stdout: Box::pin(stdout),
+            stderr: Box::pin(tokio::io::empty()),
+            wait: Box::pin(async move {
+                task.await.map_err(std::io::Error::other)??;
+                Ok(CommandStatus { code: Some(0) })
+            }),
+        })
+    }
+
+    fn output<'a>(
+        &'a self,
+        _program: &'a str,
+        args: &'a [String],
+    ) -> BoxFuture<'a, std::io::Result<CommandOutput>> {
+        let url = args.last().map(String::as_str).unwrap_or_default();
+        let has = |flag: &str| args.iter().any(|arg| arg == flag);
+        let stdout = if has("--flat-playlist") && has("--dump-json") {
+            (1..=3)
+                .map(|n| simulated_video(&format!("{}-{}", simulated_id(url), n)) + "\n")
+                .collect()
+        } else if has("--dump-json") {
+            simulated_video(&simulated_id(url)) + "\n"
+        } else {
+            "simulated\n".to_string()
+        };
+
+        Box::pin(async move {
+            tokio::time::sleep(Duration::from_millis(SIMULATE_STEP_MS)).await;
+            Ok(CommandOutput {
+                status: CommandStatus { code: Some(0) },
+                stdout: stdout.into_bytes(),
+                stderr: Vec::new(),
+            })
+        })
+    }
+}
+
+/// Id for a simulated video: the URL's `v=` parameter or last path segment
+fn simulated_id(url: &str) -> String {
+    let id = match url.split_once("v=") {
+        Some((_, rest)) => rest.split('&').next().unwrap_or(rest),
+        None => url.trim_end_matches('/').rsplit('/').next().unwrap_or(url),
+    };
+    if id.is_empty() {
+        "simulated".to_string()
+    } else {
+        id.to_string()
+    }
+}
+
+/// `--dump-json` line for a made-up video
+fn simulated_video(id: &str) -> String {
+    serde_json::json!({
+        "id": id,
+        "title": format!("Simulated {}", id),
+        "uploader": "ytdl",
+        "duration": SIMULATE_STEPS * SIMULATE_STEP_MS / 1000,
+        "view_count": 1000,
+        "url": format!("https://www.youtube.com/watch?v={}", id),
+        "playlist_title": "Simulated playlist",
+        "playlist_id": "simulated",
+        "formats": [{
+            "format_id": "18",
+            "ext": "mp4",
+            "resolution": "640x360",
+            "filesize": SIMULATE_SIZE_MIB * 1024 * 1024,
+            "vcodec": "avc1",
+            "acodec": "mp4a",
+        }],
+    })
+    .to_string()
+}
+
+/// Where a simulated download writes its placeholder: the `-P home:` directory or
+/// the `-o` template's directory, named after the video like the real download
+fn simulated_file(args: &[String]) -> Option<PathBuf> {
+    let value = |flag: &str| args.windows(2).find(|w| w[0] == flag).map(|w| w[1].as_str());
+    let dir = args
+        .windows(2)
+        .find_map(|w| (w[0] == "-P").then(|| w[1].strip_prefix("home:")).flatten())
+        .map(PathBuf::from)
+        .or_else(|| value("-o").and_then(|template| Path::new(template).parent()).map(Path::to_path_buf))?;
+    let ext = if args.iter().any(|arg| arg == "-x") { "mp3" } else { "mp4" };
+    let id = simulated_id(args.last()?);
+    Some(dir.join(format!("Simulated {}.{}", id, ext)))
+}
+
+/// Test runner that replays canned output and records every invocation
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct MockRunner {
+    stdout: String,
+    stderr: String,
+    exit_code: i32,
+    /// Calls whose arguments contain the pattern fail with this stderr and exit code
+    failure: Option<(String, String, i32)>,
+    calls: std::sync::Mutex<Vec<Vec<String>>>,
+}
+
+#[cfg(test)]
+impl MockRunner {
+    pub fn new(stdout: &str, stderr: &str, exit_code: i32) -> Self {
+        Self {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_code,
+            failure: None,
+            calls: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fail calls whose arguments contain `pattern`, e.g. one URL of a batch
+    pub fn failing_for(mut self, pattern: &str, stderr: &str, exit_code: i32) -> Self {
+        self.failure = Some((pattern.to_string(), stderr.to_string(), exit_code));
+        self
+    }
+
+    /// Program and arguments of each call, in order
+    pub fn calls(&self) -> Vec<Vec<String>> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Record the call and pick its canned (stdout, stderr, exit code)
+    fn record(&self, program: &str, args: &[String]) -> (String, String, i32) {
+        let mut call = vec![program.to_string()];
+        call.extend(args.iter().cloned());
+        self.calls.lock().unwrap().push(call);
+
+        match self.failure {
+            Some((ref pattern, ref stderr, code)) if args.iter().any(|arg| arg.contains(pattern.as_str())) => {
+                (String::new(), stderr.clone(), code)
+            }
+            _ => (self.stdout.clone(), self.stderr.clone(), self.exit_code),
+        }
+    }
+}
+
+#[cfg(test)]
+impl CommandRunner for MockRunner {
+    fn spawn(&self, program: &str, args: &[String]) -> std::io::Result<SpawnedCommand> {
+        let (stdout, stderr, code) = self.record(program, args);
+        let status = CommandStatus::from_code(code);
+
+        Ok(SpawnedCommand {
+            stdout: Box::pin(std::io::Cursor::new(stdout.into_bytes())),
+            stderr: Box::pin(std::io::Cursor::new(stderr.into_bytes())),
+            wait: Box::pin(async move { Ok(status) }),
+        })
+    }
+
+    fn output<'a>(
+        &'a self,
+        program: &'a str,
+        args: &'a [String],
+    ) -> BoxFuture<'a, std::io::Result<CommandOutput>> {
+        let (stdout, stderr, code) = self.record(program, args);
+        let output = CommandOutput {
+            status: CommandStatus::from_code(code),
+            stdout: stdout.into_bytes(),
+            stderr: stderr.into_bytes(),
+        };
+        Box::pin(async move { Ok(output) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_file_follows_output_args() {
+        let args = |extra: &[&str]| {
+            let mut args: Vec<String> = extra.iter().map(|arg| arg.to_string()).collect();
+            args.push("https://www.youtube.com/watch?v=abc&t=10".to_string());
+            args
+        };
+
+        assert_eq!(
+            simulated_file(&args(&["-o", "/tmp/videos/%(title)s.%(ext)s"])),
+            Some(PathBuf::from("/tmp/videos/Simulated abc.mp4"))
+        );
+        assert_eq!(
+            simulated_file(&args(&["-P", "home:/tmp/videos", "-P", "temp:/tmp/work", "-o", "%(title)s.%(ext)s", "-x"])),
+            Some(PathBuf::from("/tmp/videos/Simulated abc.mp3"))
+        );
+        assert_eq!(simulated_id("https://youtu.be/xyz"), "xyz");
+    }
+
+    #[tokio::test]
+    async fn test_simulated_runner_answers_metadata_queries() {
+        let args = |flags: &[&str]| -> Vec<String> {
+            flags.iter().map(|arg| arg.to_string()).chain(["https://youtu.be/xyz".to_string()]).collect()
+        };
+
+        let output = SimulatedRunner.output("yt-dlp", &args(&["--dump-json"])).await.unwrap();
+        assert!(output.status.success());
+        let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(info["title"], "Simulated xyz");
+
+        let output = SimulatedRunner
+            .output("yt-dlp", &args(&["--flat-playlist", "--dump-json"]))
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 3);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::infra::sponsorblock::info_json_path;
+use crate::shared::{Result, YtdlError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// A comment as reported in yt-dlp's `comments`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub author: String,
+    pub text: String,
+    pub like_count: Option<u64>,
+    pub timestamp: Option<i64>,
+    /// `None` for top-level comments, otherwise the ID of the comment replied to
+    pub parent: Option<String>,
+}
+
+/// Path of the `.comments.json` sidecar written next to a downloaded file
+pub fn comments_path(media: &Path) -> PathBuf {
+    let stem = media
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    media.with_file_name(format!("{}.comments.json", stem))
+}
+
+/// Extract the comments from a yt-dlp info json.
+/// `None` when the video has comments disabled (no `comments` list).
+pub fn parse_comments(info: &serde_json::Value) -> Option<Vec<Comment>> {
+    let comments = info["comments"].as_array()?;
+    Some(
+        comments
+            .iter()
+            .filter_map(|c| {
+                Some(Comment {
+                    id: c["id"].as_str()?.to_string(),
+                    author: c["author"].as_str().unwrap_or("Unknown").to_string(),
+                    text: c["text"].as_str()?.to_string(),
+                    like_count: c["like_count"].as_u64(),
+                    timestamp: c["timestamp"].as_i64(),
+                    parent: c["parent"]
+                        .as_str()
+                        .filter(|p| *p != "root")
+                        .map(|p| p.to_string()),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Read the info json next to `media` and write its comments to a
+/// `.comments.json` sidecar. Returns `None` when comments are disabled.
+/// The info json is removed unless `keep_info_json` (another export still needs it).
+pub async fn export_comments(media: &Path, keep_info_json: bool) -> Result<Option<usize>> {
+    let info_path = info_json_path(media);
+    let content = tokio::fs::read_to_string(&info_path).await.map_err(|e| {
+        YtdlError::Other(format!("Failed to read {:?}: {}", info_path, e))
+    })?;
+
+    let info: serde_json::Value = serde_json::from_str(&content)?;
+    let exported = match parse_comments(&info) {
+        Some(comments) => {
+            let dest = comments_path(media);
+            tokio::fs::write(&dest, serde_json::to_string_pretty(&comments)?).await?;
+            info!("Exported {} comments to {:?}", comments.len(), dest);
+            Some(comments.len())
+        }
+        None => {
+            info!("No comments available for {:?}", media);
+            None
+        }
+    };
+
+    if !keep_info_json {
+        if let Err(e) = tokio::fs::remove_file(&info_path).await {
+            debug!("Failed to remove {:?}: {}", info_path, e);
+        }
+    }
+
+    Ok(exported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_comments() {
+        let info = serde_json::json!({
+            "comment_count": 2,
+            "comments": [
+                {"id": "c1", "author": "@someone", "text": "First!", "like_count": 12, "timestamp": 1700000000, "parent": "root"},
+                {"id": "c1.r1", "author": "@other", "text": "Reply", "parent": "c1"},
+                {"id": "broken"}
+            ]
+        });
+
+        let comments = parse_comments(&info).unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].like_count, Some(12));
+        assert_eq!(comments[0].parent, None);
+        assert_eq!(comments[1].parent.as_deref(), Some("c1"));
+
+        // Comments disabled: yt-dlp reports no list at all
+        assert!(parse_comments(&serde_json::json!({"comment_count": null})).is_none());
+        assert_eq!(
+            comments_path(Path::new("/tmp/My Video.mp4")),
+            PathBuf::from("/tmp/My Video.comments.json")
+        );
+    }
+}
@@ -1,14 +1,20 @@
+use crate::cli::Config;
+use crate::infra::limiter::global_limiter;
+use crate::shared::utils::{emit_term_progress, emit_title_progress, TermProgress};
 use crate::shared::{constants::*, Result, YtdlError};
 use indicatif::{ProgressBar, ProgressStyle};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::process::Stdio;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::infra::command::{CommandRunner, CommandStatus, ProcessRunner, SimulatedRunner, SpawnedCommand};
+use crate::infra::logger::EventLogger;
+use crate::infra::webhook::Webhook;
 use std::sync::Arc;
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command as TokioCommand;
 use tokio::sync::Mutex;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 #[derive(Debug, Clone)]
 pub struct DownloadProgressInfo {
@@ -19,6 +25,24 @@ pub struct DownloadProgressInfo {
     pub eta: Option<u64>,
 }
 
+/// One `--progress-json` line: `{"percent":..,"downloaded":..,"total":..,"speed":..,"eta":..}`
+pub fn progress_json_line(progress: &DownloadProgressInfo) -> String {
+    serde_json::json!({
+        "percent": progress.percentage,
+        "downloaded": progress.downloaded_bytes,
+        "total": progress.total_bytes,
+        "speed": progress.speed,
+        "eta": progress.eta,
+    })
+    .to_string()
+}
+
+fn emit_progress_json(progress: DownloadProgressInfo) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", progress_json_line(&progress));
+    let _ = stdout.flush();
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoMetadata {
     pub id: String,
@@ -26,13 +50,73 @@ pub struct VideoMetadata {
     pub uploader: String,
     pub duration: Option<u64>,
     pub view_count: Option<u64>,
+    /// `None` when comments are disabled or yt-dlp didn't report a count
+    #[serde(default)]
+    pub comment_count: Option<u64>,
     pub upload_date: Option<String>,
     pub description: Option<String>,
     pub thumbnail: Option<String>,
     pub formats: Vec<Format>,
+    /// The full `--dump-json` output, reusable with `--load-info-json`
+    #[serde(skip)]
+    pub raw_json: Option<Arc<str>>,
 }
 
 impl VideoMetadata {
+    /// Tallest video resolution among the available formats
+    pub fn best_available_height(&self) -> Option<u32> {
+        self.formats.iter().filter_map(Format::height).max()
+    }
+
+    /// The height yt-dlp will fall back to when `requested` (e.g. "2160" or "2160p")
+    /// is above anything available; `None` if the request can be met or isn't a height
+    pub fn quality_fallback(&self, requested: &str) -> Option<u32> {
+        let requested: u32 = requested.trim_end_matches('p').parse().ok()?;
+        let best = self.best_available_height()?;
+        (requested > best).then_some(best)
+    }
+
+    /// Fails with `NoFormats` when yt-dlp returned metadata but nothing to download,
+    /// e.g. members-only, geo-blocked or not-yet-started live videos
+    pub fn ensure_downloadable(&self) -> Result<()> {
+        if self.formats.is_empty() {
+            return Err(YtdlError::NoFormats(self.id.clone()));
+        }
+        Ok(())
+    }
+
+    /// Rough size of the download from the formats' reported sizes: the largest
+    /// audio stream, plus the largest video stream within `quality` unless `audio_only`.
+    /// `None` when yt-dlp reported no sizes.
+    pub fn estimated_size(&self, quality: &str, audio_only: bool) -> Option<u64> {
+        let is_video = |f: &&Format| f.vcodec.as_deref().is_some_and(|v| v != "none");
+        let max_height = quality.trim_end_matches('p').parse::<u32>().ok();
+
+        let audio = self
+            .formats
+            .iter()
+            .filter(|f| !is_video(f))
+            .filter_map(|f| f.filesize)
+            .max();
+
+        if audio_only {
+            return audio;
+        }
+
+        let video = self
+            .formats
+            .iter()
+            .filter(is_video)
+            .filter(|f| match (max_height, f.height()) {
+                (Some(max), Some(height)) => height <= max,
+                _ => true,
+            })
+            .filter_map(|f| f.filesize)
+            .max()?;
+
+        Some(video + audio.unwrap_or(0))
+    }
+
     /// Convert to display-friendly format used by TUI
     pub fn to_display_info(&self) -> crate::tui::app::VideoInfo {
         crate::tui::app::VideoInfo {
@@ -70,9 +154,332 @@ pub struct Format {
     pub acodec: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Resolution,
+    Size,
+    Fps,
+}
+
+impl SortKey {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "res" | "resolution" => Some(Self::Resolution),
+            "size" | "filesize" => Some(Self::Size),
+            "fps" => Some(Self::Fps),
+            _ => None,
+        }
+    }
+}
+
+impl Format {
+    /// Vertical resolution parsed from "WIDTHxHEIGHT"
+    pub fn height(&self) -> Option<u32> {
+        self.resolution
+            .as_ref()
+            .and_then(|r| r.split('x').nth(1))
+            .and_then(|h| h.parse::<u32>().ok())
+    }
+}
+
+/// Sort formats descending by `key`; formats missing that field always sort last
+pub fn sort_formats(formats: &mut [Format], key: SortKey) {
+    let value = |f: &Format| -> Option<u64> {
+        match key {
+            SortKey::Resolution => f.height().map(u64::from),
+            SortKey::Size => f.filesize,
+            SortKey::Fps => f.fps.map(u64::from),
+        }
+    };
+
+    formats.sort_by(|a, b| match (value(a), value(b)) {
+        (Some(x), Some(y)) => y.cmp(&x),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
+#[derive(Clone)]
 pub struct Downloader {
     output_dir: PathBuf,
     quality: String,
+    audio_format: String,
+    audio_quality: Option<String>,
+    audio_copy: bool,
+    concurrent_fragments: Option<usize>,
+    match_filter: Option<String>,
+    age_limit: Option<u32>,
+    min_filesize: Option<u64>,
+    max_filesize: Option<u64>,
+    term_progress: bool,
+    title_progress: bool,
+    progress_json: bool,
+    extra_args: Vec<String>,
+    cleanup_partials: bool,
+    sponsorblock_export: bool,
+    write_description: bool,
+    write_comments: bool,
+    embed_chapters: bool,
+    parse_metadata: Option<String>,
+    recode_video: Option<String>,
+    write_info_json: bool,
+    write_thumbnail: bool,
+    download_archive: Option<PathBuf>,
+    download_sections: Option<String>,
+    prefer_free_formats: bool,
+    info_json: Option<PathBuf>,
+    filename_prefix: Option<String>,
+    id_filename: bool,
+    no_part: bool,
+    overwrites: Option<bool>,
+    webhook: Option<Webhook>,
+    events: Option<EventLogger>,
+    cache_dir: Option<PathBuf>,
+    temp_dir: Option<PathBuf>,
+    sleep_interval: Option<f64>,
+    max_sleep_interval: Option<f64>,
+    sleep_requests: Option<f64>,
+    cookies_file: Option<PathBuf>,
+    cookies_from_browser: Option<String>,
+    player_client_fallbacks: Vec<String>,
+    runner: Arc<dyn CommandRunner>,
+}
+
+/// Whether yt-dlp stderr shows YouTube's "Sign in to confirm you're not a bot" check.
+/// The age gate ("Sign in to confirm your age") is deliberately not matched.
+pub fn is_bot_check(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    stderr.contains("not a bot")
+        || (stderr.contains("sign in to confirm") && !stderr.contains("your age"))
+}
+
+/// yt-dlp stderr fragments (lowercased) that point at a broken, usually outdated, extractor
+const EXTRACTOR_ERROR_MARKERS: [&str; 5] = [
+    "unable to extract",
+    "signature extraction failed",
+    "nsig extraction failed",
+    "please report this issue on",
+    "make sure you are using the latest version",
+];
+
+/// Whether yt-dlp stderr shows an HTTP 403, which another player client often avoids
+pub fn is_forbidden(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    stderr.contains("http error 403") || stderr.contains("403: forbidden")
+}
+
+pub fn is_extractor_error(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    EXTRACTOR_ERROR_MARKERS.iter().any(|marker| stderr.contains(marker))
+}
+
+/// yt-dlp arguments forcing YouTube's `player_client`, if one is given
+fn player_client_args(player_client: Option<&str>) -> Vec<String> {
+    match player_client {
+        Some(client) => vec![
+            "--extractor-args".to_string(),
+            format!("youtube:player_client={}", client),
+        ],
+        None => Vec::new(),
+    }
+}
+
+/// Whether `result` is an HTTP 403 worth retrying with `client`; logs the retry
+fn retry_with_player_client(result: &Result<PathBuf>, client: &str) -> bool {
+    if !matches!(result, Err(YtdlError::Forbidden(_))) {
+        return false;
+    }
+    warn!("Download refused with HTTP 403, retrying with player client {}", client);
+    true
+}
+
+/// yt-dlp `[download]` messages for a video it decided not to download: match
+/// filter (which also carries the duration and view limits), size limits, age limit
+/// and the download archive
+const SKIP_MARKERS: [&str; 5] = [
+    "does not pass filter",
+    "larger than max-filesize",
+    "smaller than min-filesize",
+    "because it is age restricted",
+    "has already been recorded in the archive",
+];
+
+/// A video yt-dlp exited successfully for without downloading it
+#[derive(Debug, Clone, PartialEq)]
+enum NotDownloaded {
+    /// Rejected by a filter or limit, with yt-dlp's reason
+    Skipped(String),
+    /// Already on disk and kept because of `--no-overwrites`
+    Existing(PathBuf),
+}
+
+/// Recognize a yt-dlp stdout line saying the video won't be downloaded
+fn not_downloaded(line: &str) -> Option<NotDownloaded> {
+    let message = line.trim().strip_prefix("[download] ")?.trim();
+    if SKIP_MARKERS.iter().any(|marker| message.contains(marker)) {
+        return Some(NotDownloaded::Skipped(message.to_string()));
+    }
+    let path = message
+        .strip_suffix(" has already been downloaded and merged")
+        .or_else(|| message.strip_suffix(" has already been downloaded"))?;
+    Some(NotDownloaded::Existing(PathBuf::from(path)))
+}
+
+/// Classify a failed yt-dlp run from its stderr
+pub fn ytdlp_error(stderr: &str) -> YtdlError {
+    if is_bot_check(stderr) {
+        YtdlError::BotCheck(stderr.trim().to_string())
+    } else if is_forbidden(stderr) {
+        YtdlError::Forbidden(stderr.trim().to_string())
+    } else if is_extractor_error(stderr) {
+        YtdlError::ExtractorOutdated(stderr.trim().to_string())
+    } else {
+        YtdlError::YtdlpFailed(stderr.to_string())
+    }
+}
+
+/// Run `yt-dlp -U` through `runner` and return the last line of its output as a summary
+pub async fn update_ytdlp(runner: &dyn CommandRunner) -> Result<String> {
+    info!("Updating yt-dlp");
+
+    let output = runner
+        .output("yt-dlp", &["-U".to_string()])
+        .await
+        .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to execute yt-dlp: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    debug!("yt-dlp -U output: {}", stdout);
+
+    if !output.status.success() {
+        error!("yt-dlp update failed: {}", stderr);
+        return Err(YtdlError::YtdlpFailed(last_line(&stderr)));
+    }
+
+    Ok(last_line(&stdout))
+}
+
+/// Run `yt-dlp --rm-cache-dir` through `runner` on `cache_dir` (or yt-dlp's default
+/// cache) and return the last line of its output as a summary
+pub async fn clear_ytdlp_cache(runner: &dyn CommandRunner, cache_dir: Option<&Path>) -> Result<String> {
+    info!("Clearing yt-dlp cache: {:?}", cache_dir);
+
+    let mut args = Vec::new();
+    if let Some(dir) = cache_dir {
+        args.push("--cache-dir".to_string());
+        args.push(dir.display().to_string());
+    }
+    args.push("--rm-cache-dir".to_string());
+    let output = runner
+        .output("yt-dlp", &args)
+        .await
+        .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to execute yt-dlp: {}", e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    debug!("yt-dlp --rm-cache-dir output: {}", stdout);
+
+    if !output.status.success() {
+        error!("Clearing the yt-dlp cache failed: {}", stderr);
+        return Err(YtdlError::YtdlpFailed(last_line(&stderr)));
+    }
+
+    Ok(last_line(&stdout))
+}
+
+/// Last non-empty line of yt-dlp output, used as a one-line summary
+fn last_line(text: &str) -> String {
+    text.lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Metadata files yt-dlp writes next to the media file
+const SIDECAR_SUFFIXES: [&str; 7] = [
+    ".info.json",
+    ".comments.json",
+    ".description",
+    ".jpg",
+    ".webp",
+    ".png",
+    DOWNLOAD_ARCHIVE_FILE,
+];
+
+fn is_sidecar_file(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    SIDECAR_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// File stem yt-dlp produces for `%(title)s`, which swaps path-hostile
+/// characters for full-width lookalikes
+fn expected_file_stem(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| match c {
+            '/' => '⧸',
+            '\\' => '⧹',
+            ':' => '：',
+            '*' => '＊',
+            '?' => '？',
+            '"' => '＂',
+            '<' => '＜',
+            '>' => '＞',
+            '|' => '｜',
+            c => c,
+        })
+        .collect()
+}
+
+/// Title recovered from a file written with the `%(title)s.%(ext)s` template,
+/// so history doesn't need a second metadata fetch. With `--id-filename` this is the id.
+pub fn title_from_path(path: &Path) -> Option<String> {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .filter(|stem| !stem.is_empty())
+}
+
+/// Extensions yt-dlp uses for in-progress downloads
+const PARTIAL_EXTENSIONS: [&str; 2] = ["part", "ytdl"];
+
+fn is_partial_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| PARTIAL_EXTENSIONS.contains(&ext))
+        .unwrap_or(false)
+}
+
+/// Partial download files currently in `dir`
+fn partial_files(dir: &Path) -> HashSet<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| is_partial_file(path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Remove partial files in `dir` that aren't in `existing`, returning how many were removed
+fn remove_new_partials(dir: &Path, existing: &HashSet<PathBuf>) -> usize {
+    let mut removed = 0;
+
+    for path in partial_files(dir).difference(existing) {
+        match std::fs::remove_file(path) {
+            Ok(()) => {
+                debug!("Removed partial download: {:?}", path);
+                removed += 1;
+            }
+            Err(e) => warn!("Failed to remove partial download {:?}: {}", path, e),
+        }
+    }
+
+    removed
 }
 
 impl Downloader {
@@ -80,19 +487,620 @@ impl Downloader {
         Self {
             output_dir,
             quality,
+            audio_format: "mp3".to_string(),
+            audio_quality: None,
+            audio_copy: false,
+            concurrent_fragments: None,
+            match_filter: None,
+            min_filesize: None,
+            max_filesize: None,
+            age_limit: None,
+            term_progress: false,
+            title_progress: false,
+            progress_json: false,
+            extra_args: Vec::new(),
+            cleanup_partials: false,
+            sponsorblock_export: false,
+            write_description: false,
+            write_comments: false,
+            embed_chapters: false,
+            parse_metadata: None,
+            recode_video: None,
+            write_info_json: false,
+            write_thumbnail: false,
+            download_archive: None,
+            download_sections: None,
+            prefer_free_formats: false,
+            info_json: None,
+            filename_prefix: None,
+            id_filename: false,
+            no_part: false,
+            overwrites: None,
+            webhook: None,
+            events: None,
+            cache_dir: None,
+            temp_dir: None,
+            sleep_interval: None,
+            max_sleep_interval: None,
+            sleep_requests: None,
+            cookies_file: None,
+            cookies_from_browser: None,
+            player_client_fallbacks: DEFAULT_PLAYER_CLIENT_FALLBACKS.iter().map(|c| c.to_string()).collect(),
+            runner: Arc::new(ProcessRunner),
+        }
+    }
+
+    /// Downloader into `config`'s download directory with every download option from
+    /// the config applied, so single, batch, playlist and TUI downloads behave alike.
+    /// Terminal progress reporting is left to the caller
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.download_dir(), config.quality.clone())
+            .with_audio_format(config.audio_format.clone().unwrap_or_else(|| "mp3".to_string()))
+            .with_match_filter(config.effective_match_filter())
+            .with_filesize_limits(config.min_filesize, config.max_filesize)
+            .with_age_limit(config.age_limit)
+            .with_extra_args(config.extra_ytdlp_args.clone())
+            .with_cleanup_partials(config.cleanup_partials.unwrap_or(false))
+            .with_sponsorblock_export(config.sponsorblock_export.is_some())
+            .with_download_sections(config.download_sections.clone())
+            .with_prefer_free_formats(config.prefer_free_formats.unwrap_or(false))
+            .with_id_filename(config.id_filename.unwrap_or(false))
+            .with_write_description(config.write_description.unwrap_or(false))
+            .with_write_comments(config.write_comments.unwrap_or(false))
+            .with_no_part(config.no_part.unwrap_or(false))
+            .with_overwrites(config.overwrites)
+            .with_audio_quality(config.audio_quality.clone())
+            .with_audio_copy(config.audio_copy.unwrap_or(false))
+            .with_cache_dir(config.cache_dir.clone())
+            .with_temp_dir(config.temp_dir.clone())
+            .with_cookies(config.cookies_file.clone(), config.cookies_from_browser.clone())
+            .with_sleep_intervals(config.sleep_interval, config.max_sleep_interval, config.sleep_requests)
+            .with_embed_chapters(config.embed_chapters.unwrap_or(false))
+            .with_parse_metadata(config.parse_metadata.clone())
+            .with_recode_video(config.recode_video.clone())
+            .with_player_client_fallbacks(config.player_client_fallbacks.clone())
+            .with_webhook(config.webhook_url.clone().map(Webhook::new))
+            .with_events_file(config.events_file.clone())
+            .with_runner(if config.simulate_slow {
+                Arc::new(SimulatedRunner)
+            } else {
+                Arc::new(ProcessRunner)
+            })
+    }
+
+    /// yt-dlp `--audio-quality` for audio-only downloads. 0-10 selects a VBR level
+    /// (0 best, yt-dlp defaults to 5); a bitrate such as "192K" encodes at that constant rate
+    pub fn with_audio_quality(mut self, quality: Option<String>) -> Self {
+        self.audio_quality = quality;
+        self
+    }
+
+    /// Keep the source audio codec (usually opus or m4a) instead of converting to
+    /// the audio format: yt-dlp `--audio-format best` only remuxes, so nothing is
+    /// re-encoded and `--audio-quality` doesn't apply
+    pub fn with_audio_copy(mut self, enabled: bool) -> Self {
+        self.audio_copy = enabled;
+        self
+    }
+
+    /// Number of fragments yt-dlp downloads in parallel (`-N`)
+    pub fn with_concurrent_fragments(mut self, count: usize) -> Self {
+        self.concurrent_fragments = Some(count.max(1));
+        self
+    }
+
+    /// yt-dlp `--match-filter` expression; videos that don't match are skipped by yt-dlp
+    pub fn with_match_filter(mut self, filter: Option<String>) -> Self {
+        self.match_filter = filter;
+        self
+    }
+
+    /// yt-dlp `--min-filesize`/`--max-filesize`, in bytes
+    pub fn with_filesize_limits(mut self, min: Option<u64>, max: Option<u64>) -> Self {
+        self.min_filesize = min;
+        self.max_filesize = max;
+        self
+    }
+
+    /// yt-dlp `--age-limit`
+    pub fn with_age_limit(mut self, age_limit: Option<u32>) -> Self {
+        self.age_limit = age_limit;
+        self
+    }
+
+    /// Report progress to the terminal's taskbar via OSC 9;4 sequences
+    pub fn with_term_progress(mut self, enabled: bool) -> Self {
+        self.term_progress = enabled;
+        self
+    }
+
+    /// Show the download percentage in the terminal window title (OSC 0)
+    pub fn with_title_progress(mut self, enabled: bool) -> Self {
+        self.title_progress = enabled;
+        self
+    }
+
+    /// Print progress as JSON lines on stdout instead of drawing a progress bar
+    pub fn with_progress_json(mut self, enabled: bool) -> Self {
+        self.progress_json = enabled;
+        self
+    }
+
+    /// Raw arguments appended to every yt-dlp invocation (`--ytdlp-args`)
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    /// Delete `.part`/`.ytdl` files left by a failed download (never ones that predate it)
+    pub fn with_cleanup_partials(mut self, enabled: bool) -> Self {
+        self.cleanup_partials = enabled;
+        self
+    }
+
+    /// Mark SponsorBlock segments and write an `.info.json` so they can be exported
+    pub fn with_sponsorblock_export(mut self, enabled: bool) -> Self {
+        self.sponsorblock_export = enabled;
+        self
+    }
+
+    /// Save the video description as `<title>.description` next to the download
+    pub fn with_write_description(mut self, enabled: bool) -> Self {
+        self.write_description = enabled;
+        self
+    }
+
+    /// YouTube player clients to retry with, one at a time, while a download fails
+    /// with HTTP 403. `None` keeps the defaults; an empty list disables the retry.
+    pub fn with_player_client_fallbacks(mut self, clients: Option<Vec<String>>) -> Self {
+        if let Some(clients) = clients {
+            self.player_client_fallbacks = clients.into_iter().filter(|c| !c.trim().is_empty()).collect();
+        }
+        self
+    }
+
+    /// yt-dlp `--cache-dir`: where it caches extracted player code and signatures
+    pub fn with_cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache_dir = dir;
+        self
+    }
+
+    fn cache_args(&self) -> Vec<String> {
+        match self.cache_dir {
+            Some(ref dir) => vec!["--cache-dir".to_string(), dir.display().to_string()],
+            None => Vec::new(),
+        }
+    }
+
+    /// Download into `dir` and move finished files to the output directory
+    /// (yt-dlp `-P temp:`), e.g. a fast local disk in front of a NAS
+    pub fn with_temp_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.temp_dir = dir;
+        self
+    }
+
+    /// Where yt-dlp writes `.part` files while downloading
+    fn partials_dir(&self) -> &Path {
+        self.temp_dir.as_deref().unwrap_or(&self.output_dir)
+    }
+
+    /// `-o` and, with a temp dir, the `-P` paths. yt-dlp ignores `-P` for an
+    /// absolute output template, so the template is then relative to `home:`.
+    fn output_args(&self) -> Vec<String> {
+        match self.temp_dir {
+            Some(ref temp) => vec![
+                "-P".to_string(),
+                format!("home:{}", self.output_dir.display()),
+                "-P".to_string(),
+                format!("temp:{}", temp.display()),
+                "-o".to_string(),
+                self.output_template(),
+            ],
+            None => vec![
+                "-o".to_string(),
+                format!("{}/{}", self.output_dir.display(), self.output_template()),
+            ],
+        }
+    }
+
+    /// yt-dlp `--sleep-interval`/`--max-sleep-interval` (seconds to wait before each
+    /// download, randomized up to the max) and `--sleep-requests` (seconds between
+    /// metadata requests), to avoid being rate-limited on large playlists
+    pub fn with_sleep_intervals(
+        mut self,
+        interval: Option<f64>,
+        max_interval: Option<f64>,
+        requests: Option<f64>,
+    ) -> Self {
+        self.sleep_interval = interval;
+        self.max_sleep_interval = max_interval;
+        self.sleep_requests = requests;
+        self
+    }
+
+    /// `--sleep-requests` for metadata-only invocations
+    pub fn request_sleep_args(&self) -> Vec<String> {
+        match self.sleep_requests {
+            Some(secs) => vec!["--sleep-requests".to_string(), secs.to_string()],
+            None => Vec::new(),
         }
     }
 
-    pub fn check_partial_download(&self, _url: &str) -> Option<PathBuf> {
-        let entries = std::fs::read_dir(&self.output_dir).ok()?;
+    /// Authenticate with a Netscape cookies file or the cookies of a local
+    /// browser profile, for private, members-only or age-restricted videos
+    pub fn with_cookies(mut self, file: Option<PathBuf>, browser: Option<String>) -> Self {
+        self.cookies_file = file;
+        self.cookies_from_browser = browser;
+        self
+    }
+
+    /// `--cookies`/`--cookies-from-browser`, needed by every invocation that
+    /// talks to the site, including metadata-only ones
+    pub fn cookie_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(ref file) = self.cookies_file {
+            args.push("--cookies".to_string());
+            args.push(file.display().to_string());
+        }
+
+        if let Some(ref browser) = self.cookies_from_browser {
+            args.push("--cookies-from-browser".to_string());
+            args.push(browser.clone());
+        }
+
+        args
+    }
+
+    fn sleep_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(secs) = self.sleep_interval {
+            args.push("--sleep-interval".to_string());
+            args.push(secs.to_string());
+
+            // yt-dlp rejects --max-sleep-interval without --sleep-interval
+            if let Some(max) = self.max_sleep_interval {
+                args.push("--max-sleep-interval".to_string());
+                args.push(max.to_string());
+            }
+        }
+
+        args.extend(self.request_sleep_args());
+        args
+    }
+
+    /// Embed chapter markers in the downloaded file (yt-dlp `--embed-chapters`, needs FFmpeg)
+    pub fn with_embed_chapters(mut self, enabled: bool) -> Self {
+        self.embed_chapters = enabled;
+        self
+    }
+
+    /// yt-dlp `--parse-metadata FROM:TO` pattern, e.g. `title:%(artist)s - %(title)s`.
+    /// The parsed fields only reach the file as tags, so metadata is embedded too.
+    pub fn with_parse_metadata(mut self, pattern: Option<String>) -> Self {
+        self.parse_metadata = pattern;
+        self
+    }
+
+    /// Transcode video downloads to this container (yt-dlp `--recode-video`, needs FFmpeg).
+    /// Audio-only downloads are extracted instead and ignore it.
+    pub fn with_recode_video(mut self, format: Option<String>) -> Self {
+        self.recode_video = format;
+        self
+    }
+
+    /// Fetch the video's comments into an `.info.json` so they can be exported
+    pub fn with_write_comments(mut self, enabled: bool) -> Self {
+        self.write_comments = enabled;
+        self
+    }
+
+    /// Only download the given time range (`*START-END`, yt-dlp `--download-sections`)
+    pub fn with_download_sections(mut self, sections: Option<String>) -> Self {
+        self.download_sections = sections;
+        self
+    }
+
+    /// Pass `--prefer-free-formats`; composes with the `-f` quality selector
+    pub fn with_prefer_free_formats(mut self, enabled: bool) -> Self {
+        self.prefer_free_formats = enabled;
+        self
+    }
+
+    /// Pass `--no-part` so yt-dlp writes straight to the final file; such downloads can't be resumed
+    pub fn with_no_part(mut self, enabled: bool) -> Self {
+        self.no_part = enabled;
+        self
+    }
+
+    /// `Some(true)` passes `--force-overwrites` to re-download existing files,
+    /// `Some(false)` passes `--no-overwrites`; `None` keeps yt-dlp's default
+    pub fn with_overwrites(mut self, overwrites: Option<bool>) -> Self {
+        self.overwrites = overwrites;
+        self
+    }
+
+    /// Post start, throttled progress and outcome events to a webhook
+    pub fn with_webhook(mut self, webhook: Option<Webhook>) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Append started/progress/completed/failed events to an NDJSON file
+    pub fn with_events_file(mut self, path: Option<PathBuf>) -> Self {
+        self.events = path.map(EventLogger::new);
+        self
+    }
+
+    /// Write downloads to `dir` instead of the directory given to `new`; created on download
+    pub fn with_output_dir(mut self, dir: PathBuf) -> Self {
+        self.output_dir = dir;
+        self
+    }
+
+    /// The runner every yt-dlp call goes through
+    pub fn runner(&self) -> &dyn CommandRunner {
+        self.runner.as_ref()
+    }
+
+    /// Run yt-dlp through `runner` instead of spawning it directly
+    pub fn with_runner(mut self, runner: Arc<dyn CommandRunner>) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Download from a previously fetched info json (`--load-info-json`) instead of
+    /// re-extracting the URL
+    pub fn with_info_json(mut self, path: Option<PathBuf>) -> Self {
+        self.info_json = path;
+        self
+    }
+
+    /// Literal text put in front of the title in output filenames
+    pub fn with_filename_prefix(mut self, prefix: Option<String>) -> Self {
+        self.filename_prefix = prefix;
+        self
+    }
+
+    /// Name files after the video id instead of the title
+    pub fn with_id_filename(mut self, enabled: bool) -> Self {
+        self.id_filename = enabled;
+        self
+    }
+
+    /// Last arguments of a download: the URL, or the cached info json when set
+    fn source_args(&self, url: &str) -> Vec<String> {
+        match self.info_json {
+            Some(ref path) => vec!["--load-info-json".to_string(), path.display().to_string()],
+            None => vec![url.to_string()],
+        }
+    }
+
+    /// `-o` template; clips get the section range in their name so they don't overwrite the full video
+    fn output_template(&self) -> String {
+        let prefix = self.filename_prefix.as_deref().unwrap_or("");
+        let name = if self.id_filename { "%(id)s" } else { "%(title)s" };
+        if self.download_sections.is_some() {
+            format!("{}{} [%(section_start)s-%(section_end)s].%(ext)s", prefix, name)
+        } else {
+            format!("{}{}.%(ext)s", prefix, name)
+        }
+    }
+
+    /// Stem yt-dlp gives the file for a video, used to find its partial download
+    pub fn file_stem_for<'a>(&self, title: &'a str, id: &'a str) -> &'a str {
+        if self.id_filename {
+            id
+        } else {
+            title
+        }
+    }
+
+    /// Options shared by every download invocation
+    fn common_args(&self) -> Vec<String> {
+        let mut args = self.cache_args();
+        args.extend(self.cookie_args());
+        args.extend(self.sleep_args());
+
+        if let Some(count) = self.concurrent_fragments {
+            args.push("--concurrent-fragments".to_string());
+            args.push(count.to_string());
+        }
+
+        if let Some(ref filter) = self.match_filter {
+            args.push("--match-filter".to_string());
+            args.push(filter.clone());
+        }
+
+        if let Some(age) = self.age_limit {
+            args.push("--age-limit".to_string());
+            args.push(age.to_string());
+        }
+
+        if let Some(min) = self.min_filesize {
+            args.push("--min-filesize".to_string());
+            args.push(min.to_string());
+        }
+
+        if let Some(max) = self.max_filesize {
+            args.push("--max-filesize".to_string());
+            args.push(max.to_string());
+        }
+
+        if self.prefer_free_formats {
+            args.push("--prefer-free-formats".to_string());
+        }
+
+        if self.no_part {
+            args.push("--no-part".to_string());
+        }
+
+        if let Some(ref sections) = self.download_sections {
+            args.push("--download-sections".to_string());
+            args.push(sections.clone());
+        }
+
+        if self.write_description {
+            args.push("--write-description".to_string());
+        }
+
+        if self.write_comments {
+            // yt-dlp only stores comments inside the info json
+            args.push("--write-comments".to_string());
+            if !self.write_info_json {
+                args.push("--write-info-json".to_string());
+            }
+        }
+
+        if self.write_info_json {
+            args.push("--write-info-json".to_string());
+        }
+
+        if self.embed_chapters {
+            args.push("--embed-chapters".to_string());
+        }
+
+        if let Some(ref pattern) = self.parse_metadata {
+            args.push("--parse-metadata".to_string());
+            args.push(pattern.clone());
+            args.push("--embed-metadata".to_string());
+        }
+
+        if self.write_thumbnail {
+            args.push("--write-thumbnail".to_string());
+        }
+
+        if let Some(ref archive) = self.download_archive {
+            args.push("--download-archive".to_string());
+            args.push(archive.display().to_string());
+        }
+
+        if self.sponsorblock_export {
+            args.extend_from_slice(&[
+                "--sponsorblock-mark".to_string(),
+                "all".to_string(),
+                "--write-info-json".to_string(),
+            ]);
+        }
+
+        args
+    }
+
+    /// yt-dlp arguments for a download: output and progress, the shared options
+    /// (`common_args`, including `--download-sections`), then format selection or
+    /// audio extraction, and the user's extra arguments just before the source
+    fn download_args(
+        &self,
+        url: &str,
+        audio_only: bool,
+        continue_download: bool,
+        player_client: Option<&str>,
+    ) -> Vec<String> {
+        let mut args = self.output_args();
+        args.extend(["--progress".to_string(), "--newline".to_string()]);
+        args.extend(self.common_args());
+
+        match self.overwrites {
+            // yt-dlp drops --continue when forcing overwrites, so don't pretend to resume
+            Some(true) => {
+                args.push("--force-overwrites".to_string());
+                if continue_download {
+                    warn!("Forcing overwrites, the partial download is restarted instead of resumed");
+                }
+            }
+            Some(false) => args.push("--no-overwrites".to_string()),
+            None => {}
+        }
+
+        if continue_download && self.overwrites != Some(true) {
+            args.push("--continue".to_string());
+            info!("Resume mode enabled");
+        }
+
+        if audio_only && self.audio_copy {
+            args.extend_from_slice(&[
+                "-f".to_string(),
+                "bestaudio/best".to_string(),
+                "-x".to_string(),
+                "--audio-format".to_string(),
+                "best".to_string(),
+            ]);
+            info!("Audio-only mode: keeping the original audio codec");
+        } else if audio_only {
+            // Fetch only the audio stream, so a --section is cut from it directly
+            args.extend_from_slice(&[
+                "-f".to_string(),
+                "bestaudio/best".to_string(),
+                "-x".to_string(),
+                "--audio-format".to_string(),
+                self.audio_format.clone(),
+            ]);
+            if let Some(ref quality) = self.audio_quality {
+                args.extend_from_slice(&["--audio-quality".to_string(), quality.clone()]);
+            }
+            info!("Audio-only mode: converting to {}", self.audio_format);
+        } else {
+            if self.quality == "best" {
+                args.push("-f".to_string());
+                args.push("bestvideo+bestaudio/best".to_string());
+            } else {
+                args.push("-f".to_string());
+                args.push(format!("bestvideo[height<={}]+bestaudio/best", self.quality));
+            }
+            info!("Video quality: {}", self.quality);
+
+            if let Some(ref format) = self.recode_video {
+                args.extend_from_slice(&["--recode-video".to_string(), format.clone()]);
+                info!("Recoding video to {}", format);
+            }
+        }
+
+        args.extend(player_client_args(player_client));
+        args.extend(self.extra_args.iter().cloned());
+        args.extend(self.source_args(url));
+
+        args
+    }
+
+    /// Keep a `.info.json` with the full metadata next to each download
+    pub fn with_write_info_json(mut self, enabled: bool) -> Self {
+        self.write_info_json = enabled;
+        self
+    }
+
+    /// Save the video thumbnail next to each download
+    pub fn with_write_thumbnail(mut self, enabled: bool) -> Self {
+        self.write_thumbnail = enabled;
+        self
+    }
+
+    /// yt-dlp `--download-archive`: record each downloaded video ID in `path`
+    pub fn with_download_archive(mut self, path: Option<PathBuf>) -> Self {
+        self.download_archive = path;
+        self
+    }
+
+    /// Audio format used when extracting audio (mp3, opus, flac, m4a)
+    pub fn with_audio_format(mut self, audio_format: String) -> Self {
+        self.audio_format = audio_format;
+        self
+    }
+
+    /// Find the `.part` file belonging to `url`, whose fetched title is `title`.
+    ///
+    /// Partials are named after the `%(title)s` output template, so only files whose
+    /// name starts with the expected stem match; unrelated partials are ignored.
+    pub fn find_partial_for(&self, url: &str, title: &str) -> Option<PathBuf> {
+        let prefix = format!("{}.", expected_file_stem(title));
+        let entries = std::fs::read_dir(self.partials_dir()).ok()?;
 
         for entry in entries.flatten() {
             let path = entry.path();
-            if let Some(ext) = path.extension() {
-                if ext == "part" {
-                    debug!("Found partial download: {:?}", path);
-                    return Some(path);
-                }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if path.extension().map(|ext| ext == "part").unwrap_or(false) && name.starts_with(&prefix) {
+                debug!("Found partial download for {}: {:?}", url, path);
+                return Some(path);
             }
         }
 
@@ -104,6 +1112,69 @@ impl Downloader {
         self.download_with_resume(url, audio_only, true).await
     }
 
+    /// Download only the subtitles in `langs` (yt-dlp `--sub-langs`, e.g. "en,de"),
+    /// skipping the video. Returns the subtitle files written, taken from yt-dlp's
+    /// output since there's no media file to look for.
+    pub async fn download_subtitles(&self, url: &str, langs: &str) -> Result<Vec<PathBuf>> {
+        info!("Downloading {} subtitles for: {}", langs, url);
+
+        std::fs::create_dir_all(&self.output_dir)?;
+
+        let args = self.subtitle_args(url, langs);
+        debug!("Executing yt-dlp with args: {:?}", args);
+
+        let output = self
+            .runner
+            .output("yt-dlp", &args)
+            .await
+            .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to execute yt-dlp: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            error!("yt-dlp failed: {}", stderr);
+            return Err(ytdlp_error(&stderr));
+        }
+
+        let files = self.subtitle_paths(&String::from_utf8_lossy(&output.stdout));
+        if files.is_empty() {
+            return Err(YtdlError::NoSubtitles(langs.to_string()));
+        }
+
+        info!("Subtitles written: {:?}", files);
+        Ok(files)
+    }
+
+    /// yt-dlp arguments for a subtitles-only download
+    fn subtitle_args(&self, url: &str, langs: &str) -> Vec<String> {
+        let mut args = self.output_args();
+        args.extend(self.cache_args());
+        args.extend(self.cookie_args());
+        args.extend(self.sleep_args());
+        args.extend([
+            "--skip-download".to_string(),
+            "--write-subs".to_string(),
+            "--sub-langs".to_string(),
+            langs.to_string(),
+        ]);
+        args.extend(self.extra_args.iter().cloned());
+        args.extend(self.source_args(url));
+        args
+    }
+
+    /// Subtitle files named in yt-dlp's output. With a temp dir they're reported
+    /// there and then moved home, so the path is rebased onto the output directory.
+    fn subtitle_paths(&self, stdout: &str) -> Vec<PathBuf> {
+        stdout
+            .lines()
+            .filter_map(|line| line.split_once("Writing video subtitles to: "))
+            .map(|(_, path)| PathBuf::from(path.trim()))
+            .map(|path| match self.temp_dir.as_ref().and_then(|temp| path.strip_prefix(temp).ok()) {
+                Some(relative) => self.output_dir.join(relative),
+                None => path,
+            })
+            .collect()
+    }
+
     fn find_newest_file(&self) -> Result<PathBuf> {
         let entries = std::fs::read_dir(&self.output_dir)
             .map_err(|e| YtdlError::Other(format!("Failed to read output directory: {}", e)))?;
@@ -113,8 +1184,8 @@ impl Downloader {
         for entry in entries.flatten() {
             let path = entry.path();
 
-            // Skip directories and .part files
-            if path.is_dir() {
+            // Skip directories, .part files and sidecars (info json, description)
+            if path.is_dir() || is_sidecar_file(&path) {
                 continue;
             }
             if let Some(ext) = path.extension() {
@@ -138,47 +1209,56 @@ impl Downloader {
             }
         }
 
-        newest_file
-            .map(|(path, _)| path)
-            .ok_or_else(|| YtdlError::Other("No downloaded file found in output directory".to_string()))
+        newest_file
+            .map(|(path, _)| path)
+            .ok_or_else(|| YtdlError::Other("No downloaded file found in output directory".to_string()))
+    }
+
+    async fn download_with_resume(&self, url: &str, audio_only: bool, continue_download: bool) -> Result<PathBuf> {
+        if self.progress_json {
+            return self
+                .download_with_callback(url, audio_only, continue_download, emit_progress_json)
+                .await;
+        }
+
+        if let Some(ref webhook) = self.webhook {
+            webhook.started(url);
+        }
+        if let Some(ref events) = self.events {
+            events.started(url);
+        }
+
+        let mut result = self.run_download(url, audio_only, continue_download, None).await;
+        for client in &self.player_client_fallbacks {
+            if !retry_with_player_client(&result, client) {
+                break;
+            }
+            result = self
+                .run_download(url, audio_only, continue_download, Some(client))
+                .await;
+        }
+
+        self.report_outcome(url, &result).await;
+        result
     }
 
-    async fn download_with_resume(&self, url: &str, audio_only: bool, continue_download: bool) -> Result<PathBuf> {
+    /// One yt-dlp run, optionally forcing a YouTube `player_client`
+    async fn run_download(
+        &self,
+        url: &str,
+        audio_only: bool,
+        continue_download: bool,
+        player_client: Option<&str>,
+    ) -> Result<PathBuf> {
         info!("Starting download: {} (audio_only: {}, resume: {})", url, audio_only, continue_download);
 
         std::fs::create_dir_all(&self.output_dir)?;
+        std::fs::create_dir_all(self.partials_dir())?;
 
-        let mut args = vec![
-            "-o".to_string(),
-            format!("{}/%(title)s.%(ext)s", self.output_dir.display()),
-            "--progress".to_string(),
-            "--newline".to_string(),
-        ];
-
-        if continue_download {
-            args.push("--continue".to_string());
-            info!("Resume mode enabled");
-        }
-
-        if audio_only {
-            args.extend_from_slice(&[
-                "-x".to_string(),
-                "--audio-format".to_string(),
-                "mp3".to_string(),
-            ]);
-            info!("Audio-only mode: converting to MP3");
-        } else {
-            if self.quality == "best" {
-                args.push("-f".to_string());
-                args.push("bestvideo+bestaudio/best".to_string());
-            } else {
-                args.push("-f".to_string());
-                args.push(format!("bestvideo[height<={}]+bestaudio/best", self.quality));
-            }
-            info!("Video quality: {}", self.quality);
-        }
+        let _permit = global_limiter().acquire().await;
+        let existing_partials = partial_files(self.partials_dir());
 
-        args.push(url.to_string());
+        let args = self.download_args(url, audio_only, continue_download, player_client);
 
         debug!("Executing yt-dlp with args: {:?}", args);
 
@@ -190,17 +1270,17 @@ impl Downloader {
                 .progress_chars("#>-"),
         );
 
-        let mut child = TokioCommand::new("yt-dlp")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
+        let SpawnedCommand { stdout, stderr, wait } = self
+            .runner
+            .spawn("yt-dlp", &args)
             .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to spawn yt-dlp: {}", e)))?;
 
-        let stdout = child.stdout.take().expect("Failed to capture stdout");
-        let stderr = child.stderr.take().expect("Failed to capture stderr");
-
         let pb_clone = pb.clone();
+        let term_progress = self.term_progress;
+        let title_progress = self.title_progress;
+        let webhook = self.webhook.clone();
+        let events = self.events.clone();
+        let webhook_url = url.to_string();
         let stdout_handle = tokio::spawn(async move {
             let reader = BufReader::new(stdout);
             let mut lines = reader.lines();
@@ -208,13 +1288,30 @@ impl Downloader {
             let progress_re = Regex::new(r"\[download\]\s+(\d+\.?\d*)%").unwrap();
             let eta_re = Regex::new(r"ETA\s+([\d:]+)").unwrap();
             let speed_re = Regex::new(r"at\s+([\d.]+\w+/s)").unwrap();
+            let mut skipped = None;
 
             while let Ok(Some(line)) = lines.next_line().await {
                 trace!("yt-dlp stdout: {}", line);
 
+                if let Some(found) = not_downloaded(&line) {
+                    skipped = Some(found);
+                }
+
                 if let Some(caps) = progress_re.captures(&line) {
                     if let Ok(percent) = caps[1].parse::<f64>() {
                         pb_clone.set_position(percent as u64);
+                        if term_progress {
+                            emit_term_progress(TermProgress::Percent(percent));
+                        }
+                        if title_progress {
+                            emit_title_progress(Some(percent));
+                        }
+                        if let Some(ref webhook) = webhook {
+                            webhook.progress(&webhook_url, percent);
+                        }
+                        if let Some(ref events) = events {
+                            events.progress(&webhook_url, percent);
+                        }
 
                         let mut msg = format!("{:.1}%", percent);
 
@@ -231,59 +1328,66 @@ impl Downloader {
                     }
                 }
             }
+
+            skipped
         });
 
         let stderr_handle = tokio::spawn(async move {
             let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
+            let mut error_lines = Vec::new();
 
             while let Ok(Some(line)) = lines.next_line().await {
                 if !line.is_empty() {
                     trace!("yt-dlp stderr: {}", line);
+                    if line.starts_with("ERROR:") {
+                        error_lines.push(line);
+                    }
                 }
             }
+
+            error_lines
         });
 
-        let status = child
-            .wait()
+        let status = wait
             .await
             .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to wait for yt-dlp: {}", e)))?;
 
-        stdout_handle.await.ok();
-        stderr_handle.await.ok();
+        let skipped = stdout_handle.await.unwrap_or_default();
+        let error_lines = stderr_handle.await.unwrap_or_default();
 
-        pb.finish_with_message("Download complete!");
-
-        if !status.success() {
-            error!("yt-dlp exited with status: {}", status);
-            return Err(YtdlError::YtdlpFailed(format!(
-                "yt-dlp exited with code {}",
-                status.code().unwrap_or(-1)
-            )));
+        if matches!(skipped, Some(NotDownloaded::Skipped(_))) {
+            pb.finish_with_message("Skipped");
+        } else {
+            pb.finish_with_message("Download complete!");
         }
+        self.finish_term_progress(status.success());
 
-        info!("Download completed successfully");
-
-        // Find the most recently created file in the output directory
-        let downloaded_file = self.find_newest_file()?;
-        info!("Downloaded file: {:?}", downloaded_file);
-
-        Ok(downloaded_file)
+        // A resumed download keeps its partials for the next attempt
+        let cleanup = (!continue_download).then_some(&existing_partials);
+        self.download_outcome(status, &error_lines, skipped, cleanup)
     }
 
     pub async fn fetch_video_info(&self, url: &str) -> Result<VideoMetadata> {
         info!("Fetching video information for: {}", url);
 
-        let output = TokioCommand::new("yt-dlp")
-            .args(&["--dump-json", "--no-playlist", url])
-            .output()
+        let mut args = vec!["--dump-json".to_string(), "--no-playlist".to_string()];
+        args.extend(self.cache_args());
+        args.extend(self.cookie_args());
+        args.extend(self.request_sleep_args());
+        args.extend(self.extra_args.iter().cloned());
+        args.push(url.to_string());
+
+        let output = self
+            .runner
+            .output("yt-dlp", &args)
             .await
             .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to execute yt-dlp: {}", e)))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             error!("yt-dlp failed: {}", stderr);
-            return Err(YtdlError::YtdlpFailed(stderr.to_string()));
+            return Err(ytdlp_error(&stderr));
         }
 
         let json_str = String::from_utf8_lossy(&output.stdout);
@@ -300,7 +1404,7 @@ impl Downloader {
                         ext: f["ext"].as_str()?.to_string(),
                         resolution: f["resolution"].as_str().map(|s| s.to_string()),
                         fps: f["fps"].as_u64().map(|v| v as u32),
-                        filesize: f["filesize"].as_u64(),
+                        filesize: f["filesize"].as_u64().or_else(|| f["filesize_approx"].as_u64()),
                         vcodec: f["vcodec"].as_str().map(|s| s.to_string()),
                         acodec: f["acodec"].as_str().map(|s| s.to_string()),
                     })
@@ -325,13 +1429,15 @@ impl Downloader {
                 .to_string(),
             duration: json_value["duration"].as_u64(),
             view_count: json_value["view_count"].as_u64(),
+            comment_count: json_value["comment_count"].as_u64(),
             upload_date: json_value["upload_date"].as_str().map(|s| s.to_string()),
             description: json_value["description"].as_str().map(|s| s.to_string()),
             thumbnail: json_value["thumbnail"].as_str().map(|s| s.to_string()),
             formats,
+            raw_json: Some(Arc::from(json_str.as_ref())),
         };
 
-        debug!("Fetched video info: {:?}", info);
+        debug!("Fetched video info: {} ({})", info.title, info.id);
         Ok(info)
     }
 
@@ -343,54 +1449,87 @@ impl Downloader {
         &self,
         url: &str,
         audio_only: bool,
-        mut progress_callback: F,
+        progress_callback: F,
     ) -> Result<PathBuf>
     where
         F: FnMut(DownloadProgressInfo) + Send + 'static,
     {
-        info!("Starting download with progress callback: {} (audio_only: {})", url, audio_only);
+        self.download_with_callback(url, audio_only, false, progress_callback).await
+    }
 
-        std::fs::create_dir_all(&self.output_dir)?;
+    async fn download_with_callback<F>(
+        &self,
+        url: &str,
+        audio_only: bool,
+        continue_download: bool,
+        progress_callback: F,
+    ) -> Result<PathBuf>
+    where
+        F: FnMut(DownloadProgressInfo) + Send + 'static,
+    {
+        if let Some(ref webhook) = self.webhook {
+            webhook.started(url);
+        }
+        if let Some(ref events) = self.events {
+            events.started(url);
+        }
 
-        let mut args = vec![
-            "-o".to_string(),
-            format!("{}/%(title)s.%(ext)s", self.output_dir.display()),
-            "--progress".to_string(),
-            "--newline".to_string(),
-        ];
+        // Shared so a retry with another player client keeps reporting to the same callback
+        let callback = Arc::new(std::sync::Mutex::new(progress_callback));
+        let forward = || {
+            let callback = Arc::clone(&callback);
+            move |progress| (callback.lock().unwrap())(progress)
+        };
 
-        if audio_only {
-            args.extend_from_slice(&[
-                "-x".to_string(),
-                "--audio-format".to_string(),
-                "mp3".to_string(),
-            ]);
-            info!("Audio-only mode: converting to MP3");
-        } else {
-            if self.quality == "best" {
-                args.push("-f".to_string());
-                args.push("bestvideo+bestaudio/best".to_string());
-            } else {
-                args.push("-f".to_string());
-                args.push(format!("bestvideo[height<={}]+bestaudio/best", self.quality));
+        let mut result = self
+            .run_download_with_callback(url, audio_only, continue_download, None, forward())
+            .await;
+        for client in &self.player_client_fallbacks {
+            if !retry_with_player_client(&result, client) {
+                break;
             }
-            info!("Video quality: {}", self.quality);
+            result = self
+                .run_download_with_callback(url, audio_only, continue_download, Some(client), forward())
+                .await;
         }
 
-        args.push(url.to_string());
+        self.report_outcome(url, &result).await;
+        result
+    }
+
+    async fn run_download_with_callback<F>(
+        &self,
+        url: &str,
+        audio_only: bool,
+        continue_download: bool,
+        player_client: Option<&str>,
+        mut progress_callback: F,
+    ) -> Result<PathBuf>
+    where
+        F: FnMut(DownloadProgressInfo) + Send + 'static,
+    {
+        info!("Starting download with progress callback: {} (audio_only: {})", url, audio_only);
+
+        std::fs::create_dir_all(&self.output_dir)?;
+        std::fs::create_dir_all(self.partials_dir())?;
+
+        let _permit = global_limiter().acquire().await;
+        let existing_partials = partial_files(self.partials_dir());
+
+        let args = self.download_args(url, audio_only, continue_download, player_client);
 
         debug!("Executing yt-dlp with args: {:?}", args);
 
-        let mut child = TokioCommand::new("yt-dlp")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
+        let SpawnedCommand { stdout, stderr, wait } = self
+            .runner
+            .spawn("yt-dlp", &args)
             .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to spawn yt-dlp: {}", e)))?;
 
-        let stdout = child.stdout.take().expect("Failed to capture stdout");
-        let stderr = child.stderr.take().expect("Failed to capture stderr");
-
+        let term_progress = self.term_progress;
+        let title_progress = self.title_progress;
+        let webhook = self.webhook.clone();
+        let events = self.events.clone();
+        let webhook_url = url.to_string();
         let stdout_handle = tokio::spawn(async move {
             let reader = BufReader::new(stdout);
             let mut lines = reader.lines();
@@ -400,10 +1539,15 @@ impl Downloader {
             let downloaded_re = Regex::new(r"\[download\]\s+[\d.]+%\s+of\s+~?\s*[\d.]+\w+\s+at\s+[\d.]+\w+/s\s+ETA\s+[\d:]+").unwrap();
             let speed_re = Regex::new(r"at\s+([\d.]+)(\w+)/s").unwrap();
             let eta_re = Regex::new(r"ETA\s+([\d:]+)").unwrap();
+            let mut skipped = None;
 
             while let Ok(Some(line)) = lines.next_line().await {
                 trace!("yt-dlp stdout: {}", line);
 
+                if let Some(found) = not_downloaded(&line) {
+                    skipped = Some(found);
+                }
+
                 if let Some(caps) = progress_re.captures(&line) {
                     if let Ok(percentage) = caps[1].parse::<f64>() {
                         let mut progress = DownloadProgressInfo {
@@ -456,40 +1600,96 @@ impl Downloader {
                             }
                         }
 
+                        if term_progress {
+                            emit_term_progress(TermProgress::Percent(percentage));
+                        }
+                        if title_progress {
+                            emit_title_progress(Some(percentage));
+                        }
+                        if let Some(ref webhook) = webhook {
+                            webhook.progress(&webhook_url, percentage);
+                        }
+                        if let Some(ref events) = events {
+                            events.progress(&webhook_url, percentage);
+                        }
                         progress_callback(progress);
                         debug!("Progress: {:.1}%", percentage);
                     }
                 }
             }
+
+            skipped
         });
 
         let stderr_handle = tokio::spawn(async move {
             let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
+            let mut error_lines = Vec::new();
 
             while let Ok(Some(line)) = lines.next_line().await {
                 if !line.is_empty() {
                     trace!("yt-dlp stderr: {}", line);
+                    if line.starts_with("ERROR:") {
+                        error_lines.push(line);
+                    }
                 }
             }
+
+            error_lines
         });
 
-        let status = child
-            .wait()
+        let status = wait
             .await
             .map_err(|e| YtdlError::YtdlpFailed(format!("Failed to wait for yt-dlp: {}", e)))?;
 
-        stdout_handle.await.ok();
-        stderr_handle.await.ok();
+        let skipped = stdout_handle.await.unwrap_or_default();
+        let error_lines = stderr_handle.await.unwrap_or_default();
+        self.finish_term_progress(status.success());
+
+        let cleanup = (!continue_download).then_some(&existing_partials);
+        self.download_outcome(status, &error_lines, skipped, cleanup)
+    }
 
+    /// Turn yt-dlp's exit status into the downloaded file or a classified error.
+    /// `not_downloaded` is what its stdout said about skipping the video, if anything.
+    /// On failure, partials not in `existing_partials` are cleaned up when it is given.
+    fn download_outcome(
+        &self,
+        status: CommandStatus,
+        error_lines: &[String],
+        not_downloaded: Option<NotDownloaded>,
+        existing_partials: Option<&HashSet<PathBuf>>,
+    ) -> Result<PathBuf> {
         if !status.success() {
             error!("yt-dlp exited with status: {}", status);
+            if let Some(existing) = existing_partials {
+                self.cleanup_failed_partials(existing);
+            }
+            if !error_lines.is_empty() {
+                return Err(ytdlp_error(&error_lines.join("\n")));
+            }
+            if let Some(NotDownloaded::Skipped(reason)) = not_downloaded {
+                info!("yt-dlp skipped the download: {}", reason);
+                return Err(YtdlError::Skipped(reason));
+            }
             return Err(YtdlError::YtdlpFailed(format!(
                 "yt-dlp exited with code {}",
                 status.code().unwrap_or(-1)
             )));
         }
 
+        match not_downloaded {
+            Some(NotDownloaded::Skipped(reason)) => {
+                info!("yt-dlp skipped the download: {}", reason);
+                return Err(YtdlError::Skipped(reason));
+            }
+            Some(NotDownloaded::Existing(path)) if path.exists() => {
+                info!("Already downloaded: {:?}", path);
+                return Ok(path);
+            }
+            _ => {}
+        }
+
         info!("Download completed successfully");
 
         // Find the most recently created file in the output directory
@@ -499,6 +1699,40 @@ impl Downloader {
         Ok(downloaded_file)
     }
 
+    async fn report_outcome(&self, url: &str, result: &Result<PathBuf>) {
+        if let Some(ref webhook) = self.webhook {
+            webhook.finished(url, result).await;
+        }
+        if let Some(ref events) = self.events {
+            match result {
+                Ok(path) => events.completed(url, path),
+                Err(YtdlError::Skipped(reason)) => events.skipped(url, reason),
+                Err(e) => events.failed(url, &e.to_string()),
+            }
+        }
+    }
+
+    fn finish_term_progress(&self, success: bool) {
+        if self.term_progress {
+            emit_term_progress(if success { TermProgress::Done } else { TermProgress::Error });
+        }
+        if self.title_progress {
+            emit_title_progress(None);
+        }
+    }
+
+    /// After a failed download, remove partial files that weren't there before it started
+    fn cleanup_failed_partials(&self, existing: &HashSet<PathBuf>) {
+        if !self.cleanup_partials {
+            return;
+        }
+
+        let removed = remove_new_partials(self.partials_dir(), existing);
+        if removed > 0 {
+            info!("Cleaned up {} partial files after failed download", removed);
+        }
+    }
+
     pub fn list_formats(&self, info: &VideoMetadata) {
         println!("\nAvailable formats for: {}", info.title);
         println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
@@ -521,3 +1755,721 @@ impl Downloader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::command::MockRunner;
+    use crate::shared::constants::TITLE_METADATA_PATTERN;
+
+    fn format(id: &str, resolution: Option<&str>, fps: Option<u32>, filesize: Option<u64>) -> Format {
+        Format {
+            format_id: id.to_string(),
+            ext: "mp4".to_string(),
+            resolution: resolution.map(|r| r.to_string()),
+            fps,
+            filesize,
+            vcodec: Some("avc1".to_string()),
+            acodec: None,
+        }
+    }
+
+    fn ids(formats: &[Format]) -> Vec<&str> {
+        formats.iter().map(|f| f.format_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_formats() {
+        let mut formats = vec![
+            format("a", Some("1280x720"), Some(30), None),
+            format("b", None, Some(60), Some(500)),
+            format("c", Some("1920x1080"), None, Some(100)),
+            format("d", Some("audio only"), Some(24), Some(900)),
+        ];
+
+        sort_formats(&mut formats, SortKey::Resolution);
+        assert_eq!(ids(&formats), ["c", "a", "b", "d"]);
+
+        sort_formats(&mut formats, SortKey::Size);
+        assert_eq!(ids(&formats), ["d", "b", "c", "a"]);
+
+        sort_formats(&mut formats, SortKey::Fps);
+        assert_eq!(ids(&formats), ["b", "a", "d", "c"]);
+    }
+
+    #[test]
+    fn test_progress_json_line() {
+        let progress = DownloadProgressInfo {
+            percentage: 42.5,
+            downloaded_bytes: 1024,
+            total_bytes: 4096,
+            speed: 512.0,
+            eta: Some(6),
+        };
+        let line: serde_json::Value = serde_json::from_str(&progress_json_line(&progress)).unwrap();
+        assert_eq!(
+            line,
+            serde_json::json!({"percent": 42.5, "downloaded": 1024, "total": 4096, "speed": 512.0, "eta": 6})
+        );
+
+        let unknown_eta = DownloadProgressInfo { eta: None, ..progress };
+        assert!(progress_json_line(&unknown_eta).contains("\"eta\":null"));
+    }
+
+    #[test]
+    fn test_quality_fallback() {
+        let info = VideoMetadata {
+            id: "id".to_string(),
+            title: "title".to_string(),
+            uploader: "uploader".to_string(),
+            duration: None,
+            view_count: None,
+            comment_count: None,
+            upload_date: None,
+            description: None,
+            thumbnail: None,
+            formats: vec![
+                format("a", Some("1920x1080"), Some(30), None),
+                format("b", Some("1280x720"), Some(60), None),
+                format("c", Some("audio only"), None, None),
+            ],
+            raw_json: None,
+        };
+
+        assert_eq!(info.best_available_height(), Some(1080));
+        assert_eq!(info.quality_fallback("2160"), Some(1080));
+        assert_eq!(info.quality_fallback("2160p"), Some(1080));
+        assert_eq!(info.quality_fallback("1080"), None);
+        assert_eq!(info.quality_fallback("720"), None);
+        assert_eq!(info.quality_fallback("best"), None);
+    }
+
+    #[test]
+    fn test_estimated_size() {
+        let mut audio = format("audio", Some("audio only"), None, Some(5_000_000));
+        audio.vcodec = Some("none".to_string());
+        let mut info = VideoMetadata {
+            id: "id".to_string(),
+            title: "title".to_string(),
+            uploader: "uploader".to_string(),
+            duration: None,
+            view_count: None,
+            comment_count: None,
+            upload_date: None,
+            description: None,
+            thumbnail: None,
+            formats: vec![
+                format("a", Some("1920x1080"), Some(30), Some(300_000_000)),
+                format("b", Some("1280x720"), Some(30), Some(100_000_000)),
+                audio,
+            ],
+            raw_json: None,
+        };
+
+        assert_eq!(info.estimated_size("best", false), Some(305_000_000));
+        assert_eq!(info.estimated_size("720p", false), Some(105_000_000));
+        assert_eq!(info.estimated_size("best", true), Some(5_000_000));
+
+        info.formats.iter_mut().for_each(|f| f.filesize = None);
+        assert_eq!(info.estimated_size("best", false), None);
+        assert!(info.ensure_downloadable().is_ok());
+
+        info.formats.clear();
+        assert!(matches!(info.ensure_downloadable(), Err(YtdlError::NoFormats(id)) if id == "id"));
+    }
+
+    #[test]
+    fn test_bot_check_detection() {
+        assert!(is_bot_check(
+            "ERROR: [youtube] abc123: Sign in to confirm you’re not a bot. Use --cookies-from-browser"
+        ));
+        assert!(is_bot_check("ERROR: Please confirm you're not a bot"));
+        assert!(!is_bot_check("ERROR: [youtube] abc123: Video unavailable"));
+
+        assert!(!is_bot_check("ERROR: Sign in to confirm your age"));
+
+        assert!(matches!(ytdlp_error("Sign in to confirm you're not a bot"), YtdlError::BotCheck(_)));
+        assert!(matches!(ytdlp_error("HTTP Error 404"), YtdlError::YtdlpFailed(_)));
+    }
+
+    #[test]
+    fn test_find_partial_for() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "Other Video.mp4.part",
+            "My Video： Part 2.f137.mp4.part",
+            "My Video： Part 2.f137.mp4.ytdl",
+            "My Video： Part 20.webm.part",
+        ] {
+            std::fs::write(dir.path().join(name), b"partial").unwrap();
+        }
+
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string());
+        assert_eq!(
+            downloader.find_partial_for("https://youtu.be/abc", "My Video: Part 2"),
+            Some(dir.path().join("My Video： Part 2.f137.mp4.part"))
+        );
+        assert_eq!(downloader.find_partial_for("https://youtu.be/xyz", "Missing"), None);
+    }
+
+    #[test]
+    fn test_extractor_error_suggests_update() {
+        let stderr = "ERROR: [youtube] dQw4w9WgXcQ: Unable to extract uploader id; please report this issue on  https://github.com/yt-dlp/yt-dlp/issues?q= , filling out the appropriate issue template. Confirm you are on the latest version using  yt-dlp -U";
+        assert!(is_extractor_error(stderr));
+
+        let error = ytdlp_error(stderr);
+        assert!(matches!(error, YtdlError::ExtractorOutdated(_)));
+        assert!(error.suggests_update());
+
+        assert!(!ytdlp_error("ERROR: [youtube] abc: Video unavailable").suggests_update());
+    }
+
+    #[test]
+    fn test_title_from_path() {
+        assert_eq!(
+            title_from_path(Path::new("downloads/My Video.mp4")),
+            Some("My Video".to_string())
+        );
+        assert_eq!(title_from_path(Path::new("downloads/")), Some("downloads".to_string()));
+        assert_eq!(title_from_path(Path::new("")), None);
+    }
+
+    #[test]
+    fn test_sort_key_parse() {
+        assert_eq!(SortKey::parse("res"), Some(SortKey::Resolution));
+        assert_eq!(SortKey::parse("SIZE"), Some(SortKey::Size));
+        assert_eq!(SortKey::parse("fps"), Some(SortKey::Fps));
+        assert_eq!(SortKey::parse("bitrate"), None);
+    }
+
+    #[test]
+    fn test_failed_download_removes_only_new_partials() {
+        let dir = tempfile::tempdir().unwrap();
+        let resumable = dir.path().join("earlier.mp4.part");
+        std::fs::write(&resumable, b"old").unwrap();
+
+        let existing = partial_files(dir.path());
+
+        // What a failed yt-dlp run leaves behind
+        let new_part = dir.path().join("video.mp4.part");
+        let new_ytdl = dir.path().join("video.mp4.ytdl");
+        let finished = dir.path().join("other.mp4");
+        for path in [&new_part, &new_ytdl, &finished] {
+            std::fs::write(path, b"new").unwrap();
+        }
+
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string());
+        downloader.cleanup_failed_partials(&existing);
+        assert!(new_part.exists(), "cleanup is opt-in");
+
+        let downloader = downloader.with_cleanup_partials(true);
+        downloader.cleanup_failed_partials(&existing);
+        assert!(resumable.exists());
+        assert!(finished.exists());
+        assert!(!new_part.exists());
+        assert!(!new_ytdl.exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_with_progress_parses_ytdlp_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let video = dir.path().join("Test Video.mp4");
+        std::fs::write(&video, b"video").unwrap();
+
+        let stdout = "[youtube] abc: Downloading webpage\n\
+            [download]  50.0% of 10.00MiB at  1.00MiB/s ETA 00:05\n\
+            [download] 100.0% of 10.00MiB at  2.00MiB/s ETA 00:00\n";
+        let runner = Arc::new(MockRunner::new(stdout, "", 0));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "720".to_string())
+            .with_runner(runner.clone());
+
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&updates);
+        let path = downloader
+            .download_with_progress("https://youtu.be/abc", false, move |p| sink.lock().unwrap().push(p))
+            .await
+            .unwrap();
+        assert_eq!(path, video);
+
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].percentage, 50.0);
+        assert_eq!(updates[0].total_bytes, 10 * BYTES_PER_MB as u64);
+        assert_eq!(updates[0].downloaded_bytes, 5 * BYTES_PER_MB as u64);
+        assert_eq!(updates[0].speed, BYTES_PER_MB);
+        assert_eq!(updates[0].eta, Some(5));
+        assert_eq!(updates[1].percentage, 100.0);
+
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0][0], "yt-dlp");
+        assert!(calls[0]
+            .windows(2)
+            .any(|w| w[0] == "-f" && w[1] == "bestvideo[height<=720]+bestaudio/best"));
+        assert_eq!(calls[0].last().map(String::as_str), Some("https://youtu.be/abc"));
+    }
+
+    #[tokio::test]
+    async fn test_download_with_progress_classifies_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let stderr = "WARNING: retrying\nERROR: [youtube] abc: Sign in to confirm you're not a bot\n";
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_runner(Arc::new(MockRunner::new("", stderr, 1)));
+
+        let result = downloader.download_with_progress("https://youtu.be/abc", true, |_| {}).await;
+        assert!(matches!(result, Err(YtdlError::BotCheck(_))));
+
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_runner(Arc::new(MockRunner::new("", "", 2)));
+        let result = downloader.download_with_progress("https://youtu.be/abc", true, |_| {}).await;
+        assert!(matches!(result, Err(YtdlError::YtdlpFailed(msg)) if msg.contains("code 2")));
+    }
+
+    #[tokio::test]
+    async fn test_skipped_download_is_not_reported_as_newest_file() {
+        let dir = tempfile::tempdir().unwrap();
+        // Left over from an earlier download
+        let other = dir.path().join("Other Video.mp4");
+        std::fs::write(&other, b"video").unwrap();
+
+        for stdout in [
+            "[download] Test Video does not pass filter (view_count >= 1000), skipping ..\n",
+            "[download] File is larger than max-filesize (52428800 bytes > 1048576 bytes). Aborting.\n",
+            "\r[download] File is smaller than min-filesize (1024 bytes < 1048576 bytes). Aborting.\n",
+        ] {
+            let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+                .with_runner(Arc::new(MockRunner::new(stdout, "", 0)));
+            let result = downloader.download("https://youtu.be/abc", false).await;
+            assert!(matches!(result, Err(YtdlError::Skipped(_))), "{:?}", result);
+
+            let result = downloader.download_with_progress("https://youtu.be/abc", false, |_| {}).await;
+            assert!(matches!(result, Err(YtdlError::Skipped(_))), "{:?}", result);
+        }
+
+        // --no-overwrites: yt-dlp names the existing file, which isn't the newest
+        let existing = dir.path().join("Test Video.mp4");
+        std::fs::write(&existing, b"video").unwrap();
+        std::fs::write(&other, b"newer").unwrap();
+        let stdout = format!("[download] {} has already been downloaded\n", existing.display());
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_runner(Arc::new(MockRunner::new(&stdout, "", 0)));
+        assert_eq!(downloader.download("https://youtu.be/abc", false).await.unwrap(), existing);
+    }
+
+    #[test]
+    fn test_not_downloaded_lines() {
+        assert_eq!(
+            not_downloaded("[download] Rick Astley has already been recorded in the archive"),
+            Some(NotDownloaded::Skipped("Rick Astley has already been recorded in the archive".to_string()))
+        );
+        assert_eq!(
+            not_downloaded("[download] Skipping \"Rick Astley\" because it is age restricted"),
+            Some(NotDownloaded::Skipped("Skipping \"Rick Astley\" because it is age restricted".to_string()))
+        );
+        assert_eq!(
+            not_downloaded("[download] /tmp/Rick Astley.mp4 has already been downloaded and merged"),
+            Some(NotDownloaded::Existing(PathBuf::from("/tmp/Rick Astley.mp4")))
+        );
+        assert_eq!(not_downloaded("[download]  50.0% of 10.00MiB at  1.00MiB/s ETA 00:05"), None);
+        assert_eq!(not_downloaded("[download] Destination: /tmp/Rick Astley.mp4"), None);
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_retries_with_fallback_player_client() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("video.mp4"), b"data").unwrap();
+
+        // Every run without a forced player client gets a 403
+        let runner = Arc::new(MockRunner::new("", "", 0).failing_for(
+            "https://youtu.be/abc",
+            "ERROR: unable to download video data: HTTP Error 403: Forbidden\n",
+            1,
+        ));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_player_client_fallbacks(Some(vec!["android".to_string(), "ios".to_string()]))
+            .with_runner(runner.clone());
+
+        let result = downloader.download("https://youtu.be/abc", false).await;
+        assert!(matches!(result, Err(YtdlError::Forbidden(_))));
+
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 3);
+        assert!(!calls[0].iter().any(|arg| arg == "--extractor-args"));
+        let client = |call: &Vec<String>| {
+            call.windows(2)
+                .find(|w| w[0] == "--extractor-args")
+                .map(|w| w[1].clone())
+        };
+        assert_eq!(client(&calls[1]).as_deref(), Some("youtube:player_client=android"));
+        assert_eq!(client(&calls[2]).as_deref(), Some("youtube:player_client=ios"));
+
+        // Other failures aren't retried
+        let runner = Arc::new(MockRunner::new("", "ERROR: [youtube] abc: Video unavailable\n", 1));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_runner(runner.clone());
+        let _ = downloader.download_with_progress("https://youtu.be/abc", false, |_| {}).await;
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_audio_section_composes_with_extraction() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("clip.mp3"), b"data").unwrap();
+        let section = crate::cli::parse_section("0:30-0:45").unwrap();
+        let runner = Arc::new(MockRunner::new("", "", 0));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_download_sections(Some(section))
+            .with_runner(runner.clone());
+
+        downloader.download("https://youtu.be/abc", true).await.unwrap();
+        downloader
+            .download_with_progress("https://youtu.be/abc", true, |_| {})
+            .await
+            .unwrap();
+
+        for call in runner.calls() {
+            let value_after = |flag: &str| {
+                call.windows(2)
+                    .find(|w| w[0] == flag)
+                    .map(|w| w[1].as_str())
+            };
+            assert_eq!(value_after("--download-sections"), Some("*0:30-0:45"));
+            assert_eq!(value_after("-f"), Some("bestaudio/best"));
+            assert_eq!(value_after("--audio-format"), Some("mp3"));
+            assert!(call.iter().any(|arg| arg == "-x"));
+            assert_eq!(call.iter().filter(|arg| *arg == "-f").count(), 1);
+            assert_eq!(call.last().map(String::as_str), Some("https://youtu.be/abc"));
+        }
+    }
+
+    #[test]
+    fn test_overwrite_args_compose_with_continue() {
+        let downloader = Downloader::new(PathBuf::from("/tmp"), "best".to_string());
+        let url = "https://youtu.be/abc";
+
+        let args = downloader.download_args(url, false, true, None);
+        assert!(args.contains(&"--continue".to_string()));
+        assert!(!args.iter().any(|a| a.contains("overwrites")));
+
+        let args = downloader.clone().with_overwrites(Some(false)).download_args(url, false, true, None);
+        assert!(args.contains(&"--no-overwrites".to_string()));
+        assert!(args.contains(&"--continue".to_string()));
+
+        let args = downloader.clone().with_overwrites(Some(true)).download_args(url, false, false, None);
+        assert!(args.contains(&"--force-overwrites".to_string()));
+        let args = downloader.with_overwrites(Some(true)).download_args(url, false, true, None);
+        assert!(!args.contains(&"--continue".to_string()));
+    }
+
+    #[test]
+    fn test_recode_video_args() {
+        let downloader = Downloader::new(PathBuf::from("/tmp"), "720".to_string())
+            .with_recode_video(Some("mp4".to_string()));
+
+        let args = downloader.download_args("https://youtu.be/abc", false, false, None);
+        assert!(args.windows(2).any(|w| w[0] == "--recode-video" && w[1] == "mp4"));
+
+        // Audio extraction never recodes video
+        let args = downloader.download_args("https://youtu.be/abc", true, false, None);
+        assert!(!args.contains(&"--recode-video".to_string()));
+    }
+
+    #[test]
+    fn test_parse_metadata_args() {
+        let downloader = Downloader::new(PathBuf::from("/tmp"), "best".to_string());
+        let args = downloader.download_args("https://youtu.be/abc", true, false, None);
+        assert!(!args.contains(&"--parse-metadata".to_string()));
+
+        let args = downloader
+            .with_parse_metadata(Some(TITLE_METADATA_PATTERN.to_string()))
+            .download_args("https://youtu.be/abc", true, false, None);
+        assert!(args
+            .windows(2)
+            .any(|w| w[0] == "--parse-metadata" && w[1] == "title:%(artist)s - %(title)s"));
+        assert!(args.contains(&"--embed-metadata".to_string()));
+        assert!(args.contains(&"-x".to_string()));
+    }
+
+    #[test]
+    fn test_filesize_limit_args() {
+        let downloader = Downloader::new(PathBuf::from("/tmp"), "best".to_string());
+        let args = downloader.download_args("https://youtu.be/abc", false, false, None);
+        assert!(!args.contains(&"--max-filesize".to_string()));
+
+        let args = downloader
+            .with_filesize_limits(None, Some(2 * 1_073_741_824))
+            .download_args("https://youtu.be/abc", false, false, None);
+        assert!(args.windows(2).any(|w| w[0] == "--max-filesize" && w[1] == "2147483648"));
+        assert!(!args.contains(&"--min-filesize".to_string()));
+    }
+
+    #[test]
+    fn test_from_config_forwards_options() {
+        let config = Config {
+            quality: "720".to_string(),
+            min_views: Some(1000),
+            min_filesize: Some(1024),
+            age_limit: Some(18),
+            prefer_free_formats: Some(true),
+            write_comments: Some(true),
+            sponsorblock_export: Some(PathBuf::from("segments.json")),
+            parse_metadata: Some("title:%(artist)s - %(title)s".to_string()),
+            recode_video: Some("mp4".to_string()),
+            id_filename: Some(true),
+            webhook_url: Some("http://localhost/hook".to_string()),
+            events_file: Some(PathBuf::from("events.ndjson")),
+            ..Config::default()
+        };
+        let downloader = Downloader::from_config(&config);
+        let args = downloader.download_args("https://youtu.be/abc", false, false, None);
+
+        assert!(args.windows(2).any(|w| w[0] == "--match-filter" && w[1] == "view_count>=1000"));
+        assert!(args.windows(2).any(|w| w[0] == "--min-filesize" && w[1] == "1024"));
+        assert!(args.windows(2).any(|w| w[0] == "--age-limit" && w[1] == "18"));
+        assert!(args.windows(2).any(|w| w[0] == "--recode-video" && w[1] == "mp4"));
+        assert!(args.contains(&"--prefer-free-formats".to_string()));
+        assert!(args.contains(&"--write-comments".to_string()));
+        assert!(args.contains(&"--sponsorblock-mark".to_string()));
+        assert!(args.contains(&"--parse-metadata".to_string()));
+        assert!(args.iter().any(|a| a.contains("%(id)s")));
+        assert!(downloader.webhook.is_some());
+        assert!(downloader.events.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_temp_dir_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("nas");
+        let temp = dir.path().join("fast");
+        let runner = Arc::new(MockRunner::new("", "", 0));
+        let downloader = Downloader::new(home.clone(), "best".to_string())
+            .with_temp_dir(Some(temp.clone()))
+            .with_runner(runner.clone());
+
+        // yt-dlp leaves the partial in temp and moves the finished file home
+        std::fs::create_dir_all(&temp).unwrap();
+        std::fs::write(temp.join("Other.mp4.part"), b"partial").unwrap();
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(home.join("Video.mp4"), b"video").unwrap();
+
+        let path = downloader.download("https://youtu.be/abc", false).await.unwrap();
+        assert_eq!(path, home.join("Video.mp4"));
+        assert_eq!(downloader.find_partial_for("https://youtu.be/xyz", "Other"), Some(temp.join("Other.mp4.part")));
+
+        let call = &runner.calls()[0];
+        let has = |flag: &str, value: &str| call.windows(2).any(|w| w[0] == flag && w[1] == value);
+        assert!(has("-P", &format!("home:{}", home.display())));
+        assert!(has("-P", &format!("temp:{}", temp.display())));
+        assert!(has("-o", "%(title)s.%(ext)s"));
+
+        let runner = Arc::new(MockRunner::new("", "", 1));
+        let _ = Downloader::new(home.clone(), "best".to_string())
+            .with_runner(runner.clone())
+            .download("https://youtu.be/abc", false)
+            .await;
+        let call = &runner.calls()[0];
+        assert!(!call.contains(&"-P".to_string()));
+        assert!(call
+            .windows(2)
+            .any(|w| w[0] == "-o" && w[1] == format!("{}/%(title)s.%(ext)s", home.display())));
+    }
+
+    #[tokio::test]
+    async fn test_id_filename() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Arc::new(MockRunner::new("", "", 0));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_id_filename(true)
+            .with_runner(runner.clone());
+        assert_eq!(downloader.output_template(), "%(id)s.%(ext)s");
+        assert_eq!(
+            downloader.clone().with_download_sections(Some("*0:10-0:20".to_string())).output_template(),
+            "%(id)s [%(section_start)s-%(section_end)s].%(ext)s"
+        );
+        assert_eq!(downloader.file_stem_for("My Video", "dQw4w9WgXcQ"), "dQw4w9WgXcQ");
+
+        std::fs::write(dir.path().join("dQw4w9WgXcQ.mp4"), b"video").unwrap();
+        let path = downloader.download("https://youtu.be/dQw4w9WgXcQ", false).await.unwrap();
+        assert_eq!(path, dir.path().join("dQw4w9WgXcQ.mp4"));
+        assert!(runner.calls()[0]
+            .windows(2)
+            .any(|w| w[0] == "-o" && w[1] == format!("{}/%(id)s.%(ext)s", dir.path().display())));
+
+        let entry = crate::core::history::HistoryEntry::new(
+            "https://youtu.be/dQw4w9WgXcQ".to_string(),
+            title_from_path(&path).unwrap(),
+            path.clone(),
+            5,
+            "best".to_string(),
+            "mp4".to_string(),
+        );
+        assert_eq!(entry.title, "dQw4w9WgXcQ");
+        assert_eq!(entry.file_path, dir.path().join("dQw4w9WgXcQ.mp4"));
+    }
+
+    #[tokio::test]
+    async fn test_sleep_intervals_passed_to_ytdlp() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = r#"{"id": "abc", "title": "Test Video", "formats": []}"#;
+        let runner = Arc::new(MockRunner::new(json, "", 0));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_sleep_intervals(Some(5.0), Some(30.0), Some(1.5))
+            .with_runner(runner.clone());
+
+        downloader.fetch_video_info("https://youtu.be/abc").await.unwrap();
+        let _ = downloader.download("https://youtu.be/abc", false).await;
+
+        let calls = runner.calls();
+        let has = |call: &[String], flag: &str, value: &str| call.windows(2).any(|w| w[0] == flag && w[1] == value);
+        assert!(has(&calls[0], "--sleep-requests", "1.5"));
+        assert!(!calls[0].contains(&"--sleep-interval".to_string()));
+        assert!(has(&calls[1], "--sleep-interval", "5"));
+        assert!(has(&calls[1], "--max-sleep-interval", "30"));
+        assert!(has(&calls[1], "--sleep-requests", "1.5"));
+
+        // A max without a base interval would make yt-dlp exit with an error
+        let runner = Arc::new(MockRunner::new(json, "", 0));
+        let _ = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_sleep_intervals(None, Some(30.0), None)
+            .with_runner(runner.clone())
+            .download("https://youtu.be/abc", false)
+            .await;
+        assert!(!runner.calls()[0].iter().any(|arg| arg.contains("sleep")));
+    }
+
+    #[tokio::test]
+    async fn test_cache_dir_passed_when_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("ytdlp-cache");
+        let json = r#"{"id": "abc", "title": "Test Video", "formats": []}"#;
+        let runner = Arc::new(MockRunner::new(json, "", 0));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_cache_dir(Some(cache.clone()))
+            .with_runner(runner.clone());
+
+        downloader.fetch_video_info("https://youtu.be/abc").await.unwrap();
+        let _ = downloader.download("https://youtu.be/abc", false).await;
+
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 2);
+        for call in &calls {
+            assert!(call
+                .windows(2)
+                .any(|w| w[0] == "--cache-dir" && w[1] == cache.display().to_string()));
+        }
+
+        let runner = Arc::new(MockRunner::new(json, "", 0));
+        Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_runner(runner.clone())
+            .fetch_video_info("https://youtu.be/abc")
+            .await
+            .unwrap();
+        assert!(!runner.calls()[0].iter().any(|arg| arg == "--cache-dir"));
+    }
+
+    #[tokio::test]
+    async fn test_match_filter_passed_as_single_argument() {
+        let dir = tempfile::tempdir().unwrap();
+        let filter = "duration > 60 & title !~= '(?i)live stream' & like_count >? 100";
+        let runner = Arc::new(MockRunner::new("", "", 1));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_match_filter(Some(filter.to_string()))
+            .with_runner(runner.clone());
+
+        let _ = downloader.download("https://youtu.be/abc", false).await;
+        let _ = downloader.download_with_progress("https://youtu.be/abc", true, |_| {}).await;
+
+        for call in runner.calls() {
+            let at = call.iter().position(|arg| arg == "--match-filter").unwrap();
+            assert_eq!(call[at + 1], filter);
+            assert_eq!(call.iter().filter(|arg| arg.contains("live stream")).count(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audio_quality_only_for_audio_downloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Arc::new(MockRunner::new("", "", 1));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_audio_quality(Some("192K".to_string()))
+            .with_runner(runner.clone());
+
+        let _ = downloader.download("https://youtu.be/abc", true).await;
+        let _ = downloader.download_with_progress("https://youtu.be/abc", true, |_| {}).await;
+        let _ = downloader.download("https://youtu.be/abc", false).await;
+
+        let has_quality = |call: &Vec<String>| {
+            call.windows(2).any(|w| w[0] == "--audio-quality" && w[1] == "192K")
+        };
+        let calls = runner.calls();
+        assert!(has_quality(&calls[0]));
+        assert!(has_quality(&calls[1]));
+        assert!(!calls[2].iter().any(|arg| arg == "--audio-quality"));
+    }
+
+    #[tokio::test]
+    async fn test_download_subtitles() {
+        let dir = tempfile::tempdir().unwrap();
+        let stdout = format!(
+            "[youtube] abc: Downloading webpage\n[info] Writing video subtitles to: {}/My Video.en.vtt\n",
+            dir.path().display()
+        );
+        let runner = Arc::new(MockRunner::new(&stdout, "", 0));
+        let downloader = Downloader::new(dir.path().to_path_buf(), "best".to_string()).with_runner(runner.clone());
+
+        let files = downloader.download_subtitles("https://youtu.be/abc", "en").await.unwrap();
+        assert_eq!(files, vec![dir.path().join("My Video.en.vtt")]);
+
+        let call = &runner.calls()[0];
+        assert!(call.contains(&"--skip-download".to_string()));
+        assert!(call.contains(&"--write-subs".to_string()));
+        assert!(call.windows(2).any(|w| w[0] == "--sub-langs" && w[1] == "en"));
+        assert!(!call.iter().any(|arg| arg == "-f" || arg == "-x"));
+
+        // yt-dlp succeeds with a warning when the language doesn't exist
+        let runner = Arc::new(MockRunner::new("[youtube] abc: Downloading webpage\n", "", 0));
+        let result = Downloader::new(dir.path().to_path_buf(), "best".to_string())
+            .with_runner(runner)
+            .download_subtitles("https://youtu.be/abc", "xx")
+            .await;
+        assert!(matches!(result, Err(YtdlError::NoSubtitles(_))));
+    }
+
+    #[test]
+    fn test_audio_copy_args() {
+        let downloader = Downloader::new(PathBuf::from("/tmp"), "best".to_string())
+            .with_audio_format("mp3".to_string())
+            .with_audio_quality(Some("192K".to_string()))
+            .with_audio_copy(true);
+
+        let args = downloader.download_args("https://youtu.be/abc", true, false, None);
+        assert!(args.windows(2).any(|w| w[0] == "-f" && w[1] == "bestaudio/best"));
+        assert!(args.contains(&"-x".to_string()));
+        assert!(args.windows(2).any(|w| w[0] == "--audio-format" && w[1] == "best"));
+        assert!(!args.contains(&"mp3".to_string()));
+        assert!(!args.contains(&"--audio-quality".to_string()));
+
+        // Video downloads are unaffected
+        let args = downloader.download_args("https://youtu.be/abc", false, false, None);
+        assert!(!args.contains(&"--audio-format".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_video_info_with_mock_runner() {
+        let json = r#"{"id": "abc", "title": "Test Video", "uploader": "Someone", "duration": 61, "comment_count": 1234, "formats": []}"#;
+        let runner = Arc::new(MockRunner::new(json, "", 0));
+        let downloader = Downloader::new(PathBuf::from("/tmp"), "best".to_string())
+            .with_extra_args(vec!["--no-mtime".to_string()])
+            .with_runner(runner.clone());
+
+        let info = downloader.fetch_video_info("https://youtu.be/abc").await.unwrap();
+        assert_eq!(info.id, "abc");
+        assert_eq!(info.title, "Test Video");
+        assert_eq!(info.duration, Some(61));
+        assert_eq!(info.comment_count, Some(1234));
+        assert_eq!(
+            runner.calls()[0],
+            ["yt-dlp", "--dump-json", "--no-playlist", "--no-mtime", "https://youtu.be/abc"]
+        );
+    }
+}
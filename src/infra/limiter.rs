@@ -0,0 +1,85 @@
+use crate::shared::constants::{
+    DEFAULT_CONCURRENT_DOWNLOADS, MAX_CONCURRENT_DOWNLOADS, MIN_CONCURRENT_DOWNLOADS,
+};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, warn};
+
+static GLOBAL_LIMITER: OnceLock<DownloadLimiter> = OnceLock::new();
+
+/// Bounds how many yt-dlp downloads run at once, whatever started them
+#[derive(Debug, Clone)]
+pub struct DownloadLimiter {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+}
+
+impl DownloadLimiter {
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.clamp(MIN_CONCURRENT_DOWNLOADS, MAX_CONCURRENT_DOWNLOADS);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+        }
+    }
+
+    /// Wait for a free download slot; the slot is released when the permit is dropped
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        debug!(
+            "Waiting for download slot ({} of {} free)",
+            self.semaphore.available_permits(),
+            self.limit
+        );
+        Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .expect("download semaphore is never closed")
+    }
+}
+
+/// Size the process-wide limiter; must be called before the first download
+pub fn init_download_limit(limit: usize) {
+    if GLOBAL_LIMITER.set(DownloadLimiter::new(limit)).is_err() {
+        warn!("Download limit already initialized, ignoring new limit {}", limit);
+    }
+}
+
+pub fn global_limiter() -> &'static DownloadLimiter {
+    GLOBAL_LIMITER.get_or_init(|| DownloadLimiter::new(DEFAULT_CONCURRENT_DOWNLOADS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limiter_bounds_concurrency() {
+        let limit = 3;
+        let limiter = DownloadLimiter::new(limit);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_seen = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..limit + 2)
+            .map(|_| {
+                let limiter = limiter.clone();
+                let running = Arc::clone(&running);
+                let max_seen = Arc::clone(&max_seen);
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire().await;
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_seen.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(max_seen.load(Ordering::SeqCst), limit);
+    }
+}
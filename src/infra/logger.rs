@@ -1,9 +1,200 @@
+use crate::shared::constants::{
+    DEFAULT_LOG_RETENTION_DAYS, EVENTS_PROGRESS_INTERVAL_MS, LOG_FILE_PREFIX, LOG_PANEL_CAPACITY,
+};
 use anyhow::Result;
+use chrono::{Local, NaiveDate, SecondsFormat, Utc};
 use directories::ProjectDirs;
-use std::path::PathBuf;
-use tracing::Level;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn, Level};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+use tracing_subscriber::{
+    fmt, fmt::writer::BoxMakeWriter, fmt::MakeWriter, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
+};
+
+/// In-memory ring buffer of formatted log lines, shown in the TUI's log panel.
+/// Cloning shares the same buffer.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Snapshot of the buffered lines, oldest first
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().map(|l| l.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    /// Append a line, dropping the oldest once the buffer is full
+    pub fn push(&self, line: String) {
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() == self.capacity {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(LOG_PANEL_CAPACITY)
+    }
+}
+
+/// Writer for a single event; its lines are added to the buffer when dropped
+pub struct LogBufferWriter {
+    buffer: LogBuffer,
+    bytes: Vec<u8>,
+}
+
+impl Write for LogBufferWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LogBufferWriter {
+    fn drop(&mut self) {
+        for line in String::from_utf8_lossy(&self.bytes).lines() {
+            if !line.trim().is_empty() {
+                self.buffer.push(line.to_string());
+            }
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBufferWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogBufferWriter {
+            buffer: self.clone(),
+            bytes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadEventKind {
+    Started,
+    Progress,
+    Completed,
+    Failed,
+    Skipped,
+}
+
+/// One line of the `--events-file` stream
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadEvent {
+    pub timestamp: String,
+    pub event: DownloadEventKind,
+    pub url: String,
+    pub details: serde_json::Value,
+}
+
+impl DownloadEvent {
+    pub fn new(event: DownloadEventKind, url: &str, details: serde_json::Value) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            event,
+            url: url.to_string(),
+            details,
+        }
+    }
+}
+
+/// Appends download lifecycle events to a file as NDJSON, one object per line.
+/// Independent of the `tracing` subscriber; write failures are logged and never
+/// affect the download.
+#[derive(Debug, Clone)]
+pub struct EventLogger {
+    path: PathBuf,
+    last_progress: Arc<Mutex<Option<Instant>>>,
+}
+
+impl EventLogger {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            last_progress: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Append `event` as a single line
+    pub fn log(&self, event: &DownloadEvent) {
+        let mut line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Failed to serialize download event: {}", e);
+                return;
+            }
+        };
+        line.push('\n');
+
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        if let Err(e) = written {
+            warn!("Failed to write event to {:?}: {}", self.path, e);
+        }
+    }
+
+    pub fn started(&self, url: &str) {
+        self.log(&DownloadEvent::new(DownloadEventKind::Started, url, serde_json::json!({})));
+    }
+
+    /// Log a progress update, at most one per `EVENTS_PROGRESS_INTERVAL_MS`
+    pub fn progress(&self, url: &str, percent: f64) {
+        {
+            let mut last = self.last_progress.lock().unwrap_or_else(|e| e.into_inner());
+            let interval = Duration::from_millis(EVENTS_PROGRESS_INTERVAL_MS);
+            if last.is_some_and(|logged| logged.elapsed() < interval) {
+                return;
+            }
+            *last = Some(Instant::now());
+        }
+
+        let details = serde_json::json!({ "percent": percent });
+        self.log(&DownloadEvent::new(DownloadEventKind::Progress, url, details));
+    }
+
+    pub fn completed(&self, url: &str, path: &Path) {
+        let details = serde_json::json!({ "path": path });
+        self.log(&DownloadEvent::new(DownloadEventKind::Completed, url, details));
+    }
+
+    pub fn failed(&self, url: &str, error: &str) {
+        let details = serde_json::json!({ "error": error });
+        self.log(&DownloadEvent::new(DownloadEventKind::Failed, url, details));
+    }
+
+    pub fn skipped(&self, url: &str, reason: &str) {
+        let details = serde_json::json!({ "reason": reason });
+        self.log(&DownloadEvent::new(DownloadEventKind::Skipped, url, details));
+    }
+}
 
 pub struct LoggerConfig {
     pub level: Level,
@@ -11,6 +202,16 @@ pub struct LoggerConfig {
     pub enable_json_format: bool,
     pub log_dir: Option<PathBuf>,
     pub tui_mode: bool,
+    /// Colored console output; off for `--no-color` / `NO_COLOR`
+    pub ansi: bool,
+    /// `EnvFilter` directives from `--log-filter`, e.g. `ytdl::infra::downloader=debug,info`
+    pub filter: Option<String>,
+    /// Log files older than this are deleted on startup; 0 keeps them forever
+    pub log_retention_days: usize,
+    /// Also capture log lines here, for the TUI's log panel
+    pub log_buffer: Option<LogBuffer>,
+    /// Console logs go to stderr, keeping stdout for `--progress-json` lines
+    pub console_stderr: bool,
 }
 
 impl Default for LoggerConfig {
@@ -21,6 +222,11 @@ impl Default for LoggerConfig {
             enable_json_format: false,
             log_dir: None,
             tui_mode: false,
+            ansi: true,
+            filter: None,
+            log_retention_days: DEFAULT_LOG_RETENTION_DAYS,
+            log_buffer: None,
+            console_stderr: false,
         }
     }
 }
@@ -55,6 +261,49 @@ impl LoggerConfig {
         self
     }
 
+    pub fn with_ansi(mut self, enable: bool) -> Self {
+        self.ansi = enable;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: Option<String>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_log_retention_days(mut self, days: usize) -> Self {
+        self.log_retention_days = days;
+        self
+    }
+
+    pub fn with_log_buffer(mut self, buffer: Option<LogBuffer>) -> Self {
+        self.log_buffer = buffer;
+        self
+    }
+
+    pub fn with_console_stderr(mut self, enable: bool) -> Self {
+        self.console_stderr = enable;
+        self
+    }
+
+    fn console_writer(&self) -> BoxMakeWriter {
+        if self.console_stderr {
+            BoxMakeWriter::new(std::io::stderr)
+        } else {
+            BoxMakeWriter::new(std::io::stdout)
+        }
+    }
+
+    /// Filter precedence: `--log-filter`, then `RUST_LOG`, then the verbosity level
+    pub fn env_filter(&self) -> Result<EnvFilter> {
+        if let Some(ref spec) = self.filter {
+            return EnvFilter::try_new(spec)
+                .map_err(|e| anyhow::anyhow!("Invalid --log-filter \"{}\": {}", spec, e));
+        }
+
+        Ok(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(self.level.as_str())))
+    }
+
     pub fn get_log_dir(&self) -> PathBuf {
         if let Some(ref dir) = self.log_dir {
             return dir.clone();
@@ -71,8 +320,7 @@ impl LoggerConfig {
 }
 
 pub fn init_logger(config: LoggerConfig) -> Result<Option<WorkerGuard>> {
-    let env_filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new(config.level.as_str()));
+    let env_filter = config.env_filter()?;
 
     let mut guard = None;
 
@@ -81,7 +329,7 @@ pub fn init_logger(config: LoggerConfig) -> Result<Option<WorkerGuard>> {
         let log_dir = config.get_log_dir();
         std::fs::create_dir_all(&log_dir)?;
 
-        let file_appender = tracing_appender::rolling::daily(&log_dir, "ytdl");
+        let file_appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_PREFIX);
         let (non_blocking, worker_guard) = tracing_appender::non_blocking(file_appender);
         guard = Some(worker_guard);
 
@@ -91,13 +339,20 @@ pub fn init_logger(config: LoggerConfig) -> Result<Option<WorkerGuard>> {
             .with_line_number(true)
             .with_file(true)
             .with_ansi(false)
-            .with_filter(env_filter)
+            .with_filter(env_filter.clone())
             .boxed();
 
+        let buffer_layer = config
+            .log_buffer
+            .clone()
+            .map(|buffer| buffer_layer(buffer, env_filter));
+
         tracing_subscriber::registry()
             .with(file_layer)
+            .with(buffer_layer)
             .init();
 
+        apply_log_retention(&log_dir, config.log_retention_days);
         return Ok(guard);
     }
 
@@ -106,20 +361,21 @@ pub fn init_logger(config: LoggerConfig) -> Result<Option<WorkerGuard>> {
         let log_dir = config.get_log_dir();
         std::fs::create_dir_all(&log_dir)?;
 
-        let file_appender = tracing_appender::rolling::daily(&log_dir, "ytdl");
+        let file_appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_PREFIX);
         let (non_blocking, worker_guard) = tracing_appender::non_blocking(file_appender);
         guard = Some(worker_guard);
 
         let console_layer = if config.enable_json_format {
             fmt::layer()
                 .json()
-                .with_writer(std::io::stdout)
+                .with_writer(config.console_writer())
                 .with_filter(env_filter.clone())
                 .boxed()
         } else {
             fmt::layer()
                 .pretty()
-                .with_writer(std::io::stdout)
+                .with_writer(config.console_writer())
+                .with_ansi(config.ansi)
                 .with_thread_ids(true)
                 .with_line_number(true)
                 .with_file(true)
@@ -148,18 +404,21 @@ pub fn init_logger(config: LoggerConfig) -> Result<Option<WorkerGuard>> {
             .with(console_layer)
             .with(file_layer)
             .init();
+
+        apply_log_retention(&log_dir, config.log_retention_days);
     } else {
         // Console-only logging (CLI mode)
         let console_layer = if config.enable_json_format {
             fmt::layer()
                 .json()
-                .with_writer(std::io::stdout)
+                .with_writer(config.console_writer())
                 .with_filter(env_filter)
                 .boxed()
         } else {
             fmt::layer()
                 .pretty()
-                .with_writer(std::io::stdout)
+                .with_writer(config.console_writer())
+                .with_ansi(config.ansi)
                 .with_thread_ids(true)
                 .with_line_number(true)
                 .with_file(true)
@@ -173,6 +432,57 @@ pub fn init_logger(config: LoggerConfig) -> Result<Option<WorkerGuard>> {
     Ok(guard)
 }
 
+/// Compact, uncolored layer that writes each event into `buffer`
+fn buffer_layer<S>(buffer: LogBuffer, filter: EnvFilter) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fmt::layer()
+        .compact()
+        .with_writer(buffer)
+        .with_ansi(false)
+        .with_target(false)
+        .with_filter(filter)
+        .boxed()
+}
+
+/// Delete daily log files (`ytdl.YYYY-MM-DD`) in `dir` dated more than `days` days ago.
+/// Returns the number of files removed.
+pub fn prune_old_logs(dir: &Path, days: usize) -> Result<usize> {
+    let cutoff = Local::now().date_naive() - chrono::Duration::days(days as i64);
+    let mut removed = 0;
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(date) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(LOG_FILE_PREFIX)?.strip_prefix('.'))
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        else {
+            continue;
+        };
+
+        if date < cutoff && std::fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+fn apply_log_retention(dir: &Path, days: usize) {
+    if days == 0 {
+        return;
+    }
+
+    match prune_old_logs(dir, days) {
+        Ok(0) => {}
+        Ok(removed) => debug!("Removed {} log files older than {} days", removed, days),
+        Err(e) => warn!("Failed to prune old logs in {:?}: {}", dir, e),
+    }
+}
+
 pub fn level_from_verbosity(verbosity: u8) -> Level {
     match verbosity {
         0 => Level::INFO,
@@ -201,4 +511,86 @@ mod tests {
         assert!(!config.enable_file_logging);
         assert!(!config.enable_json_format);
     }
+
+    #[test]
+    fn test_custom_log_filter() {
+        let config = LoggerConfig::new()
+            .with_filter(Some("ytdl::infra::downloader=debug,info".to_string()));
+        let filter = config.env_filter().unwrap();
+        assert_eq!(filter.to_string(), "ytdl::infra::downloader=debug,info");
+
+        let config = LoggerConfig::new().with_filter(Some("ytdl=loud".to_string()));
+        assert!(config.env_filter().is_err());
+    }
+
+    #[test]
+    fn test_log_buffer_captures_events() {
+        let buffer = LogBuffer::new(2);
+        let subscriber = tracing_subscriber::registry()
+            .with(buffer_layer(buffer.clone(), EnvFilter::new("info")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("first");
+            tracing::debug!("filtered out");
+            tracing::warn!("second");
+            tracing::error!("third");
+        });
+
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("WARN") && lines[0].ends_with("second"));
+        assert!(lines[1].contains("ERROR") && lines[1].ends_with("third"));
+    }
+
+    #[test]
+    fn test_event_logger_appends_ndjson() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.ndjson");
+        let url = "https://youtube.com/watch?v=dQw4w9WgXcQ";
+
+        let events = EventLogger::new(path.clone());
+        events.started(url);
+        events.progress(url, 12.5);
+        events.progress(url, 13.0);
+        events.completed(url, Path::new("/tmp/video.mp4"));
+        events.failed(url, "boom");
+        events.skipped(url, "File is larger than max-filesize");
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let kinds: Vec<&str> = lines.iter().map(|e| e["event"].as_str().unwrap()).collect();
+        assert_eq!(kinds, ["started", "progress", "completed", "failed", "skipped"]);
+
+        assert!(lines.iter().all(|e| e["url"] == url && e["timestamp"].is_string()));
+        assert_eq!(lines[1]["details"]["percent"], 12.5);
+        assert_eq!(lines[2]["details"]["path"], "/tmp/video.mp4");
+        assert_eq!(lines[3]["details"]["error"], "boom");
+        assert_eq!(lines[4]["details"]["reason"], "File is larger than max-filesize");
+    }
+
+    #[test]
+    fn test_prune_old_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let today = Local::now().date_naive();
+        let dated = |days_ago: i64| {
+            let date = today - chrono::Duration::days(days_ago);
+            dir.path().join(format!("ytdl.{}", date.format("%Y-%m-%d")))
+        };
+
+        for days_ago in [0, 3, 7, 8, 30] {
+            std::fs::write(dated(days_ago), "log").unwrap();
+        }
+        std::fs::write(dir.path().join("notes.txt"), "keep").unwrap();
+
+        assert_eq!(prune_old_logs(dir.path(), 7).unwrap(), 2);
+        for days_ago in [0, 3, 7] {
+            assert!(dated(days_ago).exists());
+        }
+        assert!(!dated(8).exists());
+        assert!(!dated(30).exists());
+        assert!(dir.path().join("notes.txt").exists());
+    }
 }
@@ -0,0 +1,78 @@
+use crate::shared::format_bytes;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Copy a downloaded file into each mirror directory.
+///
+/// A failed copy is logged and skipped so it never fails the primary download.
+/// Returns the total number of bytes copied across all mirrors.
+pub async fn mirror_file(file: &Path, mirror_dirs: &[PathBuf]) -> u64 {
+    let file_name = match file.file_name() {
+        Some(name) => name,
+        None => {
+            warn!("Cannot mirror {:?}: path has no file name", file);
+            return 0;
+        }
+    };
+
+    let mut total_copied = 0;
+
+    for dir in mirror_dirs {
+        if let Err(e) = tokio::fs::create_dir_all(dir).await {
+            warn!("Failed to create mirror directory {:?}: {}", dir, e);
+            continue;
+        }
+
+        let target = dir.join(file_name);
+        match tokio::fs::copy(file, &target).await {
+            Ok(bytes) => {
+                info!("Mirrored {:?} to {:?} ({})", file, target, format_bytes(bytes));
+                total_copied += bytes;
+            }
+            Err(e) => {
+                warn!("Failed to mirror {:?} to {:?}: {}", file, target, e);
+            }
+        }
+    }
+
+    total_copied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mirror_file() {
+        let src_dir = tempfile::tempdir().unwrap();
+        let mirror_dir = tempfile::tempdir().unwrap();
+        let file = src_dir.path().join("video.mp4");
+        std::fs::write(&file, b"hello").unwrap();
+
+        let mirrors = vec![
+            mirror_dir.path().join("a"),
+            mirror_dir.path().join("b"),
+        ];
+        let copied = mirror_file(&file, &mirrors).await;
+
+        assert_eq!(copied, 10);
+        assert!(mirrors[0].join("video.mp4").exists());
+        assert!(mirrors[1].join("video.mp4").exists());
+    }
+
+    #[tokio::test]
+    async fn test_mirror_failure_is_not_fatal() {
+        let src_dir = tempfile::tempdir().unwrap();
+        let file = src_dir.path().join("video.mp4");
+        std::fs::write(&file, b"hello").unwrap();
+
+        // A regular file can't be used as a mirror directory
+        let blocker = src_dir.path().join("blocker");
+        std::fs::write(&blocker, b"").unwrap();
+        let good = src_dir.path().join("good");
+
+        let copied = mirror_file(&file, &[blocker, good.clone()]).await;
+        assert_eq!(copied, 5);
+        assert!(good.join("video.mp4").exists());
+    }
+}
@@ -4,11 +4,25 @@
 //! and dependencies (yt-dlp, clipboard, file system, logging, etc.)
 
 pub mod clipboard;
+pub mod command;
+pub mod comments;
 pub mod downloader;
+pub mod limiter;
 pub mod logger;
+pub mod mirror;
 pub mod notifications;
+pub mod rename;
+pub mod sponsorblock;
+pub mod webhook;
 
 pub use clipboard::{get_clipboard_url, ClipboardWatcher};
-pub use downloader::{DownloadProgressInfo, Downloader, Format, VideoMetadata};
-pub use logger::{init_logger, level_from_verbosity, LoggerConfig};
-pub use notifications::{are_notifications_available, notify_download_complete, notify_download_error};
+pub use comments::export_comments;
+pub use downloader::{DownloadProgressInfo, Downloader, Format, SortKey, VideoMetadata};
+pub use limiter::init_download_limit;
+pub use logger::{init_logger, level_from_verbosity, LogBuffer, LoggerConfig};
+pub use mirror::mirror_file;
+pub use rename::sanitize_downloaded_file;
+pub use sponsorblock::{export_sponsor_segments, item_segments_path};
+pub use notifications::{
+    are_notifications_available, notify_download_complete, notify_download_error, play_completion_sound,
+};
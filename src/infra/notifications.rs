@@ -1,6 +1,9 @@
 use crate::shared::constants::*;
 use notify_rust::{Notification, Timeout};
-use tracing::{error, info};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tracing::{debug, error, info, warn};
 
 /// Send a desktop notification for successful download
 pub fn notify_download_complete(title: &str, file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
@@ -69,6 +72,61 @@ pub fn notify_download_error(title: &str, error: &str) -> Result<(), Box<dyn std
     Ok(())
 }
 
+/// Program and arguments used to play an audio file on this platform
+pub fn sound_player_command(path: &Path) -> (&'static str, Vec<String>) {
+    let path = path.display().to_string();
+
+    if cfg!(target_os = "macos") {
+        ("afplay", vec![path])
+    } else if cfg!(target_os = "windows") {
+        (
+            "powershell",
+            vec![
+                "-NoProfile".to_string(),
+                "-Command".to_string(),
+                format!("(New-Object Media.SoundPlayer '{}').PlaySync()", path.replace('\'', "''")),
+            ],
+        )
+    } else {
+        ("paplay", vec![path])
+    }
+}
+
+/// Play `sound` in the background, or ring the terminal bell if no sound is set and `bell` is on.
+///
+/// Playback failures are only logged; they never fail the download.
+pub fn play_completion_sound(sound: Option<&Path>, bell: bool) {
+    match sound {
+        Some(path) => {
+            let (program, args) = sound_player_command(path);
+            debug!("Playing completion sound with {}: {:?}", program, args);
+
+            // Spawn here so the player starts even if we exit right away; reap it off-thread
+            match Command::new(program)
+                .args(&args)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+            {
+                Ok(mut child) => {
+                    std::thread::spawn(move || match child.wait() {
+                        Ok(status) if !status.success() => warn!("{} exited with {}", program, status),
+                        Ok(_) => {}
+                        Err(e) => warn!("Failed to wait for {}: {}", program, e),
+                    });
+                }
+                Err(e) => warn!("Failed to play completion sound with {}: {}", program, e),
+            }
+        }
+        None if bell => {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+        }
+        None => {}
+    }
+}
+
 /// Check if notifications are available on this platform
 pub fn are_notifications_available() -> bool {
     #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
@@ -81,3 +139,24 @@ pub fn are_notifications_available() -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sound_player_command() {
+        let (program, args) = sound_player_command(Path::new("/tmp/done.wav"));
+
+        if cfg!(target_os = "macos") {
+            assert_eq!(program, "afplay");
+            assert_eq!(args, vec!["/tmp/done.wav"]);
+        } else if cfg!(target_os = "windows") {
+            assert_eq!(program, "powershell");
+            assert!(args.last().unwrap().contains("Media.SoundPlayer"));
+        } else {
+            assert_eq!(program, "paplay");
+            assert_eq!(args, vec!["/tmp/done.wav"]);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::shared::{utils::sanitize_filename, Result};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Rename a downloaded file to a sanitized name in the same directory.
+///
+/// If the sanitized name is taken, a numeric suffix is appended (`name_1.ext`).
+/// Returns the new path, or the original path if no rename was needed.
+pub async fn sanitize_downloaded_file(path: &Path, ascii_only: bool) -> Result<PathBuf> {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    let ext = path.extension().and_then(|e| e.to_str());
+
+    let clean_stem = sanitize_filename(stem, ascii_only);
+    if clean_stem == stem {
+        debug!("Filename already clean: {:?}", path);
+        return Ok(path.to_path_buf());
+    }
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let target = unique_path(dir, &clean_stem, ext);
+
+    tokio::fs::rename(path, &target).await?;
+    info!("Renamed {:?} to {:?}", path, target);
+
+    Ok(target)
+}
+
+fn unique_path(dir: &Path, stem: &str, ext: Option<&str>) -> PathBuf {
+    let file_name = |suffix: Option<usize>| {
+        let stem = match suffix {
+            Some(n) => format!("{}_{}", stem, n),
+            None => stem.to_string(),
+        };
+        match ext {
+            Some(ext) => format!("{}.{}", stem, ext),
+            None => stem,
+        }
+    };
+
+    let mut candidate = dir.join(file_name(None));
+    let mut n = 1;
+    while candidate.exists() {
+        candidate = dir.join(file_name(Some(n)));
+        n += 1;
+    }
+
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sanitize_downloaded_file_with_collision() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("Song: Live? 🎵.mp3");
+        std::fs::write(&original, b"audio").unwrap();
+        std::fs::write(dir.path().join("Song_ Live_.mp3"), b"taken").unwrap();
+
+        let renamed = sanitize_downloaded_file(&original, true).await.unwrap();
+
+        assert_eq!(renamed, dir.path().join("Song_ Live__1.mp3"));
+        assert!(renamed.exists());
+        assert!(!original.exists());
+    }
+
+    #[tokio::test]
+    async fn test_clean_name_is_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("clean name.mp4");
+        std::fs::write(&original, b"video").unwrap();
+
+        let renamed = sanitize_downloaded_file(&original, false).await.unwrap();
+        assert_eq!(renamed, original);
+    }
+}
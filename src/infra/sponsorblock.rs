@@ -0,0 +1,157 @@
+use crate::shared::{Result, YtdlError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// A SponsorBlock segment as reported in yt-dlp's `sponsorblock_chapters`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SponsorSegment {
+    pub category: String,
+    pub start: f64,
+    pub end: f64,
+    pub title: Option<String>,
+}
+
+/// Path of the `.info.json` yt-dlp writes next to a downloaded file
+pub fn info_json_path(media: &Path) -> PathBuf {
+    let stem = media
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    media.with_file_name(format!("{}.info.json", stem))
+}
+
+/// Per-video export file for batch and playlist items: `segments.json` and
+/// `Title.mp4` give `Title.segments.json` next to `segments.json`
+pub fn item_segments_path(dest: &Path, media: &Path) -> PathBuf {
+    let stem = media
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = dest
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "segments.json".to_string());
+    dest.with_file_name(format!("{}.{}", stem, name))
+}
+
+/// Extract the sponsor segments from a yt-dlp info json
+pub fn parse_sponsor_segments(info: &serde_json::Value) -> Vec<SponsorSegment> {
+    info["sponsorblock_chapters"]
+        .as_array()
+        .map(|chapters| {
+            chapters
+                .iter()
+                .filter_map(|c| {
+                    Some(SponsorSegment {
+                        category: c["category"].as_str()?.to_string(),
+                        start: c["start_time"].as_f64()?,
+                        end: c["end_time"].as_f64()?,
+                        title: c["title"].as_str().map(|s| s.to_string()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Write segments as CSV when `dest` ends in `.csv`, otherwise as JSON
+pub fn write_segments(segments: &[SponsorSegment], dest: &Path) -> Result<()> {
+    let is_csv = dest
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("csv"))
+        .unwrap_or(false);
+
+    let content = if is_csv {
+        let mut csv = String::from("category,start,end,title\n");
+        for segment in segments {
+            csv.push_str(&format!(
+                "{},{:.3},{:.3},\"{}\"\n",
+                segment.category,
+                segment.start,
+                segment.end,
+                segment.title.as_deref().unwrap_or("").replace('"', "\"\"")
+            ));
+        }
+        csv
+    } else {
+        serde_json::to_string_pretty(segments)?
+    };
+
+    std::fs::write(dest, content)?;
+    Ok(())
+}
+
+/// Read the info json next to `media`, write its sponsor segments to `dest`
+/// and remove the info json. Returns the number of segments exported.
+pub async fn export_sponsor_segments(media: &Path, dest: &Path) -> Result<usize> {
+    let info_path = info_json_path(media);
+    let content = tokio::fs::read_to_string(&info_path).await.map_err(|e| {
+        YtdlError::Other(format!("Failed to read {:?}: {}", info_path, e))
+    })?;
+
+    let info: serde_json::Value = serde_json::from_str(&content)?;
+    let segments = parse_sponsor_segments(&info);
+    write_segments(&segments, dest)?;
+    info!("Exported {} sponsor segments to {:?}", segments.len(), dest);
+
+    if let Err(e) = tokio::fs::remove_file(&info_path).await {
+        debug!("Failed to remove {:?}: {}", info_path, e);
+    }
+
+    Ok(segments.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_write_segments() {
+        let info = serde_json::json!({
+            "title": "Video",
+            "sponsorblock_chapters": [
+                {"start_time": 10.0, "end_time": 42.5, "category": "sponsor", "title": "Sponsor", "type": "skip"},
+                {"start_time": 300.25, "end_time": 310.0, "category": "outro", "type": "skip"},
+                {"category": "broken"}
+            ]
+        });
+
+        let segments = parse_sponsor_segments(&info);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].category, "sponsor");
+        assert_eq!(segments[1].start, 300.25);
+        assert_eq!(segments[1].title, None);
+        assert!(parse_sponsor_segments(&serde_json::json!({})).is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let csv_path = dir.path().join("segments.csv");
+        write_segments(&segments, &csv_path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&csv_path).unwrap(),
+            "category,start,end,title\nsponsor,10.000,42.500,\"Sponsor\"\noutro,300.250,310.000,\"\"\n"
+        );
+
+        let json_path = dir.path().join("segments.json");
+        write_segments(&segments, &json_path).unwrap();
+        let parsed: Vec<SponsorSegment> =
+            serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+        assert_eq!(parsed, segments);
+    }
+
+    #[test]
+    fn test_info_json_path() {
+        assert_eq!(
+            info_json_path(Path::new("/tmp/My Video.mp4")),
+            PathBuf::from("/tmp/My Video.info.json")
+        );
+    }
+
+    #[test]
+    fn test_item_segments_path() {
+        assert_eq!(
+            item_segments_path(Path::new("out/segments.csv"), Path::new("/tmp/My Video.mp4")),
+            PathBuf::from("out/My Video.segments.csv")
+        );
+    }
+}
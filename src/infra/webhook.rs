@@ -0,0 +1,179 @@
+use crate::infra::downloader::title_from_path;
+use crate::shared::{constants::*, Result, YtdlError};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookStatus {
+    Started,
+    Progress,
+    Completed,
+    Failed,
+    Skipped,
+}
+
+/// JSON body posted to the webhook for each download event
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub url: String,
+    pub title: Option<String>,
+    pub status: WebhookStatus,
+    pub path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl WebhookPayload {
+    pub fn new(url: &str, status: WebhookStatus) -> Self {
+        Self {
+            url: url.to_string(),
+            title: None,
+            status,
+            path: None,
+            percent: None,
+            error: None,
+            reason: None,
+        }
+    }
+
+    /// Payload for a finished download: `Completed` with the file, `Skipped` with
+    /// yt-dlp's reason, or `Failed` with the error
+    pub fn outcome(url: &str, result: &Result<PathBuf>) -> Self {
+        match result {
+            Ok(path) => Self {
+                title: title_from_path(path),
+                path: Some(path.clone()),
+                ..Self::new(url, WebhookStatus::Completed)
+            },
+            Err(YtdlError::Skipped(reason)) => Self {
+                reason: Some(reason.clone()),
+                ..Self::new(url, WebhookStatus::Skipped)
+            },
+            Err(e) => Self {
+                error: Some(e.to_string()),
+                ..Self::new(url, WebhookStatus::Failed)
+            },
+        }
+    }
+}
+
+/// Posts download status to a configured endpoint (`webhook_url`).
+/// Failures are logged and never affect the download.
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    endpoint: String,
+    client: reqwest::Client,
+    last_progress: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Webhook {
+    pub fn new(endpoint: String) -> Self {
+        Self {
+            endpoint,
+            client: reqwest::Client::new(),
+            last_progress: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// POST `payload` and wait for the response, giving up after `WEBHOOK_TIMEOUT_SECS`
+    pub async fn post(&self, payload: &WebhookPayload) {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .json(payload)
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => {
+                debug!("Webhook accepted {:?} for {}", payload.status, payload.url);
+            }
+            Ok(response) => warn!("Webhook {} returned {}", self.endpoint, response.status()),
+            Err(e) => warn!("Webhook {} failed: {}", self.endpoint, e),
+        }
+    }
+
+    /// POST `payload` in the background without waiting
+    pub fn send(&self, payload: WebhookPayload) {
+        let webhook = self.clone();
+        tokio::spawn(async move { webhook.post(&payload).await });
+    }
+
+    pub fn started(&self, url: &str) {
+        self.send(WebhookPayload::new(url, WebhookStatus::Started));
+    }
+
+    /// Send a progress update, at most one per `WEBHOOK_PROGRESS_INTERVAL_MS`
+    pub fn progress(&self, url: &str, percent: f64) {
+        {
+            let mut last = self.last_progress.lock().unwrap_or_else(|e| e.into_inner());
+            let interval = Duration::from_millis(WEBHOOK_PROGRESS_INTERVAL_MS);
+            if last.is_some_and(|sent| sent.elapsed() < interval) {
+                return;
+            }
+            *last = Some(Instant::now());
+        }
+
+        self.send(WebhookPayload {
+            percent: Some(percent),
+            ..WebhookPayload::new(url, WebhookStatus::Progress)
+        });
+    }
+
+    /// Report the final outcome. Awaited so the event isn't lost when the process exits.
+    pub async fn finished(&self, url: &str, result: &Result<PathBuf>) {
+        self.post(&WebhookPayload::outcome(url, result)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_serialization() {
+        let url = "https://youtube.com/watch?v=dQw4w9WgXcQ";
+
+        let completed = WebhookPayload::outcome(url, &Ok(PathBuf::from("/tmp/My Video.mp4")));
+        assert_eq!(
+            serde_json::to_value(&completed).unwrap(),
+            serde_json::json!({
+                "url": url,
+                "title": "My Video",
+                "status": "completed",
+                "path": "/tmp/My Video.mp4",
+            })
+        );
+
+        let failed = WebhookPayload::outcome(url, &Err(YtdlError::Other("boom".to_string())));
+        let json = serde_json::to_value(&failed).unwrap();
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["path"], serde_json::Value::Null);
+        assert!(json["error"].as_str().unwrap().contains("boom"));
+
+        let reason = "Rick Astley does not pass filter (view_count>=1000000000), skipping ..";
+        let skipped = WebhookPayload::outcome(url, &Err(YtdlError::Skipped(reason.to_string())));
+        let json = serde_json::to_value(&skipped).unwrap();
+        assert_eq!(json["status"], "skipped");
+        assert_eq!(json["reason"], reason);
+        assert!(json.get("error").is_none());
+
+        let progress = WebhookPayload {
+            percent: Some(42.5),
+            ..WebhookPayload::new(url, WebhookStatus::Progress)
+        };
+        let json = serde_json::to_value(&progress).unwrap();
+        assert_eq!(json["status"], "progress");
+        assert_eq!(json["percent"], 42.5);
+        assert!(json.get("error").is_none());
+    }
+}
@@ -5,16 +5,26 @@ mod shared;
 mod tui;
 
 use crate::cli::{
-    download_single_url, handle_clear_history_command, handle_config_command,
-    handle_history_command, handle_playlist_download, show_video_info, CliConfig, Cli, Commands,
-    Config,
+    download_single_url, download_subtitles_only, handle_archive_command, handle_bench_command, handle_cache_clear_command,
+    handle_clear_history_command, handle_list_command, handle_config_command, handle_dedupe_history_command, handle_find_dupes_command,
+    handle_history_command, handle_merge_history_command, handle_playlist_download,
+    handle_remove_history_command, handle_restore_history_command, handle_usage_command,
+    handle_theme_preview_command, handle_validate_command, handle_verify_history_command, print_video_field,
+    show_video_info, CliConfig,
+    CacheAction, Cli, Commands, Config, parse_section,
 };
-use crate::core::{BatchDownloader, History, PlaylistDownloader};
-use crate::infra::{get_clipboard_url, init_logger, level_from_verbosity, ClipboardWatcher,
-    LoggerConfig};
-use crate::shared::{constants::*, check_ffmpeg, check_ytdlp, validate_youtube_url, Result};
+use crate::core::{
+    batch_state_path, BatchDownloadItem, BatchDownloadStats, BatchDownloader, BatchFormat,
+    DownloadStatus, History, PauseHandle, PlaylistDownloader, BATCH_STATE_FILE,
+};
+use crate::infra::{get_clipboard_url, init_download_limit, init_logger, level_from_verbosity,
+    ClipboardWatcher, LogBuffer, LoggerConfig, SortKey};
+use crate::shared::{constants::*, check_ffmpeg, check_ytdlp, format_bytes, parse_ytdlp_args, validate_audio_quality, validate_duration_limits, validate_filesize_limits, validate_match_filter, validate_sleep_intervals, validate_view_limits, validate_youtube_url, Result};
+use crate::shared::utils::format_duration;
 use clap::Parser;
 use colored::Colorize;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::path::PathBuf;
 use std::process;
 use tracing::{error, info, warn};
 
@@ -22,6 +32,9 @@ use tracing::{error, info, warn};
 async fn main() {
     if let Err(e) = run().await {
         eprintln!("{} {}", "Error:".red().bold(), e);
+        for line in e.help() {
+            eprintln!("  {}", line.dimmed());
+        }
         process::exit(1);
     }
 }
@@ -29,6 +42,14 @@ async fn main() {
 async fn run() -> Result<()> {
     let cli = Cli::parse();
 
+    // Honor --no-color and the NO_COLOR convention (https://no-color.org)
+    let no_color = cli.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    if no_color {
+        colored::control::set_override(false);
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+
     // Handle --examples flag
     if cli.examples {
         print_examples();
@@ -42,11 +63,19 @@ async fn run() -> Result<()> {
         level_from_verbosity(cli.verbose)
     };
 
+    // The TUI shows this session's log lines in its log panel
+    let log_buffer = cli.interactive.then(LogBuffer::default);
+
     let logger_config = LoggerConfig::new()
         .with_level(log_level)
         .with_file_logging(cli.log_file)
         .with_json_format(cli.log_json)
-        .with_tui_mode(cli.interactive);
+        .with_tui_mode(cli.interactive)
+        .with_ansi(!no_color)
+        .with_filter(cli.log_filter.clone())
+        .with_log_retention_days(cli.log_retention_days)
+        .with_log_buffer(log_buffer.clone())
+        .with_console_stderr(cli.progress_json);
 
     let _guard = init_logger(logger_config).expect("Failed to initialize logger");
 
@@ -63,12 +92,15 @@ async fn run() -> Result<()> {
         return Err(shared::YtdlError::Config(e));
     }
 
-    // Check for yt-dlp
-    match check_ytdlp() {
-        Ok(version) => info!("Found yt-dlp: {}", version),
-        Err(e) => {
-            error!("{}", e);
-            return Err(e);
+    // Check for yt-dlp; `validate` only parses a batch file, so it also runs in CI without it,
+    // and `--simulate-slow` never runs it
+    if !matches!(cli.command, Some(Commands::Validate { .. })) && !cli.simulate_slow {
+        match check_ytdlp() {
+            Ok(version) => info!("Found yt-dlp: {}", version),
+            Err(e) => {
+                error!("{}", e);
+                return Err(e);
+            }
         }
     }
 
@@ -88,6 +120,24 @@ async fn run() -> Result<()> {
         Config::load_with_env_overrides()
     };
 
+    if let Some(filter) = &cli.match_filter {
+        validate_match_filter(filter)?;
+    }
+
+    if let Some(quality) = &cli.audio_quality {
+        validate_audio_quality(quality)?;
+    }
+
+    validate_duration_limits(cli.min_duration, cli.max_duration)?;
+    validate_view_limits(cli.min_views, cli.max_views)?;
+    validate_filesize_limits(cli.min_filesize, cli.max_filesize)?;
+    validate_sleep_intervals(cli.sleep_interval, cli.max_sleep_interval, cli.sleep_requests)?;
+
+    let extra_ytdlp_args = match &cli.ytdlp_args {
+        Some(args) => parse_ytdlp_args(args)?,
+        None => Vec::new(),
+    };
+
     // Merge CLI options into config
     let cli_config = CliConfig {
         output: cli.output.clone(),
@@ -95,6 +145,62 @@ async fn run() -> Result<()> {
         audio_only: cli.audio_only,
         enable_file_logging: cli.log_file,
         enable_json_logging: cli.log_json,
+        mirror_dirs: cli.mirror.clone(),
+        sanitize_names: cli.sanitize_names,
+        ascii_names: cli.ascii_names,
+        match_filter: cli.match_filter.clone(),
+        age_limit: cli.age_limit,
+        min_duration: cli.min_duration,
+        max_duration: cli.max_duration,
+        min_views: cli.min_views,
+        max_views: cli.max_views,
+        min_filesize: cli.min_filesize,
+        max_filesize: cli.max_filesize,
+        term_progress: cli.term_progress,
+        extra_ytdlp_args,
+        cleanup_partials: cli.cleanup_partials,
+        quality_subdirs: cli.quality_subdirs,
+        download_sections: cli.section.as_deref().map(parse_section).transpose().map_err(shared::YtdlError::Config)?,
+        prefer_free_formats: cli.prefer_free_formats,
+        id_filename: cli.id_filename,
+        write_description: cli.write_description,
+        write_comments: cli.write_comments,
+        embed_chapters: cli.embed_chapters,
+        parse_metadata: cli
+            .parse_metadata
+            .clone()
+            .or_else(|| cli.parse_metadata_title.then(|| TITLE_METADATA_PATTERN.to_string())),
+        recode_video: cli.recode_video.clone(),
+        sub_langs: cli.sub_langs.clone(),
+        events_file: cli.events_file.clone(),
+        cache_dir: cli.cache_dir.clone(),
+        temp_dir: cli.temp_dir.clone(),
+        sleep_interval: cli.sleep_interval,
+        max_sleep_interval: cli.max_sleep_interval,
+        sleep_requests: cli.sleep_requests,
+        cookies_file: cli.cookies_file.clone(),
+        cookies_from_browser: cli.cookies_from_browser.clone(),
+        skip_metadata: cli.no_metadata,
+        hash_downloads: cli.hash,
+        player_client_fallbacks: cli.player_client_fallbacks.clone(),
+        bell: cli.bell,
+        index_prefix: cli.index_prefix,
+        write_playlist_metafiles: cli.write_playlist_metafiles,
+        no_part: cli.no_part,
+        force_download: cli.force_download,
+        no_overwrites: cli.no_overwrites,
+        no_title: cli.no_title,
+        progress_json: cli.progress_json,
+        audio_quality: cli.audio_quality.clone(),
+        audio_copy: cli.audio_copy,
+        sponsorblock_export: cli.sponsorblock_export.clone(),
+        print_template: cli.print_template.clone(),
+        simulate_slow: cli.simulate_slow,
+        stop_on_error: match (cli.stop_on_error, cli.ignore_errors) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        },
     };
     config.merge_with_cli(cli_config);
 
@@ -102,6 +208,10 @@ async fn run() -> Result<()> {
         config.concurrent_downloads = Some(cli.concurrent);
     }
 
+    // Shared by batch, playlist and watch mode so yt-dlp processes are bounded globally
+    init_download_limit(config.concurrent_downloads.unwrap_or(DEFAULT_CONCURRENT_DOWNLOADS));
+
+    config.validate_chapter_options()?;
     config.log_config();
 
     let mut history = History::load().unwrap_or_else(|e| {
@@ -114,6 +224,9 @@ async fn run() -> Result<()> {
         return handle_subcommand(command, &config, &mut history).await;
     }
 
+    // Per-download summary, left out of quiet and machine-readable output
+    let show_summary = !cli.quiet && !cli.log_json && !config.progress_json.unwrap_or(false);
+
     // Handle watch mode
     if cli.watch {
         info!("Starting clipboard watch mode");
@@ -128,7 +241,7 @@ async fn run() -> Result<()> {
                 let mut history_clone = history.clone();
 
                 tokio::spawn(async move {
-                    if let Err(e) = download_single_url(&url, &config_clone, &mut history_clone, false).await {
+                    if let Err(e) = download_single_url(&url, &config_clone, &mut history_clone, false, show_summary).await {
                         error!("Download failed: {}", e);
                         eprintln!("{} {}", "Error:".red().bold(), e);
                     }
@@ -137,6 +250,26 @@ async fn run() -> Result<()> {
             .await;
     }
 
+    if config.audio_only && config.recode_video.is_some() {
+        warn!("--recode-video is ignored with --audio-only; audio is converted with --audio-format instead");
+    }
+
+    // Cutting sections and recoding are done by FFmpeg, so it's required rather than recommended
+    if config.requires_ffmpeg() {
+        let version = check_ffmpeg()?;
+        info!("Found ffmpeg: {}", version);
+    }
+
+    if config.embed_chapters.unwrap_or(false) {
+        match check_ffmpeg() {
+            Ok(version) => info!("Found ffmpeg: {}", version),
+            Err(e) => {
+                warn!("{}", e);
+                warn!("Chapters can't be embedded without FFmpeg");
+            }
+        }
+    }
+
     // Check for FFmpeg if audio conversion is needed
     if config.audio_only {
         match check_ffmpeg() {
@@ -151,29 +284,78 @@ async fn run() -> Result<()> {
     // Handle interactive mode
     if cli.interactive {
         info!("Starting interactive TUI mode");
-        return tui::run_tui().await;
+        let ascii = cli.ascii || !tui::terminal_supports_unicode();
+        return tui::run_tui(ascii, log_buffer.unwrap_or_default(), cli.simulate_slow).await;
     }
 
-    // Handle batch download
-    if let Some(batch_file) = cli.batch {
-        info!("Starting batch download from: {:?}", batch_file);
+    // Handle resumed batch download
+    if let Some(state_file) = cli.resume_batch {
+        info!("Resuming batch download from: {:?}", state_file);
+
+        let failures_file = cli
+            .write_failures
+            .clone()
+            .unwrap_or_else(|| config.download_dir().join(FAILED_URLS_FILE));
+        let mut batch_downloader = BatchDownloader::new(config.clone(), history.clone(), config.stop_on_error.unwrap_or(false))
+            .with_state_file(state_file.clone())
+            .with_failures_file(failures_file)
+            .with_max_consecutive_failures(cli.max_consecutive_failures.map(|n| n as usize));
+        batch_downloader.load_state(&state_file).await?;
+
+        // URLs finished by another run since the state was saved
+        if config.skip_duplicates.unwrap_or(true) {
+            batch_downloader.skip_duplicates().await;
+        }
+
+        println!("\n{}", "Resuming batch download...".green().bold());
+        let stats = download_batch_with_progress(&batch_downloader).await?;
+        print_batch_stats(&stats);
+        config.play_completion_sound();
 
-        let mut batch_downloader = BatchDownloader::new(config.clone(), history.clone(), cli.stop_on_error);
-        batch_downloader.load_from_file(&batch_file).await?;
+        return Ok(());
+    }
+
+    // Handle batch download from a file or from the links on a web page
+    if cli.batch.is_some() || cli.batch_from_url.is_some() {
+        let state_file = match cli.batch {
+            Some(ref batch_file) => batch_state_path(batch_file),
+            None => PathBuf::from(BATCH_STATE_FILE),
+        };
+        let failures_file = cli
+            .write_failures
+            .clone()
+            .unwrap_or_else(|| config.download_dir().join(FAILED_URLS_FILE));
+        let mut batch_downloader = BatchDownloader::new(config.clone(), history.clone(), config.stop_on_error.unwrap_or(false))
+            .with_state_file(state_file.clone())
+            .with_failures_file(failures_file)
+            .with_max_consecutive_failures(cli.max_consecutive_failures.map(|n| n as usize));
+
+        if let Some(ref batch_file) = cli.batch {
+            info!("Starting batch download from: {:?}", batch_file);
+            let batch_format = cli.batch_format.as_deref().and_then(BatchFormat::parse);
+            batch_downloader.load_from_file(batch_file, batch_format).await?;
+        } else if let Some(ref page_url) = cli.batch_from_url {
+            println!("\n{}", "Fetching page...".green().bold());
+            let count = batch_downloader.load_from_url(page_url).await?;
+            println!("Found {} YouTube links on {}", count, page_url);
+        }
 
         if config.skip_duplicates.unwrap_or(true) {
             batch_downloader.skip_duplicates().await;
         }
 
-        println!("\n{}", "Starting batch download...".green().bold());
-        let stats = batch_downloader.download_all().await?;
+        if cli.dry_run {
+            println!("\n{}", "Fetching metadata for the batch...".green().bold());
+            batch_downloader.prefetch_metadata().await;
+            print_batch_plan(&batch_downloader.get_items().await);
+            return Ok(());
+        }
 
-        println!("\n{}", "Batch Download Complete!".green().bold());
-        println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
-        println!("Total: {}", stats.total);
-        println!("{} Successful: {}", "✓".green(), stats.successful);
-        println!("{} Failed: {}", "✗".red(), stats.failed);
-        println!("{} Skipped: {}", "⊘".yellow(), stats.skipped);
+        println!("\n{}", "Starting batch download...".green().bold());
+        println!("Progress is saved to {:?}; resume with --resume-batch", state_file);
+        let stats = download_batch_with_progress(&batch_downloader).await?;
+        print_batch_stats(&stats);
+        config.play_completion_sound();
 
         return Ok(());
     }
@@ -200,17 +382,39 @@ async fn run() -> Result<()> {
     if let Some(url) = url {
         validate_youtube_url(&url)?;
 
+        // A bare field name (no `%(...)` template) prints that field without downloading
+        if let Some(field) = cli.print_template.as_deref().filter(|t| !t.contains('%')) {
+            return print_video_field(&url, field, &config).await;
+        }
+
         // Handle --info flag: show video information without downloading
         if cli.info {
-            return show_video_info(&url, &config).await;
+            let sort_key = SortKey::parse(&cli.sort_by).unwrap_or(SortKey::Resolution);
+            return show_video_info(&url, &config, sort_key, cli.full_description).await;
+        }
+
+        if cli.subs_only {
+            let langs = config.sub_langs.clone().ok_or_else(|| {
+                shared::YtdlError::Config("--subs-only needs a language: --sub-langs en (or sub_langs in the config file)".to_string())
+            })?;
+            return download_subtitles_only(&url, &config, &mut history, &langs).await;
         }
 
         if cli.playlist || PlaylistDownloader::is_playlist_url(&url) {
             info!("Detected playlist URL");
-            return handle_playlist_download(&url, &config, cli.range.as_deref(), cli.folder.as_deref()).await;
+            return handle_playlist_download(
+                &url,
+                &config,
+                cli.range.as_deref(),
+                cli.folder.as_deref(),
+                cli.write_playlist_metafile.as_deref(),
+                cli.write_m3u.as_ref().map(Option::as_deref),
+                config.stop_on_error.unwrap_or(false),
+            )
+            .await;
         }
 
-        return download_single_url(&url, &config, &mut history, cli.resume).await;
+        return download_single_url(&url, &config, &mut history, cli.resume, show_summary).await;
     } else {
         Err(shared::YtdlError::Config(
             "No URL provided. Use --help for usage information.".to_string(),
@@ -224,14 +428,48 @@ async fn run() -> Result<()> {
 
 // Moved to commands::playlist module
 
-async fn handle_subcommand(command: Commands, _config: &Config, history: &mut History) -> Result<()> {
+async fn handle_subcommand(command: Commands, config: &Config, history: &mut History) -> Result<()> {
     match command {
         Commands::Config => handle_config_command().await,
-        Commands::History { limit, search, export } => {
+        Commands::History {
+            limit, search, export, restore, remove, verify, prune_missing, find_dupes, usage, month, merge,
+        } => {
+            if let Some(path) = merge {
+                return handle_merge_history_command(history, &path).await;
+            }
+            if restore {
+                return handle_restore_history_command(history).await;
+            }
+            if verify {
+                return handle_verify_history_command(history, prune_missing).await;
+            }
+            if find_dupes {
+                return handle_find_dupes_command(history).await;
+            }
+            if usage {
+                return handle_usage_command(history, month).await;
+            }
+            if let Some(url) = remove {
+                return handle_remove_history_command(history, &url).await;
+            }
             handle_history_command(history, limit, search, export).await
         }
-        Commands::ClearHistory { older_than } => {
-            handle_clear_history_command(history, older_than).await
+        Commands::ClearHistory { older_than, max_size, yes } => {
+            handle_clear_history_command(history, older_than, max_size, yes).await
+        }
+        Commands::DedupeHistory => handle_dedupe_history_command(history).await,
+        Commands::Archive { url, output } => {
+            handle_archive_command(&url, output.as_deref(), config).await
+        }
+        Commands::List { url, export } => handle_list_command(&url, export.as_deref(), config).await,
+        Commands::Cache { action: CacheAction::Clear } => handle_cache_clear_command(config).await,
+        Commands::Bench { url, trials, fragments, concurrent } => {
+            validate_youtube_url(&url)?;
+            handle_bench_command(&url, trials, &fragments, &concurrent, config).await
+        }
+        Commands::ThemePreview { theme } => handle_theme_preview_command(theme.as_deref()).await,
+        Commands::Validate { file, format } => {
+            handle_validate_command(&file, format.as_deref().and_then(BatchFormat::parse), history).await
         }
         Commands::Completions { shell: _ } => {
             warn!("Shell completions not yet implemented");
@@ -242,6 +480,139 @@ async fn handle_subcommand(command: Commands, _config: &Config, history: &mut Hi
     }
 }
 
+/// Toggle `pause` on every SIGUSR1, so a long batch can be paused from another terminal
+#[cfg(unix)]
+fn spawn_pause_toggle(pause: PauseHandle) -> tokio::task::JoinHandle<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    tokio::spawn(async move {
+        let mut usr1 = match signal(SignalKind::user_defined1()) {
+            Ok(usr1) => usr1,
+            Err(e) => {
+                warn!("Failed to listen for SIGUSR1, batch pausing is unavailable: {}", e);
+                return;
+            }
+        };
+        while usr1.recv().await.is_some() {
+            if pause.is_paused() {
+                pause.resume();
+            } else {
+                pause.pause();
+            }
+        }
+    })
+}
+
+/// Run the batch while drawing an overall progress bar with ETA
+async fn download_batch_with_progress(batch_downloader: &BatchDownloader) -> Result<BatchDownloadStats> {
+    let pause = batch_downloader.pause_handle();
+    #[cfg(unix)]
+    let pause_toggle = {
+        println!("Pause or resume with: kill -USR1 {}", process::id());
+        spawn_pause_toggle(pause.clone())
+    };
+
+    let total = batch_downloader.get_progress().await.total;
+    let pb = ProgressBar::new(total as u64);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} Batch [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+
+    let download = batch_downloader.download_all();
+    tokio::pin!(download);
+    let mut ticker = tokio::time::interval(std::time::Duration::from_millis(500));
+
+    let result = loop {
+        tokio::select! {
+            result = &mut download => break result,
+            _ = ticker.tick() => {
+                let progress = batch_downloader.get_progress().await;
+                pb.set_position((progress.complete + progress.failed + progress.skipped) as u64);
+                if pause.is_paused() {
+                    pb.set_message("Paused, running downloads finish first");
+                    continue;
+                }
+                let eta = match progress.eta_secs() {
+                    Some(eta) => format!("ETA {}", format_duration(eta)),
+                    None => "ETA estimating...".to_string(),
+                };
+                pb.set_message(format!(
+                    "{} · {} of {} downloading · {} saved",
+                    eta,
+                    progress.downloading,
+                    progress.concurrency,
+                    format_bytes(progress.total_bytes)
+                ));
+            }
+        }
+    };
+
+    #[cfg(unix)]
+    pause_toggle.abort();
+    pb.finish_and_clear();
+    result
+}
+
+fn print_batch_stats(stats: &BatchDownloadStats) {
+    println!("\n{}", "Batch Download Complete!".green().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    println!("Total: {}", stats.total);
+    println!("{} Successful: {}", "✓".green(), stats.successful);
+    println!("{} Failed: {}", "✗".red(), stats.failed);
+    println!("{} Skipped: {}", "⊘".yellow(), stats.skipped);
+
+    if let Some(limit) = stats.halted_after_failures {
+        println!("\n{} Stopped after {} failures in a row", "⚠".yellow(), limit);
+        println!("Check yt-dlp (yt-dlp -U) and your connection, then continue with --resume-batch");
+    }
+
+    if let Some(ref path) = stats.failures_file {
+        println!(
+            "\n{} failed URLs written to {:?}; retry them with --batch {:?}",
+            stats.failed, path, path
+        );
+    }
+}
+
+/// `--dry-run` output: what a batch would download, skip, or fail on
+fn print_batch_plan(items: &[BatchDownloadItem]) {
+    println!("\n{}", "Batch Download Plan:".green().bold());
+    println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+
+    let (mut ready, mut unavailable, mut skipped, mut filtered) = (0, 0, 0, 0);
+    for (index, item) in items.iter().enumerate() {
+        let title = item.title.as_deref().unwrap_or(&item.url);
+        match item.status {
+            DownloadStatus::Failed(ref error) => {
+                unavailable += 1;
+                println!("{:>3}. {} {}", index + 1, "✗".red(), item.url);
+                println!("     {}", error.dimmed());
+            }
+            DownloadStatus::Skipped => {
+                skipped += 1;
+                println!("{:>3}. {} {} (already downloaded)", index + 1, "⊘".yellow(), title);
+            }
+            DownloadStatus::Filtered => {
+                filtered += 1;
+                println!("{:>3}. {} {} (filtered by limits)", index + 1, "⊘".yellow(), title);
+            }
+            _ => {
+                ready += 1;
+                println!("{:>3}. {} {}", index + 1, "✓".green(), title);
+            }
+        }
+    }
+
+    println!("{}", SUBSEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
+    println!(
+        "{} to download, {} unavailable, {} already downloaded, {} filtered",
+        ready, unavailable, skipped, filtered
+    );
+}
+
 fn print_examples() {
     println!("{}", "Common Usage Examples:".green().bold());
     println!("{}", SEPARATOR_LINE.repeat(SEPARATOR_WIDTH));
@@ -257,6 +628,9 @@ fn print_examples() {
     println!("  {} ytdl -q 720 \"https://youtube.com/watch?v=VIDEO_ID\"", "$".yellow());
     println!("    Download video in 720p quality");
     println!();
+    println!("  {} ytdl -a --section 0:30-0:45 \"https://youtube.com/watch?v=VIDEO_ID\"", "$".yellow());
+    println!("    Save 15 seconds of audio, e.g. for a ringtone (requires FFmpeg)");
+    println!();
 
     println!("{}", "Interactive Mode:".cyan().bold());
     println!("  {} ytdl -i", "$".yellow());
@@ -278,6 +652,15 @@ fn print_examples() {
     println!("  {} ytdl -b urls.txt --concurrent 5", "$".yellow());
     println!("    Download with 5 concurrent downloads");
     println!();
+    println!("  {} ytdl --resume-batch .batch-state.json", "$".yellow());
+    println!("    Continue an interrupted batch where it left off");
+    println!();
+    println!("  {} ytdl -b urls.txt --sleep-interval 5 --max-sleep-interval 30", "$".yellow());
+    println!("    Pause 5-30s between downloads to avoid rate-limiting on large batches");
+    println!();
+    println!("  {} ytdl -b urls.csv", "$".yellow());
+    println!("    Batch file with per-URL columns: url,quality,audio_only,output");
+    println!();
 
     println!("{}", "Clipboard:".cyan().bold());
     println!("  {} ytdl --clipboard", "$".yellow());
@@ -297,6 +680,9 @@ fn print_examples() {
     println!("  {} ytdl history --export history.csv", "$".yellow());
     println!("    Export history to CSV file");
     println!();
+    println!("  {} ytdl history --restore", "$".yellow());
+    println!("    Undo the last clear-history from its backup");
+    println!();
 
     println!("{}", "Configuration:".cyan().bold());
     println!("  {} ytdl config", "$".yellow());
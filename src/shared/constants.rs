@@ -25,14 +25,52 @@ pub const BYTES_PER_SI_GB: f64 = 1_000_000_000.0;
 // Notification Constants
 pub const NOTIFICATION_TIMEOUT_MS: u32 = 5000;
 
+// Webhook Constants
+pub const WEBHOOK_TIMEOUT_SECS: u64 = 5;
+pub const WEBHOOK_PROGRESS_INTERVAL_MS: u64 = 5000;
+
+// --events-file
+pub const EVENTS_PROGRESS_INTERVAL_MS: u64 = 1000;
+
+// --batch-from-url page fetch
+pub const PAGE_FETCH_TIMEOUT_SECS: u64 = 30;
+
+// --simulate-slow: each fake download reports this size and takes STEPS * STEP_MS
+pub const SIMULATE_STEPS: u64 = 20;
+pub const SIMULATE_STEP_MS: u64 = 250;
+pub const SIMULATE_SIZE_MIB: u64 = 10;
+
 // Default Configuration Values
+/// yt-dlp YouTube player clients to retry with, in order, after an HTTP 403
+pub const DEFAULT_PLAYER_CLIENT_FALLBACKS: [&str; 1] = ["android"];
 pub const DEFAULT_CONCURRENT_DOWNLOADS: usize = 3;
 pub const MAX_CONCURRENT_DOWNLOADS: usize = 10;
 pub const MIN_CONCURRENT_DOWNLOADS: usize = 1;
 
+/// `--parse-metadata` pattern for "Artist - Song" titles (`--parse-metadata-title`)
+pub const TITLE_METADATA_PATTERN: &str = "title:%(artist)s - %(title)s";
+
+// Logging Constants
+pub const DEFAULT_LOG_RETENTION_DAYS: usize = 14;
+pub const LOG_FILE_PREFIX: &str = "ytdl";
+pub const LOG_PANEL_CAPACITY: usize = 500;
+
+// Channel Archive Constants
+/// yt-dlp `--download-archive` file kept in each `ytdl archive` channel folder
+pub const DOWNLOAD_ARCHIVE_FILE: &str = "download-archive.txt";
+
+// Batch Constants
+/// Default list of a batch's failed URLs, written to the download directory
+pub const FAILED_URLS_FILE: &str = "failed.txt";
+
 // History Constants
 pub const DEFAULT_HISTORY_LIMIT: usize = 10;
 
+// TUI Layout Constants
+/// Below this size the screens' fixed-height layouts no longer fit
+pub const MIN_TUI_WIDTH: u16 = 40;
+pub const MIN_TUI_HEIGHT: u16 = 12;
+
 // TUI Animation Constants
 pub const ANIMATION_FRAME_DELAY_MS: u64 = 100;
 pub const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
@@ -1,3 +1,4 @@
+use crate::shared::utils::format_bytes;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -5,16 +6,20 @@ pub enum YtdlError {
     #[error("Invalid YouTube URL: {0}")]
     InvalidUrl(String),
 
-    #[error("yt-dlp not found in PATH. Please install yt-dlp:\n  macOS: brew install yt-dlp\n  Linux: pip install yt-dlp\n  Windows: winget install yt-dlp")]
+    #[error("yt-dlp not found in PATH")]
     YtdlpNotFound,
 
     #[error("yt-dlp execution failed: {0}")]
     YtdlpFailed(String),
 
-    #[error("FFmpeg not found in PATH. Please install FFmpeg:\n  macOS: brew install ffmpeg\n  Linux: apt install ffmpeg\n  Windows: winget install ffmpeg")]
+    #[error("FFmpeg not found in PATH")]
     FfmpegNotFound,
 
-    #[error("Insufficient disk space. Required: {required} GB, Available: {available} GB")]
+    #[error(
+        "Insufficient disk space. Required: {}, Available: {}",
+        format_bytes(*required),
+        format_bytes(*available)
+    )]
     InsufficientDiskSpace { required: u64, available: u64 },
 
     #[error("Network error: {0}")]
@@ -35,11 +40,144 @@ pub enum YtdlError {
     #[error("Video format not available: {0}")]
     FormatNotAvailable(String),
 
+    #[error("No downloadable formats found for {0} — this may be members-only or geo-blocked")]
+    NoFormats(String),
+
+    #[error("No {0} subtitles found for this video")]
+    NoSubtitles(String),
+
+    #[error("yt-dlp could not extract this video, it may be outdated: {0}")]
+    ExtractorOutdated(String),
+
+    #[error("YouTube asked to sign in to confirm you're not a bot: {0}")]
+    BotCheck(String),
+
+    #[error("YouTube refused the download (HTTP 403): {0}")]
+    Forbidden(String),
+
     #[error("Download interrupted by user")]
     Interrupted,
 
+    #[error("Skipped by yt-dlp: {0}")]
+    Skipped(String),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl YtdlError {
+    /// Recovery suggestions shown under the error message by the CLI and TUI
+    pub fn help(&self) -> Vec<String> {
+        match self {
+            YtdlError::InvalidUrl(_) => vec![
+                "Check the URL for typos or missing characters".to_string(),
+                "Copy the link directly from the browser address bar".to_string(),
+            ],
+            YtdlError::YtdlpNotFound => vec![
+                "Install yt-dlp:".to_string(),
+                "  macOS: brew install yt-dlp".to_string(),
+                "  Linux: pip install yt-dlp".to_string(),
+                "  Windows: winget install yt-dlp".to_string(),
+            ],
+            YtdlError::YtdlpFailed(_) | YtdlError::JsonParse(_) | YtdlError::ProgressParse(_) => vec![
+                "Update yt-dlp: yt-dlp -U".to_string(),
+                "Verify the video is still available".to_string(),
+                "Re-run with -vv to see the yt-dlp output".to_string(),
+            ],
+            YtdlError::FfmpegNotFound => vec![
+                "Install FFmpeg:".to_string(),
+                "  macOS: brew install ffmpeg".to_string(),
+                "  Linux: apt install ffmpeg".to_string(),
+                "  Windows: winget install ffmpeg".to_string(),
+            ],
+            YtdlError::InsufficientDiskSpace { .. } => vec![
+                "Free up disk space or choose another directory with --output".to_string(),
+                "Try a lower quality with --quality".to_string(),
+            ],
+            YtdlError::Network(_) => vec![
+                "Check your internet connection".to_string(),
+                "Retry the download; partial files are resumed".to_string(),
+            ],
+            YtdlError::Io(_) => vec![
+                "Check that the output directory exists and is writable".to_string(),
+            ],
+            YtdlError::Config(_) => {
+                let mut lines = vec!["Check the command-line options and your config file".to_string()];
+                if let Some(path) = crate::cli::config::Config::get_default_config_path() {
+                    lines.push(format!("Config file: {}", path.display()));
+                }
+                lines.push("Run `ytdl config` to see the effective settings".to_string());
+                lines
+            }
+            YtdlError::FormatNotAvailable(_) => vec![
+                "Run with --info to list the available formats".to_string(),
+                "Try a different quality with --quality".to_string(),
+            ],
+            YtdlError::NoFormats(_) => vec![
+                "Members-only and private videos need your cookies: --cookies-from-browser firefox".to_string(),
+                "Geo-blocked videos may work through a proxy: --ytdlp-args \"--proxy <URL>\"".to_string(),
+                "Upcoming live streams can be downloaded once they have started".to_string(),
+            ],
+            YtdlError::NoSubtitles(_) => vec![
+                "List the available languages: yt-dlp --list-subs <URL>".to_string(),
+                "Auto-generated captions aren't included; add them with --ytdlp-args \"--write-auto-subs\"".to_string(),
+            ],
+            YtdlError::ExtractorOutdated(_) => vec![
+                "YouTube changes often break yt-dlp; update it: yt-dlp -U".to_string(),
+                "Retry the download after updating".to_string(),
+                "If it keeps failing, try clearing yt-dlp's cache: ytdl cache clear".to_string(),
+            ],
+            YtdlError::BotCheck(_) => vec![
+                "Pass your browser cookies to yt-dlp:".to_string(),
+                "  ytdl --cookies-from-browser firefox <URL>".to_string(),
+                "Wait a while before retrying, or try from another network".to_string(),
+                "Update yt-dlp: yt-dlp -U".to_string(),
+            ],
+            YtdlError::Forbidden(_) => vec![
+                "Update yt-dlp: yt-dlp -U".to_string(),
+                "Try other player clients: set player_client_fallbacks in the config".to_string(),
+                "  or pass --player-client-fallbacks ios,web".to_string(),
+                "Try clearing yt-dlp's cache: ytdl cache clear".to_string(),
+            ],
+            YtdlError::Interrupted => vec![
+                "Run the same command again to resume the download".to_string(),
+            ],
+            YtdlError::Skipped(_) => vec![
+                "Check the --match-filter, size, duration and view limits in use".to_string(),
+                "Run `ytdl config` to see limits set in the config file".to_string(),
+            ],
+            YtdlError::Other(_) => Vec::new(),
+        }
+    }
+}
+
+impl YtdlError {
+    /// Whether updating yt-dlp is likely to fix this error
+    pub fn suggests_update(&self) -> bool {
+        matches!(self, YtdlError::ExtractorOutdated(_))
+    }
+}
+
 pub type Result<T> = std::result::Result<T, YtdlError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_suggestions() {
+        let help = YtdlError::YtdlpNotFound.help();
+        assert!(help.iter().any(|line| line.contains("pip install yt-dlp")));
+
+        let help = YtdlError::Config("bad value".to_string()).help();
+        assert!(help.iter().any(|line| line.contains("ytdl config")));
+
+        let help = YtdlError::Forbidden("HTTP Error 403".to_string()).help();
+        assert!(help.iter().any(|line| line.contains("ytdl cache clear")));
+
+        let help = YtdlError::NoFormats("abc".to_string()).help();
+        assert!(help.iter().any(|line| line.contains("--cookies-from-browser")));
+
+        assert!(YtdlError::Other("unknown".to_string()).help().is_empty());
+    }
+}
@@ -7,7 +7,7 @@ pub mod error;
 pub mod utils;
 
 pub use error::{Result, YtdlError};
-pub use utils::{check_ffmpeg, check_ytdlp, format_bytes, validate_youtube_url};
+pub use utils::{check_ffmpeg, check_ytdlp, format_bytes, osc8_link, parse_ytdlp_args, validate_audio_quality, validate_duration_limits, validate_filesize_limits, validate_match_filter, validate_sleep_intervals, validate_view_limits, validate_youtube_url};
 
 // Re-export commonly used constants
 pub use constants::{
@@ -1,7 +1,14 @@
+use crate::infra::downloader::VideoMetadata;
+use crate::shared::constants::{BYTES_PER_GB, BYTES_PER_KB, BYTES_PER_MB};
 use crate::shared::{Result, YtdlError};
 use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use tokio::io::AsyncReadExt;
 use tracing::{debug, info};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub fn validate_youtube_url(url: &str) -> Result<()> {
     let youtube_patterns = [
@@ -30,6 +37,142 @@ pub fn validate_youtube_url(url: &str) -> Result<()> {
     )))
 }
 
+/// Reject empty `--match-filter` expressions; the expression itself is evaluated by yt-dlp
+pub fn validate_match_filter(filter: &str) -> Result<()> {
+    if filter.trim().is_empty() {
+        return Err(YtdlError::Config(
+            "--match-filter expression must not be empty".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reject a `--min-duration` above `--max-duration`, which would skip everything
+pub fn validate_duration_limits(min: Option<u64>, max: Option<u64>) -> Result<()> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(YtdlError::Config(format!(
+            "--min-duration ({}s) is greater than --max-duration ({}s)",
+            min, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reject a `--min-views` above `--max-views`, which would skip everything
+pub fn validate_view_limits(min: Option<u64>, max: Option<u64>) -> Result<()> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(YtdlError::Config(format!(
+            "--min-views ({}) is greater than --max-views ({})",
+            min, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reject a `--min-filesize` above `--max-filesize`, which would skip everything
+pub fn validate_filesize_limits(min: Option<u64>, max: Option<u64>) -> Result<()> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => Err(YtdlError::Config(format!(
+            "--min-filesize ({}) is greater than --max-filesize ({})",
+            format_bytes(min),
+            format_bytes(max)
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reject negative sleep times and a `--max-sleep-interval` below `--sleep-interval`
+pub fn validate_sleep_intervals(interval: Option<f64>, max: Option<f64>, requests: Option<f64>) -> Result<()> {
+    for (flag, secs) in [
+        ("--sleep-interval", interval),
+        ("--max-sleep-interval", max),
+        ("--sleep-requests", requests),
+    ] {
+        if secs.is_some_and(|s| !s.is_finite() || s < 0.0) {
+            return Err(YtdlError::Config(format!("{} must be a non-negative number of seconds", flag)));
+        }
+    }
+
+    match (interval, max) {
+        (Some(min), Some(max)) if min > max => Err(YtdlError::Config(format!(
+            "--sleep-interval ({}s) is greater than --max-sleep-interval ({}s)",
+            min, max
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Whether a video lasting `duration` seconds is within `min`..=`max`.
+/// Unknown durations pass, leaving the decision to yt-dlp's match filter.
+pub fn within_duration_limits(duration: Option<u64>, min: Option<u64>, max: Option<u64>) -> bool {
+    let Some(duration) = duration else {
+        return true;
+    };
+    min.is_none_or(|min| duration >= min) && max.is_none_or(|max| duration <= max)
+}
+
+/// Whether a video with `views` views is within `min`..=`max`. Unknown counts
+/// pass, as with durations.
+pub fn within_view_limits(views: Option<u64>, min: Option<u64>, max: Option<u64>) -> bool {
+    let Some(views) = views else {
+        return true;
+    };
+    min.is_none_or(|min| views >= min) && max.is_none_or(|max| views <= max)
+}
+
+/// Whether a file of `size` bytes is within `min`..=`max`. Unknown sizes pass,
+/// leaving the decision to yt-dlp's `--min-filesize`/`--max-filesize`.
+pub fn within_filesize_limits(size: Option<u64>, min: Option<u64>, max: Option<u64>) -> bool {
+    let Some(size) = size else {
+        return true;
+    };
+    min.is_none_or(|min| size >= min) && max.is_none_or(|max| size <= max)
+}
+
+/// yt-dlp `--match-filter` for `filter` plus the `(min, max)` duration and view
+/// count limits, joined with `&`
+pub fn combine_match_filter(
+    filter: Option<&str>,
+    duration: (Option<u64>, Option<u64>),
+    views: (Option<u64>, Option<u64>),
+) -> Option<String> {
+    let clauses: Vec<String> = filter
+        .map(str::to_string)
+        .into_iter()
+        .chain(duration.0.map(|min| format!("duration>={}", min)))
+        .chain(duration.1.map(|max| format!("duration<={}", max)))
+        .chain(views.0.map(|min| format!("view_count>={}", min)))
+        .chain(views.1.map(|max| format!("view_count<={}", max)))
+        .collect();
+
+    (!clauses.is_empty()).then(|| clauses.join(" & "))
+}
+
+/// Check an `--audio-quality` value: a VBR level from 0 (best) to 10 (worst),
+/// or a constant bitrate such as `192K`
+pub fn validate_audio_quality(quality: &str) -> Result<()> {
+    let vbr = quality.parse::<u8>().is_ok_and(|level| level <= 10);
+    let bitrate = quality
+        .strip_suffix(['K', 'k'])
+        .is_some_and(|kbps| kbps.parse::<u32>().is_ok_and(|kbps| kbps > 0));
+
+    if vbr || bitrate {
+        Ok(())
+    } else {
+        Err(YtdlError::Config(format!(
+            "Invalid --audio-quality \"{}\": use 0 (best) to 10 (worst), or a bitrate like 192K",
+            quality
+        )))
+    }
+}
+
+/// Split a `--ytdlp-args` string into arguments using shell quoting rules
+pub fn parse_ytdlp_args(args: &str) -> Result<Vec<String>> {
+    shell_words::split(args)
+        .map_err(|e| YtdlError::Config(format!("Invalid --ytdlp-args: {}", e)))
+}
+
 pub fn check_command_exists(command: &str) -> bool {
     Command::new("which")
         .arg(command)
@@ -75,6 +218,34 @@ pub fn check_ffmpeg() -> Result<String> {
     Ok(version)
 }
 
+/// Parse a size such as `500M`, `2G`, `1.5GiB` or a plain byte count. Units are
+/// binary (1K = 1024 bytes), as in yt-dlp.
+pub fn parse_size(s: &str) -> Result<u64> {
+    let invalid = || {
+        YtdlError::Other(format!(
+            "Invalid size \"{}\". Expected bytes or a number with K, M or G, e.g. 500M",
+            s
+        ))
+    };
+
+    let trimmed = s.trim();
+    let unit_start = trimmed
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(unit_start);
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+
+    let multiplier = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1.0,
+        "K" | "KB" | "KIB" => BYTES_PER_KB,
+        "M" | "MB" | "MIB" => BYTES_PER_MB,
+        "G" | "GB" | "GIB" => BYTES_PER_GB,
+        _ => return Err(invalid()),
+    };
+
+    Ok((number * multiplier).round() as u64)
+}
+
 pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
@@ -88,6 +259,96 @@ pub fn format_bytes(bytes: u64) -> String {
     format!("{:.2} {}", size, UNITS[unit_index])
 }
 
+/// Expand a leading `~` or `~user` and `$VAR`/`${VAR}` references in a path, as a
+/// shell would. Unknown users and unset variables are left as written.
+pub fn expand_path(path: &str) -> PathBuf {
+    let (home, rest) = match path.strip_prefix('~') {
+        Some(rest) => {
+            let end = rest.find(['/', std::path::MAIN_SEPARATOR]).unwrap_or(rest.len());
+            let user = &rest[..end];
+            let home = if user.is_empty() {
+                directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf())
+            } else {
+                user_home(user)
+            };
+            match home {
+                Some(home) => (Some(home), &rest[end..]),
+                None => (None, path),
+            }
+        }
+        None => (None, path),
+    };
+
+    let re = Regex::new(r"\$(\w+)|\$\{(\w+)\}").unwrap();
+    let rest = re.replace_all(rest, |caps: &regex::Captures| {
+        let name = caps.get(1).or_else(|| caps.get(2)).map_or("", |m| m.as_str());
+        std::env::var(name).unwrap_or_else(|_| caps[0].to_string())
+    });
+
+    match home {
+        Some(home) => home.join(rest.trim_start_matches(['/', std::path::MAIN_SEPARATOR])),
+        None => PathBuf::from(rest.as_ref()),
+    }
+}
+
+/// Home directory of `user`, from the system's passwd database
+fn user_home(user: &str) -> Option<PathBuf> {
+    let passwd = std::fs::read_to_string("/etc/passwd").ok()?;
+    home_from_passwd(&passwd, user)
+}
+
+fn home_from_passwd(passwd: &str, user: &str) -> Option<PathBuf> {
+    passwd
+        .lines()
+        .map(|line| line.split(':').collect::<Vec<_>>())
+        .find(|fields| fields.len() >= 7 && fields[0] == user)
+        .map(|fields| PathBuf::from(fields[5]))
+}
+
+/// Free bytes on the filesystem holding `path`. The output directory may not exist
+/// yet, so its nearest existing ancestor is checked. `None` if it can't be determined.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    fs2::available_space(existing).ok()
+}
+
+/// Bytes missing for a download of `estimated` bytes to leave `reserve` bytes free,
+/// or `None` if `available` is enough
+pub fn free_space_shortfall(estimated: u64, available: u64, reserve: u64) -> Option<u64> {
+    let required = estimated.saturating_add(reserve);
+    (required > available).then(|| required - available)
+}
+
+/// Shorten `s` to at most `max` terminal columns, ending in `…` when cut.
+/// Cuts on char boundaries and counts wide (e.g. CJK) characters as two columns.
+pub fn truncate_to_width(s: &str, max: usize) -> String {
+    if s.width() <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let char_width = c.width().unwrap_or(0);
+        // Leave a column for the ellipsis
+        if width + char_width > max - 1 {
+            break;
+        }
+        truncated.push(c);
+        width += char_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Quote a CSV field, doubling any quotes inside it
+pub fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
 pub fn format_duration(seconds: u64) -> String {
     let hours = seconds / 3600;
     let minutes = (seconds % 3600) / 60;
@@ -100,10 +361,192 @@ pub fn format_duration(seconds: u64) -> String {
     }
 }
 
+/// Expand a yt-dlp style output template such as `%(title)s [%(id)s]` against `metadata`.
+///
+/// Supports `%(field)s` / `%(field)d` for id, title, uploader, duration, duration_string,
+/// view_count, comment_count, upload_date, description, thumbnail and webpage_url, plus `%%`.
+/// Unknown or missing fields expand to `NA`, as in yt-dlp.
+pub fn expand_template(template: &str, metadata: &VideoMetadata) -> String {
+    let re = Regex::new(r"%%|%\((\w+)\)[sd]").unwrap();
+
+    re.replace_all(template, |caps: &regex::Captures| {
+        let Some(field) = caps.get(1) else {
+            return "%".to_string();
+        };
+
+        let value = match field.as_str() {
+            "id" => Some(metadata.id.clone()),
+            "title" => Some(metadata.title.clone()),
+            "uploader" => Some(metadata.uploader.clone()),
+            "duration" => metadata.duration.map(|d| d.to_string()),
+            "duration_string" => metadata.duration.map(format_duration),
+            "view_count" => metadata.view_count.map(|v| v.to_string()),
+            "comment_count" => metadata.comment_count.map(|c| c.to_string()),
+            "upload_date" => metadata.upload_date.clone(),
+            "description" => metadata.description.clone(),
+            "thumbnail" => metadata.thumbnail.clone(),
+            "webpage_url" => Some(format!("https://www.youtube.com/watch?v={}", metadata.id)),
+            _ => None,
+        };
+
+        value.unwrap_or_else(|| "NA".to_string())
+    })
+    .into_owned()
+}
+
+/// Hex SHA-256 of a file, read in chunks so multi-GB downloads aren't loaded into memory
+pub async fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Replace characters that are invalid or awkward in filenames.
+///
+/// Path separators, reserved Windows characters and control characters become `_`.
+/// With `ascii_only`, non-ASCII characters (accents, emoji) are dropped too.
+pub fn sanitize_filename(name: &str, ascii_only: bool) -> String {
+    let cleaned: String = name
+        .chars()
+        .filter(|c| !ascii_only || c.is_ascii())
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let cleaned = cleaned.trim_matches(|c| c == '.' || c == ' ').to_string();
+
+    if cleaned.is_empty() {
+        "download".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Render `path` as an OSC 8 `file://` hyperlink when stdout is a terminal.
+///
+/// Falls back to the plain path when color is disabled or output is redirected.
+pub fn osc8_link(path: &Path) -> String {
+    let text = path.display().to_string();
+
+    if !colored::control::SHOULD_COLORIZE.should_colorize() || !std::io::stdout().is_terminal() {
+        return text;
+    }
+
+    let absolute = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    match url::Url::from_file_path(&absolute) {
+        Ok(url) => osc8_escape(url.as_str(), &text),
+        Err(_) => text,
+    }
+}
+
+fn osc8_escape(target: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", target, text)
+}
+
+/// Taskbar progress state reported through OSC 9;4
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TermProgress {
+    Percent(f64),
+    Done,
+    Error,
+}
+
+/// OSC 9;4 escape sequence understood by WezTerm, ConEmu and Windows Terminal
+pub fn osc9_progress(progress: TermProgress) -> String {
+    let (state, value) = match progress {
+        TermProgress::Percent(percent) => (1, percent.clamp(0.0, 100.0).round() as u8),
+        TermProgress::Done => (0, 0),
+        TermProgress::Error => (2, 100),
+    };
+
+    format!("\x1b]9;4;{};{}\x1b\\", state, value)
+}
+
+/// Write an OSC 9;4 progress update to stdout; does nothing when stdout is not a terminal
+pub fn emit_term_progress(progress: TermProgress) {
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return;
+    }
+
+    let _ = write!(stdout, "{}", osc9_progress(progress));
+    let _ = stdout.flush();
+}
+
+/// OSC 0 escape sequence that sets the terminal window/tab title
+pub fn osc_title(title: &str) -> String {
+    format!("\x1b]0;{}\x07", title)
+}
+
+/// Show download progress in the terminal title, or reset it with `None`.
+/// Does nothing when stdout is not a terminal.
+pub fn emit_title_progress(percent: Option<f64>) {
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return;
+    }
+
+    let title = match percent {
+        Some(percent) => format!("ytdl {}%", percent.clamp(0.0, 100.0).round() as u8),
+        None => String::new(),
+    };
+
+    let _ = write!(stdout, "{}", osc_title(&title));
+    let _ = stdout.flush();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_expand_path() {
+        let home = directories::BaseDirs::new().unwrap().home_dir().to_path_buf();
+        assert_eq!(expand_path("~"), home);
+        assert_eq!(expand_path("~/cookies.txt"), home.join("cookies.txt"));
+        assert_eq!(expand_path("/abs/~/file"), PathBuf::from("/abs/~/file"));
+
+        let env_home = std::env::var("HOME").unwrap();
+        assert_eq!(expand_path("$HOME/videos"), PathBuf::from(&env_home).join("videos"));
+        assert_eq!(expand_path("${HOME}/videos"), PathBuf::from(&env_home).join("videos"));
+        assert_eq!(expand_path("$YTDL_SURELY_UNSET_VAR/x"), PathBuf::from("$YTDL_SURELY_UNSET_VAR/x"));
+
+        assert_eq!(expand_path("~ytdl-no-such-user/x"), PathBuf::from("~ytdl-no-such-user/x"));
+        let passwd = "root:x:0:0:root:/root:/bin/bash\nalice:x:1000:1000:Alice:/home/alice:/bin/zsh\n";
+        assert_eq!(home_from_passwd(passwd, "alice"), Some(PathBuf::from("/home/alice")));
+        assert_eq!(home_from_passwd(passwd, "bob"), None);
+    }
+
+    #[test]
+    fn test_free_space_shortfall() {
+        const GB: u64 = 1024 * 1024 * 1024;
+
+        assert_eq!(free_space_shortfall(2 * GB, 10 * GB, 0), None);
+        assert_eq!(free_space_shortfall(2 * GB, 10 * GB, 8 * GB), None);
+        assert_eq!(free_space_shortfall(2 * GB, 10 * GB, 9 * GB), Some(GB));
+        assert_eq!(free_space_shortfall(12 * GB, 10 * GB, 0), Some(2 * GB));
+        assert_eq!(free_space_shortfall(GB, 0, u64::MAX), Some(u64::MAX));
+        assert!(available_space(&std::env::temp_dir().join("not/created/yet")).is_some());
+    }
+
     #[test]
     fn test_validate_youtube_url() {
         assert!(validate_youtube_url("https://youtube.com/watch?v=dQw4w9WgXcQ").is_ok());
@@ -115,6 +558,123 @@ mod tests {
         assert!(validate_youtube_url("not a url").is_err());
     }
 
+    #[test]
+    fn test_osc9_progress() {
+        assert_eq!(osc9_progress(TermProgress::Percent(42.4)), "\x1b]9;4;1;42\x1b\\");
+        assert_eq!(osc9_progress(TermProgress::Percent(150.0)), "\x1b]9;4;1;100\x1b\\");
+        assert_eq!(osc9_progress(TermProgress::Done), "\x1b]9;4;0;0\x1b\\");
+        assert_eq!(osc9_progress(TermProgress::Error), "\x1b]9;4;2;100\x1b\\");
+    }
+
+    #[test]
+    fn test_expand_template() {
+        let metadata = VideoMetadata {
+            id: "dQw4w9WgXcQ".to_string(),
+            title: "Never Gonna Give You Up".to_string(),
+            uploader: "Rick Astley".to_string(),
+            duration: Some(213),
+            view_count: Some(1_500_000_000),
+            comment_count: None,
+            upload_date: Some("20091025".to_string()),
+            description: None,
+            thumbnail: None,
+            formats: Vec::new(),
+            raw_json: None,
+        };
+
+        assert_eq!(
+            expand_template("%(title)s %(id)s", &metadata),
+            "Never Gonna Give You Up dQw4w9WgXcQ"
+        );
+        assert_eq!(
+            expand_template("%(uploader)s|%(duration)d|%(duration_string)s|%(view_count)d|%(upload_date)s", &metadata),
+            "Rick Astley|213|03:33|1500000000|20091025"
+        );
+        assert_eq!(
+            expand_template("%(description)s %(nonexistent)s 100%% %(title)x", &metadata),
+            "NA NA 100% %(title)x"
+        );
+        assert_eq!(
+            expand_template("%(webpage_url)s", &metadata),
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+    }
+
+    #[test]
+    fn test_osc_title() {
+        assert_eq!(osc_title("ytdl 42%"), "\x1b]0;ytdl 42%\x07");
+        assert_eq!(osc_title(""), "\x1b]0;\x07");
+    }
+
+    #[test]
+    fn test_validate_audio_quality() {
+        assert!(validate_audio_quality("0").is_ok());
+        assert!(validate_audio_quality("10").is_ok());
+        assert!(validate_audio_quality("192K").is_ok());
+        assert!(validate_audio_quality("128k").is_ok());
+        assert!(validate_audio_quality("11").is_err());
+        assert!(validate_audio_quality("0K").is_err());
+        assert!(validate_audio_quality("high").is_err());
+        assert!(validate_audio_quality("").is_err());
+    }
+
+    #[test]
+    fn test_parse_ytdlp_args() {
+        assert_eq!(
+            parse_ytdlp_args(r#"--cookies-from-browser firefox --sub-langs "en,de""#).unwrap(),
+            vec!["--cookies-from-browser", "firefox", "--sub-langs", "en,de"]
+        );
+        assert!(parse_ytdlp_args("").unwrap().is_empty());
+        assert!(parse_ytdlp_args("--output 'unterminated").is_err());
+    }
+
+    #[test]
+    fn test_duration_limits() {
+        assert!(within_duration_limits(Some(300), Some(60), Some(600)));
+        assert!(within_duration_limits(Some(60), Some(60), Some(600)));
+        assert!(!within_duration_limits(Some(30), Some(60), None));
+        assert!(!within_duration_limits(Some(3600), None, Some(600)));
+        assert!(within_duration_limits(None, Some(60), Some(600)));
+        assert!(within_duration_limits(Some(3600), None, None));
+
+        assert!(within_view_limits(Some(5_000), Some(1_000), Some(10_000)));
+        assert!(!within_view_limits(Some(500), Some(1_000), None));
+        assert!(!within_view_limits(Some(50_000), None, Some(10_000)));
+        assert!(within_view_limits(Some(1_000), Some(1_000), Some(1_000)));
+        assert!(within_view_limits(None, Some(1_000), Some(10_000)));
+        assert!(validate_view_limits(Some(10), Some(5)).is_err());
+        assert!(validate_view_limits(Some(5), None).is_ok());
+
+        assert_eq!(combine_match_filter(None, (None, None), (None, None)), None);
+        assert_eq!(
+            combine_match_filter(None, (None, Some(600)), (None, None)).as_deref(),
+            Some("duration<=600")
+        );
+        assert_eq!(
+            combine_match_filter(Some("view_count>1000"), (Some(60), Some(600)), (None, None)).as_deref(),
+            Some("view_count>1000 & duration>=60 & duration<=600")
+        );
+        assert_eq!(
+            combine_match_filter(None, (Some(60), None), (Some(1000), Some(1_000_000))).as_deref(),
+            Some("duration>=60 & view_count>=1000 & view_count<=1000000")
+        );
+
+        assert!(validate_duration_limits(Some(60), Some(600)).is_ok());
+        assert!(validate_duration_limits(Some(600), None).is_ok());
+        assert!(validate_duration_limits(Some(600), Some(60)).is_err());
+
+        assert!(validate_sleep_intervals(Some(5.0), Some(30.0), Some(1.0)).is_ok());
+        assert!(validate_sleep_intervals(Some(30.0), Some(5.0), None).is_err());
+        assert!(validate_sleep_intervals(None, None, Some(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_validate_match_filter() {
+        assert!(validate_match_filter("view_count>1000").is_ok());
+        assert!(validate_match_filter("").is_err());
+        assert!(validate_match_filter("   ").is_err());
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0.00 B");
@@ -123,6 +683,61 @@ mod tests {
         assert_eq!(format_bytes(1_073_741_824), "1.00 GB");
     }
 
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1048576").unwrap(), 1_048_576);
+        assert_eq!(parse_size("500M").unwrap(), 500 * 1_048_576);
+        assert_eq!(parse_size("2G").unwrap(), 2 * 1_073_741_824);
+        assert_eq!(parse_size("50k").unwrap(), 51_200);
+        assert_eq!(parse_size("1.5GiB").unwrap(), 1_610_612_736);
+        assert_eq!(parse_size(" 10 MB ").unwrap(), 10_485_760);
+        assert_eq!(parse_size("0").unwrap(), 0);
+
+        for invalid in ["", "M", "-5M", "10X", "1.2.3M", "ten", "5 M B"] {
+            assert!(parse_size(invalid).is_err(), "{:?} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn test_filesize_limits() {
+        assert!(within_filesize_limits(Some(400), Some(100), Some(500)));
+        assert!(!within_filesize_limits(Some(600), None, Some(500)));
+        assert!(!within_filesize_limits(Some(50), Some(100), None));
+        assert!(within_filesize_limits(None, Some(100), Some(500)));
+        assert!(validate_filesize_limits(Some(2048), Some(1024)).is_err());
+        assert!(validate_filesize_limits(Some(1024), Some(2048)).is_ok());
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("Short", 10), "Short");
+        assert_eq!(truncate_to_width("Exactly10!", 10), "Exactly10!");
+        assert_eq!(truncate_to_width("A much longer title", 10), "A much lo…");
+        assert_eq!(truncate_to_width("Café Müller Live", 6), "Café …");
+        // Wide characters take two columns, so fewer fit
+        assert_eq!(truncate_to_width("日本語のタイトル", 7), "日本語…");
+        assert_eq!(truncate_to_width("日本語のタイトル", 6), "日本…");
+        assert_eq!(truncate_to_width("🎵🎵🎵", 4), "🎵…");
+        assert_eq!(truncate_to_width("Title", 1), "…");
+        assert_eq!(truncate_to_width("Title", 0), "");
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("a/b: c?", false), "a_b_ c_");
+        assert_eq!(sanitize_filename("Café 🎵  Mix", false), "Café 🎵 Mix");
+        assert_eq!(sanitize_filename("Café 🎵  Mix", true), "Caf Mix");
+        assert_eq!(sanitize_filename("...", false), "download");
+    }
+
+    #[test]
+    fn test_osc8_escape() {
+        assert_eq!(
+            osc8_escape("file:///tmp/a.mp4", "/tmp/a.mp4"),
+            "\x1b]8;;file:///tmp/a.mp4\x1b\\/tmp/a.mp4\x1b]8;;\x1b\\"
+        );
+    }
+
     #[test]
     fn test_format_duration() {
         assert_eq!(format_duration(0), "00:00");
@@ -1,8 +1,17 @@
-use crate::tui::screens::SettingsState;
+use crate::cli::config::Config;
+use crate::core::playlist::{PlaylistDownloader, PlaylistInfo, PlaylistVideo};
+use crate::tui::screens::{
+    SettingsState, SETTING_AUDIO_FORMAT, SETTING_NOTIFICATIONS, SETTING_SAVE, SETTING_SPINNER_SPEED,
+    SETTING_SPINNER_STYLE,
+};
 use crate::tui::theme::Theme;
 use crate::tui::widgets::{BlinkingCursor, CheckmarkAnimation, PulsingSelection, Spinner, TypingAnimation};
+use crate::infra::downloader::VideoMetadata;
+use crate::infra::logger::LogBuffer;
 use chrono::{DateTime, Local};
 use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::task::AbortHandle;
 
 #[derive(Debug, Clone)]
 pub struct VideoInfo {
@@ -46,6 +55,32 @@ pub struct DownloadHistory {
     pub url: String,
 }
 
+/// Progress of the error screen's "Update yt-dlp" recovery action
+#[derive(Debug, Clone, PartialEq)]
+pub enum YtdlpUpdate {
+    Available,
+    Running,
+    Done(String),
+    Failed(String),
+}
+
+/// What a submitted URL leads to: the format screen or the playlist screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UrlKind {
+    Video,
+    Playlist,
+}
+
+/// Playlist links (including a video opened from a playlist) go to the playlist
+/// screen, as they do on the command line
+pub fn url_kind(url: &str) -> UrlKind {
+    if PlaylistDownloader::is_playlist_url(url) {
+        UrlKind::Playlist
+    } else {
+        UrlKind::Video
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum AppState {
     UrlInput {
@@ -63,6 +98,14 @@ pub enum AppState {
         video_info: VideoInfo,
         formats: Vec<FormatOption>,
         selected_index: usize,
+        /// Fetched metadata, carried into the download so yt-dlp doesn't fetch it again
+        metadata: Arc<VideoMetadata>,
+    },
+    /// Pick which videos of a playlist to download; all are selected at first
+    PlaylistSelection {
+        url: String,
+        playlist: PlaylistInfo,
+        selected_index: usize,
     },
     Downloading {
         url: String,
@@ -79,6 +122,7 @@ pub enum AppState {
         suggestions: Vec<String>,
         last_url: Option<String>,
         retry_count: usize,
+        update: Option<YtdlpUpdate>,
     },
     Help {
         previous_state: Box<AppState>,
@@ -87,6 +131,10 @@ pub enum AppState {
         settings: SettingsState,
         previous_state: Box<AppState>,
     },
+    /// Quit was pressed during a download; the download keeps running underneath
+    ConfirmQuit {
+        previous_state: Box<AppState>,
+    },
 }
 
 pub struct App {
@@ -98,10 +146,26 @@ pub struct App {
     pub checkmark: CheckmarkAnimation,
     pub pulsing_selection: PulsingSelection,
     pub typing_animation: TypingAnimation,
+    /// Log lines captured during this session
+    pub logs: LogBuffer,
+    pub show_logs: bool,
+    /// Lines the log panel is scrolled up from the newest entry
+    pub log_scroll: usize,
+    /// Ctrl+N was pressed during a download and awaits confirmation
+    pub confirm_restart: bool,
+    /// The running fetch or download task, aborted when starting over
+    task: Option<AbortHandle>,
 }
 
+/// Lines moved per PgUp/PgDn in the log panel
+const LOG_SCROLL_STEP: usize = 5;
+
 impl App {
-    pub fn new() -> Self {
+    /// `ascii` swaps the Unicode spinner, checkmark and symbols for ASCII ones
+    pub fn new(config: &Config, ascii: bool) -> Self {
+        let style = config.spinner_style.as_deref().unwrap_or("braille");
+        let style = if ascii && style == "braille" { "line" } else { style };
+
         Self {
             state: AppState::UrlInput {
                 input: String::new(),
@@ -110,16 +174,40 @@ impl App {
                 validation_message: String::from("Paste a YouTube URL or press Ctrl+V"),
                 recent_downloads: vec![],
             },
-            theme: Theme::default(),
+            theme: Theme::default().with_ascii(ascii),
             should_quit: false,
-            spinner: Spinner::new(),
+            spinner: Spinner::from_style(style, config.spinner_speed_ms),
             cursor: BlinkingCursor::new(),
-            checkmark: CheckmarkAnimation::new(),
+            checkmark: if ascii { CheckmarkAnimation::ascii() } else { CheckmarkAnimation::new() },
             pulsing_selection: PulsingSelection::new(),
             typing_animation: TypingAnimation::new("Fetching video information".to_string()),
+            logs: LogBuffer::default(),
+            show_logs: false,
+            log_scroll: 0,
+            confirm_restart: false,
+            task: None,
         }
     }
 
+    pub fn with_log_buffer(mut self, logs: LogBuffer) -> Self {
+        self.logs = logs;
+        self
+    }
+
+    pub fn toggle_log_panel(&mut self) {
+        self.show_logs = !self.show_logs;
+        self.log_scroll = 0;
+    }
+
+    /// Scroll the log panel towards older (`up`) or newer entries
+    pub fn scroll_logs(&mut self, up: bool) {
+        self.log_scroll = if up {
+            (self.log_scroll + LOG_SCROLL_STEP).min(self.logs.lines().len())
+        } else {
+            self.log_scroll.saturating_sub(LOG_SCROLL_STEP)
+        };
+    }
+
     /// Update animations (should be called on tick)
     pub fn tick(&mut self) {
         self.spinner.tick();
@@ -127,12 +215,70 @@ impl App {
         self.checkmark.tick();
         self.pulsing_selection.tick();
         self.typing_animation.tick();
+        if let AppState::Settings { ref mut settings, .. } = self.state {
+            settings.spinner_preview.tick();
+        }
     }
 
     pub fn quit(&mut self) {
+        // Aborting drops the yt-dlp child, which kills it instead of leaving it orphaned
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
         self.should_quit = true;
     }
 
+    /// Quit key: an active download asks for confirmation first, other screens quit at once
+    pub fn request_quit(&mut self) {
+        if matches!(self.state, AppState::Downloading { .. }) {
+            let previous = Box::new(self.state.clone());
+            self.state = AppState::ConfirmQuit {
+                previous_state: previous,
+            };
+        } else {
+            self.quit();
+        }
+    }
+
+    /// Dismiss the quit confirmation and return to the download
+    pub fn cancel_quit(&mut self) {
+        if let AppState::ConfirmQuit { previous_state } = &self.state {
+            self.state = (**previous_state).clone();
+        }
+    }
+
+    /// The download screen, also while it sits under the quit confirmation
+    fn downloading_state_mut(&mut self) -> Option<&mut AppState> {
+        match &mut self.state {
+            AppState::ConfirmQuit { previous_state } => Some(previous_state.as_mut()),
+            state @ AppState::Downloading { .. } => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Remember the background fetch or download so Ctrl+N can cancel it
+    pub fn track_task(&mut self, handle: AbortHandle) {
+        self.task = Some(handle);
+    }
+
+    /// Ctrl+N: start over from URL input. An active download asks for confirmation first.
+    pub fn request_restart(&mut self) {
+        if matches!(self.state, AppState::Downloading { .. }) {
+            self.confirm_restart = true;
+        } else {
+            self.restart();
+        }
+    }
+
+    /// Cancel any running fetch or download and return to URL input
+    pub fn restart(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.confirm_restart = false;
+        self.go_to_url_input();
+    }
+
     pub fn go_to_url_input(&mut self) {
         self.state = AppState::UrlInput {
             input: String::new(),
@@ -147,7 +293,12 @@ impl App {
         let last_url = match &self.state {
             AppState::UrlInput { input, .. } => Some(input.clone()),
             AppState::FetchingInfo { url } => Some(url.clone()),
+            AppState::PlaylistSelection { url, .. } => Some(url.clone()),
             AppState::Downloading { url, .. } => Some(url.clone()),
+            AppState::ConfirmQuit { previous_state } => match previous_state.as_ref() {
+                AppState::Downloading { url, .. } => Some(url.clone()),
+                _ => None,
+            },
             _ => None,
         };
 
@@ -157,9 +308,32 @@ impl App {
             suggestions,
             last_url,
             retry_count: 0,
+            update: None,
         };
     }
 
+    /// Offer the `[U] Update yt-dlp` action on the current error screen
+    pub fn offer_ytdlp_update(&mut self) {
+        self.set_ytdlp_update(YtdlpUpdate::Available);
+    }
+
+    pub fn set_ytdlp_update(&mut self, status: YtdlpUpdate) {
+        if let AppState::Error { ref mut update, .. } = self.state {
+            *update = Some(status);
+        }
+    }
+
+    /// Whether pressing U should start a yt-dlp update now
+    pub fn can_start_ytdlp_update(&self) -> bool {
+        matches!(
+            self.state,
+            AppState::Error {
+                update: Some(YtdlpUpdate::Available | YtdlpUpdate::Failed(_)),
+                ..
+            }
+        )
+    }
+
     pub fn go_to_help(&mut self) {
         let previous = Box::new(self.state.clone());
         self.state = AppState::Help {
@@ -167,10 +341,10 @@ impl App {
         };
     }
 
-    pub fn go_to_settings(&mut self, output_dir: String, quality: String, concurrent: usize) {
+    pub fn go_to_settings(&mut self, settings: SettingsState) {
         let previous = Box::new(self.state.clone());
         self.state = AppState::Settings {
-            settings: SettingsState::new(output_dir, quality, concurrent),
+            settings,
             previous_state: previous,
         };
     }
@@ -189,7 +363,7 @@ impl App {
 
     pub fn select_next_setting(&mut self) {
         if let AppState::Settings { ref mut settings, .. } = self.state {
-            if settings.selected_index < 4 {
+            if settings.selected_index < SETTING_SAVE {
                 settings.selected_index += 1;
             }
         }
@@ -203,10 +377,31 @@ impl App {
         }
     }
 
+    /// Keep the settings screen open and show why saving failed
+    pub fn settings_save_failed(&mut self, error: String) {
+        if let AppState::Settings { ref mut settings, .. } = self.state {
+            settings.save_error = Some(error);
+        }
+    }
+
+    /// Change the selected setting with ←/→ (or Enter for toggles)
+    pub fn adjust_setting(&mut self, forward: bool) {
+        if let AppState::Settings { ref mut settings, .. } = self.state {
+            match settings.selected_index {
+                SETTING_AUDIO_FORMAT => settings.cycle_audio_format(forward),
+                SETTING_NOTIFICATIONS => settings.toggle_notifications(),
+                SETTING_SPINNER_STYLE => settings.cycle_spinner_style(forward),
+                SETTING_SPINNER_SPEED => settings.cycle_spinner_speed(forward),
+                _ => {}
+            }
+        }
+    }
+
     pub fn update_input(&mut self, input: String, cursor_pos: usize) {
         if let AppState::UrlInput { input: ref mut i, cursor_pos: ref mut c, ref mut is_valid, ref mut validation_message, .. } = self.state {
+            // `cursor_pos` counts chars, never bytes, so it can't split a multi-byte char
+            *c = cursor_pos.min(input.chars().count());
             *i = input.clone();
-            *c = cursor_pos;
 
             // Basic URL validation
             if input.is_empty() {
@@ -222,6 +417,72 @@ impl App {
         }
     }
 
+    pub fn insert_text(&mut self, text: &str) {
+        if let AppState::UrlInput { input, cursor_pos, .. } = &self.state {
+            let mut new_input = input.clone();
+            new_input.insert_str(char_to_byte_index(input, *cursor_pos), text);
+            let new_cursor_pos = cursor_pos + text.chars().count();
+            self.update_input(new_input, new_cursor_pos);
+        }
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        if let AppState::UrlInput { input, cursor_pos, .. } = &self.state {
+            let mut new_input = input.clone();
+            new_input.insert(char_to_byte_index(input, *cursor_pos), c);
+            let new_cursor_pos = cursor_pos + 1;
+            self.update_input(new_input, new_cursor_pos);
+        }
+    }
+
+    pub fn delete_char_before_cursor(&mut self) {
+        if let AppState::UrlInput { input, cursor_pos, .. } = &self.state {
+            if *cursor_pos > 0 {
+                let mut new_input = input.clone();
+                new_input.remove(char_to_byte_index(input, cursor_pos - 1));
+                let new_cursor_pos = cursor_pos - 1;
+                self.update_input(new_input, new_cursor_pos);
+            }
+        }
+    }
+
+    pub fn delete_char_at_cursor(&mut self) {
+        if let AppState::UrlInput { input, cursor_pos, .. } = &self.state {
+            if *cursor_pos < input.chars().count() {
+                let mut new_input = input.clone();
+                new_input.remove(char_to_byte_index(input, *cursor_pos));
+                let new_cursor_pos = *cursor_pos;
+                self.update_input(new_input, new_cursor_pos);
+            }
+        }
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        if let AppState::UrlInput { ref mut cursor_pos, .. } = self.state {
+            *cursor_pos = cursor_pos.saturating_sub(1);
+        }
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        if let AppState::UrlInput { ref input, ref mut cursor_pos, .. } = self.state {
+            if *cursor_pos < input.chars().count() {
+                *cursor_pos += 1;
+            }
+        }
+    }
+
+    pub fn move_cursor_home(&mut self) {
+        if let AppState::UrlInput { ref mut cursor_pos, .. } = self.state {
+            *cursor_pos = 0;
+        }
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        if let AppState::UrlInput { ref input, ref mut cursor_pos, .. } = self.state {
+            *cursor_pos = input.chars().count();
+        }
+    }
+
     pub fn start_url_fetch(&mut self) {
         self.typing_animation.reset();
         if let AppState::UrlInput { ref mut validation_message, .. } = self.state {
@@ -233,12 +494,19 @@ impl App {
         self.state = AppState::FetchingInfo { url };
     }
 
-    pub fn show_format_selection(&mut self, url: String, video_info: VideoInfo, formats: Vec<FormatOption>) {
+    pub fn show_format_selection(
+        &mut self,
+        url: String,
+        video_info: VideoInfo,
+        formats: Vec<FormatOption>,
+        metadata: Arc<VideoMetadata>,
+    ) {
         self.state = AppState::FormatSelection {
             url,
             video_info,
             formats,
             selected_index: 0,
+            metadata,
         };
     }
 
@@ -258,27 +526,105 @@ impl App {
         }
     }
 
-    pub fn start_download(&mut self) {
-        if let AppState::FormatSelection { url, video_info, formats, selected_index } = &self.state {
+    pub fn show_playlist_selection(&mut self, url: String, mut playlist: PlaylistInfo) {
+        for video in &mut playlist.videos {
+            video.selected = true;
+        }
+        self.state = AppState::PlaylistSelection {
+            url,
+            playlist,
+            selected_index: 0,
+        };
+    }
+
+    pub fn select_next_video(&mut self) {
+        if let AppState::PlaylistSelection { ref mut selected_index, ref playlist, .. } = self.state {
+            if *selected_index + 1 < playlist.videos.len() {
+                *selected_index += 1;
+            }
+        }
+    }
+
+    pub fn select_previous_video(&mut self) {
+        if let AppState::PlaylistSelection { ref mut selected_index, .. } = self.state {
+            *selected_index = selected_index.saturating_sub(1);
+        }
+    }
+
+    /// Space: include or exclude the highlighted video
+    pub fn toggle_video(&mut self) {
+        if let AppState::PlaylistSelection { ref mut playlist, selected_index, .. } = self.state {
+            if let Some(video) = playlist.videos.get_mut(selected_index) {
+                video.selected = !video.selected;
+            }
+        }
+    }
+
+    /// Select every video, or none when all are already selected
+    pub fn toggle_all_videos(&mut self) {
+        if let AppState::PlaylistSelection { ref mut playlist, .. } = self.state {
+            let select = !playlist.videos.iter().all(|v| v.selected);
+            for video in &mut playlist.videos {
+                video.selected = select;
+            }
+        }
+    }
+
+    /// The playlist title and the videos chosen on the playlist screen
+    pub fn selected_playlist_videos(&self) -> Option<(String, Vec<PlaylistVideo>)> {
+        match &self.state {
+            AppState::PlaylistSelection { playlist, .. } => Some((
+                playlist.title.clone(),
+                playlist.videos.iter().filter(|v| v.selected).cloned().collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Move to the download screen, returning the fetched metadata for the download task
+    pub fn start_download(&mut self) -> Option<Arc<VideoMetadata>> {
+        if let AppState::FormatSelection { url, video_info, formats, selected_index, metadata } = &self.state {
             let format = formats[*selected_index].clone();
-            self.state = AppState::Downloading {
-                url: url.clone(),
-                video_info: video_info.clone(),
-                format,
-                progress: DownloadProgress {
-                    percentage: 0.0,
-                    downloaded_bytes: 0,
-                    total_bytes: 0,
-                    speed: 0.0,
-                    eta: None,
-                    elapsed: 0,
-                },
-            };
+            let metadata = Arc::clone(metadata);
+            self.show_download(url.clone(), video_info.clone(), format);
+            return Some(metadata);
+        }
+
+        None
+    }
+
+    /// Show the download screen for `url` with no progress yet. An open quit
+    /// confirmation stays on top of it.
+    pub fn show_download(&mut self, url: String, video_info: VideoInfo, format: FormatOption) {
+        let state = AppState::Downloading {
+            url,
+            video_info,
+            format,
+            progress: DownloadProgress {
+                percentage: 0.0,
+                downloaded_bytes: 0,
+                total_bytes: 0,
+                speed: 0.0,
+                eta: None,
+                elapsed: 0,
+            },
+        };
+        match &mut self.state {
+            AppState::ConfirmQuit { previous_state } => **previous_state = state,
+            _ => self.state = state,
+        }
+    }
+
+    /// Whether the download screen is (still) showing, also under the quit confirmation
+    pub fn is_downloading(&self) -> bool {
+        match &self.state {
+            AppState::ConfirmQuit { previous_state } => matches!(**previous_state, AppState::Downloading { .. }),
+            state => matches!(state, AppState::Downloading { .. }),
         }
     }
 
     pub fn update_progress(&mut self, progress: DownloadProgress) {
-        if let AppState::Downloading { progress: p, .. } = &mut self.state {
+        if let Some(AppState::Downloading { progress: p, .. }) = self.downloading_state_mut() {
             *p = progress;
         }
     }
@@ -290,6 +636,297 @@ impl App {
 
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(&Config::default(), false)
+    }
+}
+
+/// Convert a cursor position (in chars) into a byte offset within `s`
+fn char_to_byte_index(s: &str, char_idx: usize) -> usize {
+    s.char_indices()
+        .nth(char_idx)
+        .map(|(i, _)| i)
+        .unwrap_or(s.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_state(app: &App) -> (String, usize) {
+        match &app.state {
+            AppState::UrlInput { input, cursor_pos, .. } => (input.clone(), *cursor_pos),
+            _ => panic!("expected UrlInput state"),
+        }
+    }
+
+    #[test]
+    fn test_ascii_app_avoids_unicode_frames() {
+        let app = App::new(&Config::default(), true);
+        assert!(app.theme.ascii);
+        assert_eq!(app.spinner.frame(), "-");
+
+        let config = Config {
+            spinner_style: Some("dots".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(App::new(&config, true).spinner.frame(), ".  ");
+        assert_eq!(App::default().spinner.frame(), "⠋");
+    }
+
+    #[test]
+    fn test_log_panel_scroll_is_clamped() {
+        let logs = LogBuffer::new(10);
+        for i in 0..7 {
+            logs.push(format!("line {}", i));
+        }
+        let mut app = App::default().with_log_buffer(logs);
+        app.toggle_log_panel();
+        assert!(app.show_logs);
+
+        app.scroll_logs(true);
+        app.scroll_logs(true);
+        assert_eq!(app.log_scroll, 7);
+        app.scroll_logs(false);
+        assert_eq!(app.log_scroll, 2);
+
+        app.toggle_log_panel();
+        assert!(!app.show_logs);
+        assert_eq!(app.log_scroll, 0);
+    }
+
+    #[test]
+    fn test_cursor_movement() {
+        let mut app = App::default();
+        app.update_input("https://youtu.be/abc".to_string(), 20);
+
+        app.move_cursor_home();
+        assert_eq!(input_state(&app).1, 0);
+
+        app.move_cursor_left();
+        assert_eq!(input_state(&app).1, 0);
+
+        app.move_cursor_right();
+        app.move_cursor_right();
+        assert_eq!(input_state(&app).1, 2);
+
+        app.move_cursor_end();
+        assert_eq!(input_state(&app).1, 20);
+
+        app.move_cursor_right();
+        assert_eq!(input_state(&app).1, 20);
+    }
+
+    #[test]
+    fn test_edit_mid_string() {
+        let mut app = App::default();
+        app.update_input("https://youtu.be/abc".to_string(), 20);
+
+        app.move_cursor_left();
+        app.delete_char_before_cursor();
+        app.insert_char('X');
+        assert_eq!(input_state(&app), ("https://youtu.be/aXc".to_string(), 19));
+
+        app.delete_char_at_cursor();
+        assert_eq!(input_state(&app), ("https://youtu.be/aX".to_string(), 19));
+    }
+
+    #[test]
+    fn test_edit_url_with_non_ascii() {
+        let mut app = App::default();
+        let url = "https://youtube.com/watch?v=é1";
+        let len = url.chars().count();
+        app.update_input(url.to_string(), len);
+
+        app.move_cursor_left();
+        app.insert_char('ü');
+        assert_eq!(input_state(&app).0, "https://youtube.com/watch?v=éü1");
+
+        app.move_cursor_left();
+        app.delete_char_before_cursor();
+        assert_eq!(input_state(&app), ("https://youtube.com/watch?v=ü1".to_string(), len - 2));
+
+        app.delete_char_at_cursor();
+        assert_eq!(input_state(&app).0, "https://youtube.com/watch?v=1");
+    }
+
+    #[test]
+    fn test_paste_and_backspace_multibyte() {
+        let mut app = App::default();
+        app.insert_text("https://youtu.be/日本");
+        assert_eq!(input_state(&app).1, 19);
+
+        app.delete_char_before_cursor();
+        assert_eq!(input_state(&app), ("https://youtu.be/日".to_string(), 18));
+
+        app.move_cursor_left();
+        app.insert_text("🎵x");
+        assert_eq!(input_state(&app), ("https://youtu.be/🎵x日".to_string(), 19));
+
+        app.delete_char_before_cursor();
+        app.delete_char_before_cursor();
+        assert_eq!(input_state(&app), ("https://youtu.be/日".to_string(), 17));
+    }
+
+    #[test]
+    fn test_update_input_clamps_cursor() {
+        let mut app = App::default();
+        app.update_input("é".to_string(), 2);
+        assert_eq!(input_state(&app).1, 1);
+
+        app.delete_char_before_cursor();
+        assert_eq!(input_state(&app), (String::new(), 0));
+    }
+
+    #[test]
+    fn test_url_kind() {
+        assert_eq!(url_kind("https://youtu.be/dQw4w9WgXcQ"), UrlKind::Video);
+        assert_eq!(url_kind("https://www.youtube.com/watch?v=dQw4w9WgXcQ"), UrlKind::Video);
+        assert_eq!(url_kind("https://youtube.com/shorts/dQw4w9WgXcQ"), UrlKind::Video);
+        assert_eq!(
+            url_kind("https://www.youtube.com/playlist?list=PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf"),
+            UrlKind::Playlist
+        );
+        assert_eq!(
+            url_kind("https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLrAXtmErZgOeiKm4sgNOknGvNjby9efdf"),
+            UrlKind::Playlist
+        );
+    }
+
+    #[test]
+    fn test_settings_save_failure_stays_on_screen() {
+        let mut app = App::new(&Config::default(), false);
+        app.go_to_settings(SettingsState::new("./downloads".to_string(), "best".to_string(), 3));
+        app.settings_save_failed("Permission denied".to_string());
+
+        match &app.state {
+            AppState::Settings { settings, .. } => {
+                assert_eq!(settings.save_error.as_deref(), Some("Permission denied"));
+            }
+            _ => panic!("expected Settings state"),
+        }
+    }
+
+    #[test]
+    fn test_playlist_selection() {
+        let video = |index: usize| PlaylistVideo {
+            url: format!("https://www.youtube.com/watch?v=video{}", index),
+            title: format!("Video {}", index),
+            duration: None,
+            view_count: None,
+            index,
+            selected: false,
+        };
+        let playlist = PlaylistInfo {
+            id: "PL1".to_string(),
+            title: "Mix".to_string(),
+            uploader: None,
+            video_count: 3,
+            videos: vec![video(1), video(2), video(3)],
+        };
+
+        let mut app = App::default();
+        app.show_playlist_selection("https://www.youtube.com/playlist?list=PL1".to_string(), playlist);
+        assert_eq!(app.selected_playlist_videos().unwrap().1.len(), 3);
+
+        app.select_next_video();
+        app.toggle_video();
+        app.select_next_video();
+        app.select_next_video();
+        app.toggle_video();
+        let (title, videos) = app.selected_playlist_videos().unwrap();
+        assert_eq!(title, "Mix");
+        assert_eq!(videos.iter().map(|v| v.index).collect::<Vec<_>>(), vec![1]);
+
+        app.toggle_all_videos();
+        assert_eq!(app.selected_playlist_videos().unwrap().1.len(), 3);
+        app.toggle_all_videos();
+        assert!(app.selected_playlist_videos().unwrap().1.is_empty());
+    }
+
+    fn downloading_state() -> AppState {
+        AppState::Downloading {
+            url: "https://youtu.be/abc".to_string(),
+            video_info: VideoInfo {
+                title: "Title".to_string(),
+                uploader: "Uploader".to_string(),
+                duration: "1:00".to_string(),
+                view_count: None,
+                upload_date: None,
+            },
+            format: FormatOption {
+                label: "Best Quality".to_string(),
+                resolution: "Auto".to_string(),
+                file_size: "Best available".to_string(),
+                format_id: "best".to_string(),
+            },
+            progress: DownloadProgress {
+                percentage: 40.0,
+                downloaded_bytes: 0,
+                total_bytes: 0,
+                speed: 0.0,
+                eta: None,
+                elapsed: 0,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restart_confirms_and_cancels_download() {
+        let mut app = App::default();
+        app.start_fetching_info("https://youtu.be/abc".to_string());
+        app.request_restart();
+        assert!(!app.confirm_restart);
+        assert!(matches!(app.state, AppState::UrlInput { .. }));
+
+        app.state = downloading_state();
+        let task = tokio::spawn(std::future::pending::<()>());
+        app.track_task(task.abort_handle());
+
+        app.request_restart();
+        assert!(app.confirm_restart);
+        assert!(matches!(app.state, AppState::Downloading { .. }));
+
+        app.restart();
+        assert!(!app.confirm_restart);
+        assert!(matches!(app.state, AppState::UrlInput { .. }));
+        assert!(task.await.unwrap_err().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_quit_confirms_during_download() {
+        let mut app = App::default();
+        app.request_quit();
+        assert!(app.should_quit);
+
+        let mut app = App {
+            state: downloading_state(),
+            ..App::default()
+        };
+        let task = tokio::spawn(std::future::pending::<()>());
+        app.track_task(task.abort_handle());
+
+        app.request_quit();
+        assert!(!app.should_quit);
+        assert!(matches!(app.state, AppState::ConfirmQuit { .. }));
+
+        // Progress keeps landing on the download while the dialog is open
+        app.update_progress(DownloadProgress {
+            percentage: 75.0,
+            downloaded_bytes: 0,
+            total_bytes: 0,
+            speed: 0.0,
+            eta: None,
+            elapsed: 3,
+        });
+        app.cancel_quit();
+        match &app.state {
+            AppState::Downloading { progress, .. } => assert_eq!(progress.percentage, 75.0),
+            _ => panic!("expected the download screen"),
+        }
+
+        app.request_quit();
+        app.quit();
+        assert!(app.should_quit);
+        assert!(task.await.unwrap_err().is_cancelled());
     }
 }
@@ -21,8 +21,15 @@ impl EventHandler {
         }
     }
 
-    pub fn next(&self) -> Result<Event> {
-        if event::poll(self.tick_rate)? {
+    /// Wait up to one tick for the next event. Polling runs on the blocking
+    /// pool so it never stalls the runtime while a download is in flight.
+    pub async fn next(&self) -> Result<Event> {
+        let tick_rate = self.tick_rate;
+        tokio::task::spawn_blocking(move || Self::poll(tick_rate)).await?
+    }
+
+    fn poll(tick_rate: Duration) -> Result<Event> {
+        if event::poll(tick_rate)? {
             match event::read()? {
                 CrosstermEvent::Key(key) => Ok(Event::Key(key)),
                 CrosstermEvent::Paste(text) => Ok(Event::Paste(text)),
@@ -50,6 +57,20 @@ pub fn is_quit_key(key: KeyEvent) -> bool {
     )
 }
 
+/// Ctrl+N abandons the current screen and starts a new download
+pub fn is_restart_key(key: KeyEvent) -> bool {
+    matches!((key.code, key.modifiers), (KeyCode::Char('n'), KeyModifiers::CONTROL))
+}
+
+/// `l` toggles the log panel; while typing a URL only Ctrl+L does
+pub fn is_log_panel_key(key: KeyEvent, typing: bool) -> bool {
+    match (key.code, key.modifiers) {
+        (KeyCode::Char('l'), KeyModifiers::CONTROL) => true,
+        (KeyCode::Char('l') | KeyCode::Char('L'), KeyModifiers::NONE) => !typing,
+        _ => false,
+    }
+}
+
 pub fn is_back_key(key: KeyEvent) -> bool {
     matches!(key.code, KeyCode::Esc)
 }
@@ -15,4 +15,4 @@ pub use app::{App, AppState};
 pub use events::{Event, EventHandler};
 pub use runner::run_tui;
 pub use terminal::{restore_terminal, setup_terminal};
-pub use theme::Theme;
+pub use theme::{terminal_supports_unicode, Theme};
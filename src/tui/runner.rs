@@ -1,22 +1,40 @@
 use crate::cli::config::Config;
-use crate::infra::downloader::{Downloader, DownloadProgressInfo};
-use crate::shared::Result;
+use crate::core::playlist::{PlaylistDownloader, PlaylistVideo};
+use crate::core::BatchProgress;
+use crate::infra::downloader::{
+    sort_formats, update_ytdlp, Downloader, DownloadProgressInfo, SortKey, VideoMetadata,
+};
+use crate::infra::logger::LogBuffer;
+use crate::infra::mirror::mirror_file;
+use crate::infra::{export_comments, export_sponsor_segments, item_segments_path};
+use crate::infra::notifications::{notify_download_complete, notify_download_error};
+use crate::shared::{utils::format_duration, Result, YtdlError};
 use crate::tui::{
-    app::{App, AppState, DownloadProgress, DownloadSuccess, FormatOption, VideoInfo},
-    events::{is_back_key, is_quit_key, Event, EventHandler},
+    app::{
+        url_kind, App, AppState, DownloadProgress, DownloadSuccess, FormatOption, UrlKind, VideoInfo,
+        YtdlpUpdate,
+    },
+    events::{is_back_key, is_log_panel_key, is_quit_key, is_restart_key, Event, EventHandler},
     screens::{
         render_downloading, render_error, render_fetching, render_format_selection,
-        render_help, render_settings, render_success, render_url_input,
+        render_help, render_playlist_selection, render_settings, render_success, render_too_small, render_url_input,
+        terminal_too_small, SettingsState, SETTING_SAVE,
     },
     terminal::{restore_terminal, setup_panic_hook, setup_terminal},
+    widgets::{render_confirm, render_log_panel},
 };
 use crossterm::event::{KeyCode, KeyModifiers};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+/// `logs` receives this session's log lines for the `l` log panel; `simulate_slow`
+/// fakes every yt-dlp call (`--simulate-slow`)
+pub async fn run_tui(ascii: bool, logs: LogBuffer, simulate_slow: bool) -> Result<()> {
+    SIMULATE_SLOW.store(simulate_slow, Ordering::Relaxed);
 
-pub async fn run_tui() -> Result<()> {
     // Setup panic hook to restore terminal
     setup_panic_hook();
 
@@ -27,7 +45,7 @@ pub async fn run_tui() -> Result<()> {
     info!("Terminal initialized in raw mode");
 
     // Create application state wrapped in Arc<Mutex> for sharing with download task
-    let app = Arc::new(Mutex::new(App::new()));
+    let app = Arc::new(Mutex::new(App::new(&load_config(), ascii).with_log_buffer(logs)));
     let event_handler = EventHandler::default();
 
     // Main event loop
@@ -45,7 +63,10 @@ pub async fn run_tui() -> Result<()> {
         }
 
         // Handle events
-        match event_handler.next() {
+        match event_handler.next().await {
+            // Skip straight to the next iteration so the new size is drawn
+            // immediately instead of after the next tick
+            Ok(Event::Resize(_, _)) => continue,
             Ok(event) => {
                 if let Err(e) = handle_event(Arc::clone(&app), event).await {
                     error!("Error handling event: {}", e);
@@ -86,7 +107,18 @@ pub async fn run_tui() -> Result<()> {
 }
 
 fn render(app: &mut App, frame: &mut ratatui::Frame) {
-    match &app.state {
+    if terminal_too_small(frame.area()) {
+        render_too_small(frame, &app.theme);
+        return;
+    }
+
+    // The quit confirmation is drawn over the download it interrupts
+    let state = match &app.state {
+        AppState::ConfirmQuit { previous_state } => previous_state.as_ref(),
+        state => state,
+    };
+
+    match state {
         AppState::UrlInput {
             input,
             cursor_pos,
@@ -122,6 +154,13 @@ fn render(app: &mut App, frame: &mut ratatui::Frame) {
         } => {
             render_format_selection(frame, &app.theme, video_info, formats, *selected_index);
         }
+        AppState::PlaylistSelection {
+            playlist,
+            selected_index,
+            ..
+        } => {
+            render_playlist_selection(frame, &app.theme, playlist, *selected_index);
+        }
         AppState::Downloading {
             video_info,
             format,
@@ -137,9 +176,18 @@ fn render(app: &mut App, frame: &mut ratatui::Frame) {
             error_type,
             message,
             suggestions,
+            update,
             ..
         } => {
-            render_error(frame, &app.theme, error_type, message, suggestions);
+            render_error(
+                frame,
+                &app.theme,
+                error_type,
+                message,
+                suggestions,
+                update.as_ref(),
+                app.spinner.frame(),
+            );
         }
         AppState::Help { .. } => {
             render_help(frame, &app.theme);
@@ -147,6 +195,19 @@ fn render(app: &mut App, frame: &mut ratatui::Frame) {
         AppState::Settings { settings, .. } => {
             render_settings(frame, &app.theme, settings, settings.selected_index);
         }
+        AppState::ConfirmQuit { .. } => {}
+    }
+
+    if app.show_logs {
+        render_log_panel(frame, &app.theme, &app.logs.lines(), app.log_scroll);
+    }
+
+    if matches!(app.state, AppState::ConfirmQuit { .. }) {
+        render_confirm(frame, &app.theme, "Quit", "A download is in progress. Quit and cancel it?");
+    }
+
+    if app.confirm_restart {
+        render_confirm(frame, &app.theme, "New Download", "Cancel the current download and start over?");
     }
 }
 
@@ -154,67 +215,108 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
     match event {
         Event::Paste(text) => {
             // Handle pasted text - only in URL input state
-            let current_state = {
-                let app_locked = app.lock().await;
-                app_locked.state.clone()
-            };
-
-            if let AppState::UrlInput { input, cursor_pos, .. } = &current_state {
-                let mut new_input = input.clone();
-                // Insert the pasted text at cursor position
-                new_input.insert_str(*cursor_pos, &text);
-                let new_cursor_pos = cursor_pos + text.len();
-
-                let mut app_locked = app.lock().await;
-                app_locked.update_input(new_input, new_cursor_pos);
-            }
+            let mut app_locked = app.lock().await;
+            app_locked.insert_text(&text);
         }
         Event::Key(key) => {
+            // Quitting during a download is confirmed first
+            {
+                let mut app_locked = app.lock().await;
+                if matches!(app_locked.state, AppState::ConfirmQuit { .. }) {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => app_locked.quit(),
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app_locked.cancel_quit(),
+                        _ => {}
+                    }
+                    return Ok(());
+                }
+            }
+
             // Global quit key
             if is_quit_key(key) {
                 let mut app_locked = app.lock().await;
-                app_locked.quit();
+                app_locked.request_quit();
                 return Ok(());
             }
 
+            // Global new-download key; confirmed first while a download is running
+            {
+                let mut app_locked = app.lock().await;
+                if app_locked.confirm_restart {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => app_locked.restart(),
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app_locked.confirm_restart = false,
+                        _ => {}
+                    }
+                    return Ok(());
+                }
+
+                if is_restart_key(key) {
+                    app_locked.request_restart();
+                    return Ok(());
+                }
+            }
+
             // Global help key is now handled per-state to avoid conflicts with typing
 
             // State-specific key handling
-            let current_state = {
+            let (current_state, show_logs) = {
                 let app_locked = app.lock().await;
-                app_locked.state.clone()
+                (app_locked.state.clone(), app_locked.show_logs)
             };
 
+            // Log panel keys work on every screen
+            if is_log_panel_key(key, matches!(current_state, AppState::UrlInput { .. })) {
+                let mut app_locked = app.lock().await;
+                app_locked.toggle_log_panel();
+                return Ok(());
+            }
+
+            if show_logs && matches!(key.code, KeyCode::PageUp | KeyCode::PageDown) {
+                let mut app_locked = app.lock().await;
+                app_locked.scroll_logs(key.code == KeyCode::PageUp);
+                return Ok(());
+            }
+
             match &current_state {
-                AppState::UrlInput {
-                    input, cursor_pos, ..
-                } => {
+                AppState::UrlInput { input, .. } => {
                     match key.code {
                         KeyCode::Char('s') | KeyCode::Char('S') if key.modifiers.is_empty() => {
+                            let settings = SettingsState::from_config(&load_config());
                             let mut app_locked = app.lock().await;
-                            app_locked.go_to_settings(
-                                "./downloads".to_string(),
-                                "best".to_string(),
-                                3,
-                            );
+                            app_locked.go_to_settings(settings);
                         }
                         KeyCode::Char('h') | KeyCode::Char('H') if key.modifiers.is_empty() => {
                             let mut app_locked = app.lock().await;
                             app_locked.go_to_help();
                         }
                         KeyCode::Char(c) if key.modifiers.is_empty() && !matches!(c, 's' | 'S' | 'h' | 'H' | 'q' | 'Q') => {
-                            let mut new_input = input.clone();
-                            new_input.insert(*cursor_pos, c);
                             let mut app_locked = app.lock().await;
-                            app_locked.update_input(new_input, cursor_pos + 1);
+                            app_locked.insert_char(c);
                         }
                         KeyCode::Backspace => {
-                            if *cursor_pos > 0 {
-                                let mut new_input = input.clone();
-                                new_input.remove(cursor_pos - 1);
-                                let mut app_locked = app.lock().await;
-                                app_locked.update_input(new_input, cursor_pos - 1);
-                            }
+                            let mut app_locked = app.lock().await;
+                            app_locked.delete_char_before_cursor();
+                        }
+                        KeyCode::Delete => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.delete_char_at_cursor();
+                        }
+                        KeyCode::Left => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.move_cursor_left();
+                        }
+                        KeyCode::Right => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.move_cursor_right();
+                        }
+                        KeyCode::Home => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.move_cursor_home();
+                        }
+                        KeyCode::End => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.move_cursor_end();
                         }
                         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                             let mut app_locked = app.lock().await;
@@ -237,15 +339,19 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
                                 let app_clone = Arc::clone(&app);
 
                                 // Spawn fetch as background task so UI stays responsive
-                                tokio::spawn(async move {
+                                let task = tokio::spawn(async move {
                                     {
                                         let mut app_locked = app_clone.lock().await;
                                         app_locked.start_fetching_info(input_clone.clone());
                                         drop(app_locked);
                                     }
 
-                                    fetch_video_info(app_clone, input_clone).await;
+                                    match url_kind(&input_clone) {
+                                        UrlKind::Video => fetch_video_info(app_clone, input_clone).await,
+                                        UrlKind::Playlist => fetch_playlist_info(app_clone, input_clone).await,
+                                    }
                                 });
+                                app.lock().await.track_task(task.abort_handle());
                             }
                         }
                         _ => {}
@@ -269,32 +375,73 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
                         }
                         KeyCode::Enter => {
                             let url_clone = url.clone();
-                            let selected_format = {
+                            let (selected_format, metadata) = {
                                 let mut app_locked = app.lock().await;
-                                app_locked.start_download();
+                                let metadata = app_locked.start_download();
 
                                 // Get format to determine if audio only
-                                if let AppState::Downloading { format, .. } = &app_locked.state {
+                                let format_id = if let AppState::Downloading { format, .. } = &app_locked.state {
                                     format.format_id.clone()
                                 } else {
                                     "best".to_string()
-                                }
+                                };
+                                (format_id, metadata)
                             };
 
                             let audio_only = selected_format == "audio";
 
                             // Spawn download task
-                            tokio::spawn(perform_download(Arc::clone(&app), url_clone, audio_only));
+                            let task = tokio::spawn(perform_download(Arc::clone(&app), url_clone, audio_only, metadata));
+                            app.lock().await.track_task(task.abort_handle());
                         }
                         KeyCode::Char('a') | KeyCode::Char('A') => {
                             // Quick select audio only
                             let url_clone = url.clone();
-                            {
+                            let metadata = {
                                 let mut app_locked = app.lock().await;
-                                app_locked.start_download();
-                            }
+                                app_locked.start_download()
+                            };
                             // Spawn download task for audio
-                            tokio::spawn(perform_download(Arc::clone(&app), url_clone, true));
+                            let task = tokio::spawn(perform_download(Arc::clone(&app), url_clone, true, metadata));
+                            app.lock().await.track_task(task.abort_handle());
+                        }
+                        KeyCode::Esc => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.go_to_url_input();
+                        }
+                        _ => {}
+                    }
+                }
+                AppState::PlaylistSelection { .. } => {
+                    match key.code {
+                        KeyCode::Up => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.select_previous_video();
+                        }
+                        KeyCode::Down => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.select_next_video();
+                        }
+                        KeyCode::Char(' ') => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.toggle_video();
+                        }
+                        KeyCode::Char('a') | KeyCode::Char('A') => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.toggle_all_videos();
+                        }
+                        KeyCode::Enter | KeyCode::Char('m') | KeyCode::Char('M') => {
+                            let audio_only = key.code != KeyCode::Enter;
+                            let selection = app.lock().await.selected_playlist_videos();
+                            if let Some((title, videos)) = selection.filter(|(_, videos)| !videos.is_empty()) {
+                                let task = tokio::spawn(perform_playlist_download(
+                                    Arc::clone(&app),
+                                    title,
+                                    videos,
+                                    audio_only,
+                                ));
+                                app.lock().await.track_task(task.abort_handle());
+                            }
                         }
                         KeyCode::Esc => {
                             let mut app_locked = app.lock().await;
@@ -341,6 +488,18 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
                             let mut app_locked = app.lock().await;
                             app_locked.go_to_url_input();
                         }
+                        KeyCode::Char('u') | KeyCode::Char('U') => {
+                            let mut app_locked = app.lock().await;
+                            if app_locked.can_start_ytdlp_update() {
+                                app_locked.set_ytdlp_update(YtdlpUpdate::Running);
+                                drop(app_locked);
+
+                                let app_clone = Arc::clone(&app);
+                                tokio::spawn(async move {
+                                    run_ytdlp_update(app_clone).await;
+                                });
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -350,7 +509,7 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
                         app_locked.back_from_overlay();
                     }
                 }
-                AppState::Settings { .. } => {
+                AppState::Settings { settings, .. } => {
                     match key.code {
                         KeyCode::Up => {
                             let mut app_locked = app.lock().await;
@@ -360,9 +519,31 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
                             let mut app_locked = app.lock().await;
                             app_locked.select_next_setting();
                         }
+                        KeyCode::Left => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.adjust_setting(false);
+                        }
+                        KeyCode::Right => {
+                            let mut app_locked = app.lock().await;
+                            app_locked.adjust_setting(true);
+                        }
+                        KeyCode::Enter if settings.selected_index == SETTING_SAVE => {
+                            let saved = save_settings(settings);
+                            let mut app_locked = app.lock().await;
+                            match saved {
+                                Ok(()) => {
+                                    app_locked.spinner = settings.spinner();
+                                    app_locked.back_from_overlay();
+                                }
+                                Err(e) => {
+                                    error!("Failed to save settings: {}", e);
+                                    app_locked.settings_save_failed(e.to_string());
+                                }
+                            }
+                        }
                         KeyCode::Enter => {
-                            // TODO: Edit selected setting
-                            info!("Edit setting selected");
+                            let mut app_locked = app.lock().await;
+                            app_locked.adjust_setting(true);
                         }
                         KeyCode::Esc => {
                             let mut app_locked = app.lock().await;
@@ -371,10 +552,12 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
                         _ => {}
                     }
                 }
+                // Answered before the global quit key above
+                AppState::ConfirmQuit { .. } => {}
             }
         }
         Event::Resize(_, _) => {
-            // Terminal was resized, will be handled automatically by next render
+            // Redrawn by the main loop as soon as the event arrives
         }
         Event::Tick => {
             // Regular tick for animations and updates
@@ -386,12 +569,39 @@ async fn handle_event(app: Arc<Mutex<App>>, event: Event) -> Result<()> {
     Ok(())
 }
 
+/// `--simulate-slow` from the command line, which the saved config never holds
+static SIMULATE_SLOW: AtomicBool = AtomicBool::new(false);
+
+/// Load the saved config so settings changed in the TUI take effect
+fn load_config() -> Config {
+    let mut config = Config::load_default().unwrap_or_else(|e| {
+        warn!("Failed to load config, using defaults: {}", e);
+        Config::load_with_env_overrides()
+    });
+    config.simulate_slow = SIMULATE_SLOW.load(Ordering::Relaxed);
+    config
+}
+
+fn save_settings(settings: &SettingsState) -> Result<()> {
+    let path = Config::get_default_config_path().ok_or_else(|| {
+        crate::shared::YtdlError::Config("Failed to determine config path".to_string())
+    })?;
+
+    let mut config = load_config();
+    settings.apply_to(&mut config);
+    config.save_to_file(&path)
+}
+
 // Fetch real video information
 async fn fetch_video_info(app: Arc<Mutex<App>>, url: String) {
-    let config = Config::load_with_env_overrides();
-    let downloader = Downloader::new(config.output_dir.clone(), config.quality.clone());
+    let config = load_config();
+    let downloader = Downloader::from_config(&config);
 
     match downloader.fetch_video_info(&url).await {
+        Ok(metadata) if metadata.formats.is_empty() => {
+            let e = YtdlError::NoFormats(metadata.id.clone());
+            app.lock().await.go_to_error("No Formats".to_string(), e.to_string(), e.help());
+        }
         Ok(metadata) => {
             // Convert VideoMetadata to display-friendly VideoInfo
             let video_info = metadata.to_display_info();
@@ -409,18 +619,10 @@ async fn fetch_video_info(app: Arc<Mutex<App>>, url: String) {
             // Get unique video formats sorted by resolution
             let mut video_formats: Vec<_> = metadata.formats.iter()
                 .filter(|f| f.vcodec.as_ref().map(|v| v != "none").unwrap_or(false))
+                .cloned()
                 .collect();
 
-            video_formats.sort_by(|a, b| {
-                b.resolution.as_ref()
-                    .and_then(|r| r.split('x').nth(1))
-                    .and_then(|h| h.parse::<u32>().ok())
-                    .unwrap_or(0)
-                    .cmp(&a.resolution.as_ref()
-                        .and_then(|r| r.split('x').nth(1))
-                        .and_then(|h| h.parse::<u32>().ok())
-                        .unwrap_or(0))
-            });
+            sort_formats(&mut video_formats, SortKey::Resolution);
 
             for format in video_formats.iter().take(5) {
                 let resolution = format.resolution.as_ref()
@@ -449,62 +651,193 @@ async fn fetch_video_info(app: Arc<Mutex<App>>, url: String) {
             {
                 let mut app_locked = app.lock().await;
                 if let AppState::FetchingInfo { url: _ } = &app_locked.state {
-                    app_locked.show_format_selection(url.clone(), video_info, formats);
+                    app_locked.show_format_selection(url.clone(), video_info, formats, Arc::new(metadata));
                 }
             }
         }
         Err(e) => {
             error!("Failed to fetch video info: {}", e);
             let mut app_locked = app.lock().await;
+            let error_type = if matches!(e, YtdlError::BotCheck(_)) {
+                "Bot Check"
+            } else {
+                "Fetch Error"
+            };
             app_locked.go_to_error(
-                "Fetch Error".to_string(),
+                error_type.to_string(),
                 format!("Failed to fetch video information: {}", e),
-                vec![
-                    "Check your internet connection".to_string(),
-                    "Verify the URL is correct".to_string(),
-                    "Try updating yt-dlp".to_string(),
-                ],
+                e.help(),
             );
+            if e.suggests_update() {
+                app_locked.offer_ytdlp_update();
+            }
+        }
+    }
+}
+
+/// Playlist counterpart of `fetch_video_info`: list the videos for the selection screen
+async fn fetch_playlist_info(app: Arc<Mutex<App>>, url: String) {
+    let config = load_config();
+    let playlist_downloader = PlaylistDownloader::from_config(&config);
+
+    match playlist_downloader.fetch_playlist_info(&url).await {
+        Ok(playlist) if playlist.videos.is_empty() => {
+            app.lock().await.go_to_error(
+                "Empty Playlist".to_string(),
+                format!("No videos found in playlist \"{}\"", playlist.title),
+                vec!["Check that the playlist is public and not empty".to_string()],
+            );
+        }
+        Ok(playlist) => {
+            let mut app_locked = app.lock().await;
+            if let AppState::FetchingInfo { .. } = &app_locked.state {
+                app_locked.show_playlist_selection(url, playlist);
+            }
+        }
+        Err(e) => {
+            error!("Failed to fetch playlist info: {}", e);
+            let mut app_locked = app.lock().await;
+            app_locked.go_to_error(
+                "Fetch Error".to_string(),
+                format!("Failed to fetch playlist information: {}", e),
+                e.help(),
+            );
+            if e.suggests_update() {
+                app_locked.offer_ytdlp_update();
+            }
+        }
+    }
+}
+
+async fn run_ytdlp_update(app: Arc<Mutex<App>>) {
+    let downloader = Downloader::from_config(&load_config());
+    let status = match update_ytdlp(downloader.runner()).await {
+        Ok(summary) => {
+            info!("yt-dlp update finished: {}", summary);
+            YtdlpUpdate::Done(summary)
+        }
+        Err(e) => {
+            error!("yt-dlp update failed: {}", e);
+            YtdlpUpdate::Failed(e.to_string())
+        }
+    };
+
+    app.lock().await.set_ytdlp_update(status);
+}
+
+/// Write the metadata fetched for the format screen to a temp file for `--load-info-json`
+async fn write_info_json(metadata: &VideoMetadata) -> Option<PathBuf> {
+    let raw_json = metadata.raw_json.as_ref()?;
+    let path = std::env::temp_dir().join(format!("ytdl-{}.info.json", metadata.id));
+
+    match tokio::fs::write(&path, raw_json.as_bytes()).await {
+        Ok(()) => Some(path),
+        Err(e) => {
+            warn!("Failed to cache video metadata, yt-dlp will fetch it again: {}", e);
+            None
         }
     }
 }
 
+/// The downloader for TUI downloads, configured from the saved config
+fn tui_downloader(config: &Config, info_json: Option<PathBuf>) -> Downloader {
+    Downloader::from_config(config)
+        .with_term_progress(config.term_progress.unwrap_or(false))
+        .with_info_json(info_json)
+}
+
+/// Comment and SponsorBlock exports for a finished download; `segments_dest` is
+/// where its segments go when `--sponsorblock-export` is set
+async fn export_sidecars(config: &Config, path: &Path, segments_dest: Option<PathBuf>) {
+    if config.write_comments.unwrap_or(false) {
+        if let Err(e) = export_comments(path, segments_dest.is_some()).await {
+            warn!("Failed to export comments for {:?}: {}", path, e);
+        }
+    }
+
+    if let Some(dest) = segments_dest {
+        if let Err(e) = export_sponsor_segments(path, &dest).await {
+            warn!("Failed to export sponsor segments for {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Progress callback that updates the download screen
+fn progress_updater(
+    app: Arc<Mutex<App>>,
+    start_time: std::time::Instant,
+) -> impl FnMut(DownloadProgressInfo) + Send + 'static {
+    move |progress_info: DownloadProgressInfo| {
+        let elapsed = start_time.elapsed().as_secs();
+        let app_handle = Arc::clone(&app);
+
+        // Update the app state with real progress
+        tokio::spawn(async move {
+            let mut app_locked = app_handle.lock().await;
+            app_locked.update_progress(DownloadProgress {
+                percentage: progress_info.percentage,
+                downloaded_bytes: progress_info.downloaded_bytes,
+                total_bytes: progress_info.total_bytes,
+                speed: progress_info.speed,
+                eta: progress_info.eta,
+                elapsed,
+            });
+        });
+    }
+}
+
+fn display_file_size(bytes: u64) -> String {
+    if bytes > 1_000_000_000 {
+        format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
+    } else if bytes > 1_000_000 {
+        format!("{:.1} MB", bytes as f64 / 1_000_000.0)
+    } else if bytes > 1_000 {
+        format!("{:.1} KB", bytes as f64 / 1_000.0)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
+fn display_elapsed(duration: std::time::Duration) -> String {
+    if duration.as_secs() > 60 {
+        format!("{} min {} sec", duration.as_secs() / 60, duration.as_secs() % 60)
+    } else {
+        format!("{} seconds", duration.as_secs())
+    }
+}
+
 // Perform real download with progress updates
-async fn perform_download(app: Arc<Mutex<App>>, url: String, audio_only: bool) {
-    let config = Config::load_with_env_overrides();
-    let downloader = Downloader::new(config.output_dir.clone(), config.quality.clone());
+async fn perform_download(
+    app: Arc<Mutex<App>>,
+    url: String,
+    audio_only: bool,
+    metadata: Option<Arc<VideoMetadata>>,
+) {
+    let config = load_config();
+    let info_json = match metadata {
+        Some(ref metadata) => write_info_json(metadata).await,
+        None => None,
+    };
+
+    let downloader = tui_downloader(&config, info_json.clone());
+    let notify = config.notifications.unwrap_or(true);
 
-    let app_clone = Arc::clone(&app);
     let start_time = std::time::Instant::now();
 
-    let result = downloader.download_with_progress(
-        &url,
-        audio_only,
-        move |progress_info: DownloadProgressInfo| {
-            let elapsed = start_time.elapsed().as_secs();
-            let app_handle = Arc::clone(&app_clone);
-
-            // Update the app state with real progress
-            tokio::spawn(async move {
-                let mut app_locked = app_handle.lock().await;
-                if let AppState::Downloading { progress, .. } = &mut app_locked.state {
-                    *progress = DownloadProgress {
-                        percentage: progress_info.percentage,
-                        downloaded_bytes: progress_info.downloaded_bytes,
-                        total_bytes: progress_info.total_bytes,
-                        speed: progress_info.speed,
-                        eta: progress_info.eta,
-                        elapsed,
-                    };
-                }
-            });
-        }
-    ).await;
+    let result = downloader
+        .download_with_progress(&url, audio_only, progress_updater(Arc::clone(&app), start_time))
+        .await;
 
-    let mut app_locked = app.lock().await;
+    if let Some(ref path) = info_json {
+        let _ = tokio::fs::remove_file(path).await;
+    }
 
+    // Sidecars, renaming and mirroring can take a while for big files; the render
+    // loop locks `app` every frame, so it's only locked to show the outcome
     match result {
         Ok(file_path) => {
+            export_sidecars(&config, &file_path, config.sponsorblock_export.clone()).await;
+            let file_path = config.apply_filename_policy(file_path).await;
             let filename = file_path
                 .file_name()
                 .and_then(|n| n.to_str())
@@ -514,31 +847,24 @@ async fn perform_download(app: Arc<Mutex<App>>, url: String, audio_only: bool) {
             let file_size = tokio::fs::metadata(&file_path)
                 .await
                 .ok()
-                .map(|m| {
-                    let bytes = m.len();
-                    if bytes > 1_000_000_000 {
-                        format!("{:.2} GB", bytes as f64 / 1_000_000_000.0)
-                    } else if bytes > 1_000_000 {
-                        format!("{:.1} MB", bytes as f64 / 1_000_000.0)
-                    } else if bytes > 1_000 {
-                        format!("{:.1} KB", bytes as f64 / 1_000.0)
-                    } else {
-                        format!("{} bytes", bytes)
-                    }
-                })
+                .map(|m| display_file_size(m.len()))
                 .unwrap_or_else(|| "Unknown".to_string());
 
-            let duration = start_time.elapsed();
-            let duration_str = if duration.as_secs() > 60 {
-                format!("{} min {} sec", duration.as_secs() / 60, duration.as_secs() % 60)
-            } else {
-                format!("{} seconds", duration.as_secs())
-            };
+            let duration_str = display_elapsed(start_time.elapsed());
 
             let save_location = file_path.parent()
                 .map(|p| p.to_path_buf())
                 .unwrap_or_else(|| PathBuf::from("./downloads"));
 
+            if !config.mirror_dirs.is_empty() {
+                mirror_file(&file_path, &config.mirror_dirs).await;
+            }
+
+            if notify {
+                let _ = notify_download_complete(&filename, &file_path.display().to_string());
+            }
+            config.play_completion_sound();
+
             let success_info = DownloadSuccess {
                 filename,
                 file_size,
@@ -546,19 +872,173 @@ async fn perform_download(app: Arc<Mutex<App>>, url: String, audio_only: bool) {
                 save_location,
             };
 
-            app_locked.download_complete(success_info);
+            let mut app_locked = app.lock().await;
+            // The download screen may have been left while mirroring
+            if app_locked.is_downloading() {
+                app_locked.download_complete(success_info);
+            }
+        }
+        Err(e @ YtdlError::Skipped(_)) => {
+            info!("Download skipped: {}", e);
+            app.lock().await.go_to_error("Download Skipped".to_string(), e.to_string(), e.help());
         }
         Err(e) => {
             error!("Download failed: {}", e);
+            if notify {
+                let _ = notify_download_error(&url, &e.to_string());
+            }
+            let mut app_locked = app.lock().await;
             app_locked.go_to_error(
                 "Download Error".to_string(),
                 format!("Failed to download video: {}", e),
-                vec![
-                    "Check your internet connection".to_string(),
-                    "Verify the video is still available".to_string(),
-                    "Try a different quality or format".to_string(),
-                ],
+                e.help(),
             );
+            if e.suggests_update() {
+                app_locked.offer_ytdlp_update();
+            }
         }
     }
 }
+
+/// Download the videos picked on the playlist screen one after another, showing
+/// each on the download screen. Failed videos are skipped; the success screen
+/// reports how many of them finished.
+async fn perform_playlist_download(
+    app: Arc<Mutex<App>>,
+    playlist_title: String,
+    videos: Vec<PlaylistVideo>,
+    audio_only: bool,
+) {
+    let config = load_config();
+    let downloader = tui_downloader(&config, None);
+    let notify = config.notifications.unwrap_or(true);
+    let format = if audio_only {
+        FormatOption {
+            label: "Audio Only".to_string(),
+            resolution: "N/A".to_string(),
+            file_size: "Unknown".to_string(),
+            format_id: "audio".to_string(),
+        }
+    } else {
+        FormatOption {
+            label: "Best Quality".to_string(),
+            resolution: "Auto".to_string(),
+            file_size: "Best available".to_string(),
+            format_id: "best".to_string(),
+        }
+    };
+
+    let start_time = std::time::Instant::now();
+    let mut downloaded = Vec::new();
+    let mut last_error = None;
+    let mut failed = 0;
+    let mut skipped = 0;
+
+    for (position, video) in videos.iter().enumerate() {
+        {
+            let mut app_locked = app.lock().await;
+            // Leaving the download screen (Ctrl+C) stops the remaining videos
+            if position > 0 && !app_locked.is_downloading() {
+                info!("Playlist download left after {} of {} videos", position, videos.len());
+                return;
+            }
+
+            let progress = BatchProgress {
+                total: videos.len(),
+                complete: downloaded.len(),
+                failed,
+                skipped,
+                downloading: 1,
+                total_bytes: 0,
+                elapsed: Some(start_time.elapsed()),
+                concurrency: 1,
+            };
+            let eta = match progress.eta_secs() {
+                Some(eta) => format!("{} left", format_duration(eta)),
+                None => "estimating...".to_string(),
+            };
+
+            let video_info = VideoInfo {
+                title: format!("[{}/{}, {}] {}", position + 1, videos.len(), eta, video.title),
+                uploader: playlist_title.clone(),
+                duration: video.duration.map(format_duration).unwrap_or_else(|| "Unknown".to_string()),
+                view_count: None,
+                upload_date: None,
+            };
+            app_locked.show_download(video.url.clone(), video_info, format.clone());
+        }
+
+        let progress = progress_updater(Arc::clone(&app), std::time::Instant::now());
+        let result = downloader.download_with_progress(&video.url, audio_only, progress).await;
+
+        match result {
+            Ok(file_path) => {
+                let segments_dest = config
+                    .sponsorblock_export
+                    .as_deref()
+                    .map(|dest| item_segments_path(dest, &file_path));
+                export_sidecars(&config, &file_path, segments_dest).await;
+                let file_path = config.apply_filename_policy(file_path).await;
+                if !config.mirror_dirs.is_empty() {
+                    mirror_file(&file_path, &config.mirror_dirs).await;
+                }
+                downloaded.push(file_path);
+            }
+            Err(e @ YtdlError::Skipped(_)) => {
+                info!("Playlist video {} skipped: {}", video.url, e);
+                skipped += 1;
+                // A real failure is the more useful error to show
+                last_error.get_or_insert(e);
+            }
+            Err(e) => {
+                warn!("Playlist video {} failed: {}", video.url, e);
+                failed += 1;
+                last_error = Some(e);
+            }
+        }
+    }
+
+    let mut app_locked = app.lock().await;
+    if !app_locked.is_downloading() {
+        return;
+    }
+
+    let Some(first) = downloaded.first() else {
+        let e = last_error.unwrap_or_else(|| YtdlError::Other("No videos were downloaded".to_string()));
+        error!("Playlist download failed: {}", e);
+        if notify {
+            let _ = notify_download_error(&playlist_title, &e.to_string());
+        }
+        app_locked.go_to_error(
+            "Download Error".to_string(),
+            format!("None of the {} selected videos downloaded: {}", videos.len(), e),
+            e.help(),
+        );
+        if e.suggests_update() {
+            app_locked.offer_ytdlp_update();
+        }
+        return;
+    };
+
+    let save_location = first.parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("./downloads"));
+
+    let mut total_bytes = 0;
+    for path in &downloaded {
+        total_bytes += tokio::fs::metadata(path).await.map(|m| m.len()).unwrap_or(0);
+    }
+
+    let filename = format!("{} of {} videos from {}", downloaded.len(), videos.len(), playlist_title);
+    if notify {
+        let _ = notify_download_complete(&filename, &save_location.display().to_string());
+    }
+    config.play_completion_sound();
+
+    app_locked.download_complete(DownloadSuccess {
+        filename,
+        file_size: display_file_size(total_bytes),
+        duration: display_elapsed(start_time.elapsed()),
+        save_location,
+    });
+}
@@ -1,3 +1,4 @@
+use crate::shared::utils::truncate_to_width;
 use crate::tui::{
     app::{DownloadProgress, FormatOption, VideoInfo},
     theme::Theme,
@@ -43,7 +44,8 @@ pub fn render_downloading(
         .border_style(Style::default().fg(theme.color))
         .title(" Video ");
 
-    let video_title_text = vec![Line::from(vec![Span::raw(&video_info.title)])];
+    let title_width = video_title_block.inner(chunks[1]).width as usize;
+    let video_title_text = vec![Line::from(vec![Span::raw(truncate_to_width(&video_info.title, title_width))])];
 
     let video_title_para = Paragraph::new(video_title_text)
         .block(video_title_block)
@@ -113,7 +115,7 @@ pub fn render_downloading(
 
     // Footer
     let footer = Paragraph::new(Line::from(vec![
-        Span::styled("[Ctrl+C] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        Span::styled("[Ctrl+N] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
         Span::raw("Cancel Download"),
     ]))
     .alignment(Alignment::Center);
@@ -1,3 +1,4 @@
+use crate::tui::app::YtdlpUpdate;
 use crate::tui::theme::Theme;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
@@ -13,6 +14,8 @@ pub fn render_error(
     error_type: &str,
     message: &str,
     suggestions: &[String],
+    update: Option<&YtdlpUpdate>,
+    spinner: &str,
 ) {
     let area = frame.area();
 
@@ -23,7 +26,7 @@ pub fn render_error(
             Constraint::Length(2),
             Constraint::Length(6),
             Constraint::Min(5),
-            Constraint::Length(7),
+            Constraint::Length(8),
             Constraint::Length(3),
         ])
         .split(area);
@@ -36,7 +39,7 @@ pub fn render_error(
 
     // Error icon
     let icon_text = vec![Line::from(vec![Span::styled(
-        "✗",
+        theme.glyph("✗", "[X]"),
         Style::default()
             .fg(theme.color)
             .add_modifier(Modifier::BOLD),
@@ -108,7 +111,40 @@ pub fn render_error(
         .border_style(Style::default().fg(theme.color))
         .title(" Recovery Actions ");
 
-    let actions_text = vec![
+    let key_style = Style::default()
+        .fg(theme.color)
+        .add_modifier(Modifier::BOLD);
+
+    let mut actions_text = Vec::new();
+    match update {
+        Some(YtdlpUpdate::Available) => {
+            actions_text.push(Line::from(vec![
+                Span::styled("[U] ", key_style),
+                Span::raw("Update yt-dlp"),
+            ]));
+        }
+        Some(YtdlpUpdate::Running) => {
+            actions_text.push(Line::from(vec![
+                Span::styled(format!("{} ", spinner), key_style),
+                Span::raw("Updating yt-dlp..."),
+            ]));
+        }
+        Some(YtdlpUpdate::Done(summary)) => {
+            actions_text.push(Line::from(vec![
+                Span::styled("✓ ", key_style),
+                Span::raw(summary.clone()),
+            ]));
+        }
+        Some(YtdlpUpdate::Failed(message)) => {
+            actions_text.push(Line::from(vec![
+                Span::styled("[U] ", key_style),
+                Span::raw(format!("Update failed ({}), try again", message)),
+            ]));
+        }
+        None => {}
+    }
+
+    actions_text.extend([
         Line::from(vec![
             Span::styled(
                 "[R] ",
@@ -138,7 +174,7 @@ pub fn render_error(
             ),
             Span::raw("Quit"),
         ]),
-    ];
+    ]);
 
     let actions_para = Paragraph::new(actions_text)
         .block(actions_block)
@@ -1,3 +1,4 @@
+use crate::shared::utils::truncate_to_width;
 use crate::tui::{app::{FormatOption, VideoInfo}, theme::Theme};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
@@ -37,15 +38,16 @@ pub fn render_format_selection(
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.color))
         .title(" Video Information ");
+    let inner_width = info_block.inner(chunks[1]).width as usize;
 
     let info_text = vec![
         Line::from(vec![
             Span::styled("Title: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
-            Span::raw(&video_info.title),
+            Span::raw(truncate_to_width(&video_info.title, inner_width.saturating_sub("Title: ".len()))),
         ]),
         Line::from(vec![
             Span::styled("Uploader: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
-            Span::raw(&video_info.uploader),
+            Span::raw(truncate_to_width(&video_info.uploader, inner_width.saturating_sub("Uploader: ".len()))),
         ]),
         Line::from(vec![
             Span::styled("Duration: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
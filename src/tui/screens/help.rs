@@ -49,7 +49,7 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
         Line::from(""),
         Line::from(vec![
             Span::styled("q", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
-            Span::raw(" - Quit application"),
+            Span::raw(" - Quit application (asks first during a download)"),
         ]),
         Line::from(vec![
             Span::styled("h or ?", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
@@ -67,6 +67,14 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
             Span::styled("Ctrl+C", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
             Span::raw(" - Interrupt/Quit"),
         ]),
+        Line::from(vec![
+            Span::styled("Ctrl+N", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - New download from any screen (cancels a running one)"),
+        ]),
+        Line::from(vec![
+            Span::styled("l", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Toggle log panel (Ctrl+L in URL input, PgUp/PgDn to scroll)"),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Welcome Screen", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
@@ -93,6 +101,14 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
             Span::styled("Ctrl+U", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
             Span::raw(" - Clear input"),
         ]),
+        Line::from(vec![
+            Span::styled("←/→ Home/End", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Move cursor"),
+        ]),
+        Line::from(vec![
+            Span::styled("Del", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Delete at cursor"),
+        ]),
     ];
 
     let global_para = Paragraph::new(global_shortcuts)
@@ -123,6 +139,23 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
             Span::raw(" - Quick select audio"),
         ]),
         Line::from(""),
+        Line::from(vec![
+            Span::styled("Playlist Selection", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Space", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Toggle video"),
+        ]),
+        Line::from(vec![
+            Span::styled("a", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Select all / none"),
+        ]),
+        Line::from(vec![
+            Span::styled("Enter / m", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Download selected / as audio"),
+        ]),
+        Line::from(""),
         Line::from(vec![
             Span::styled("Success Screen", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
         ]),
@@ -148,6 +181,10 @@ pub fn render_help(frame: &mut Frame, theme: &Theme) {
             Span::styled("r", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
             Span::raw(" - Retry download"),
         ]),
+        Line::from(vec![
+            Span::styled("u", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Update yt-dlp (extractor errors)"),
+        ]),
         Line::from(vec![
             Span::styled("n", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
             Span::raw(" - New download"),
@@ -1,17 +1,24 @@
 pub mod url_input;
 pub mod fetching;
 pub mod format_selection;
+pub mod playlist_selection;
 pub mod downloading;
 pub mod success;
 pub mod error;
 pub mod help;
 pub mod settings;
+pub mod too_small;
 
 pub use url_input::render_url_input;
 pub use fetching::render_fetching;
 pub use format_selection::render_format_selection;
+pub use playlist_selection::render_playlist_selection;
 pub use downloading::render_downloading;
 pub use success::render_success;
 pub use error::render_error;
 pub use help::render_help;
-pub use settings::{render_settings, SettingsState};
+pub use too_small::{render_too_small, terminal_too_small};
+pub use settings::{
+    render_settings, SettingsState, SETTING_AUDIO_FORMAT, SETTING_NOTIFICATIONS, SETTING_SAVE,
+    SETTING_SPINNER_SPEED, SETTING_SPINNER_STYLE,
+};
@@ -0,0 +1,125 @@
+use crate::core::playlist::PlaylistInfo;
+use crate::shared::utils::{format_duration, truncate_to_width};
+use crate::tui::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+pub fn render_playlist_selection(
+    frame: &mut Frame,
+    theme: &Theme,
+    playlist: &PlaylistInfo,
+    selected_index: usize,
+) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(5),
+            Constraint::Min(5),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    // Title
+    let title = Paragraph::new("Select Videos")
+        .style(Style::default().fg(theme.color).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center);
+    frame.render_widget(title, chunks[0]);
+
+    // Playlist info
+    let info_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.color))
+        .title(" Playlist Information ");
+    let inner_width = info_block.inner(chunks[1]).width as usize;
+
+    let selected = playlist.videos.iter().filter(|v| v.selected).count();
+    let info_text = vec![
+        Line::from(vec![
+            Span::styled("Title: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(truncate_to_width(&playlist.title, inner_width.saturating_sub("Title: ".len()))),
+        ]),
+        Line::from(vec![
+            Span::styled("Uploader: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(truncate_to_width(
+                playlist.uploader.as_deref().unwrap_or("Unknown"),
+                inner_width.saturating_sub("Uploader: ".len()),
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Selected: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{} of {} videos", selected, playlist.videos.len())),
+        ]),
+    ];
+
+    let info_para = Paragraph::new(info_text)
+        .block(info_block)
+        .style(Style::default().fg(theme.color));
+    frame.render_widget(info_para, chunks[1]);
+
+    // Video list, scrolled to keep the highlighted video visible
+    let video_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.color))
+        .title(" Videos ");
+    let title_width = video_block.inner(chunks[2]).width.saturating_sub(18) as usize;
+
+    let items: Vec<ListItem> = playlist
+        .videos
+        .iter()
+        .map(|video| {
+            let checkbox = if video.selected { "[x] " } else { "[ ] " };
+            let duration = video.duration.map(format_duration).unwrap_or_else(|| "--:--".to_string());
+            ListItem::new(Line::from(vec![
+                Span::raw(checkbox),
+                Span::raw(format!("{:>3}. ", video.index)),
+                Span::raw(truncate_to_width(&video.title, title_width)),
+                Span::raw("  "),
+                Span::styled(duration, Style::default().fg(theme.color)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(video_block)
+        .style(Style::default().fg(theme.color))
+        .highlight_style(
+            Style::default()
+                .fg(theme.color)
+                .add_modifier(Modifier::BOLD)
+                .add_modifier(Modifier::REVERSED),
+        )
+        .highlight_symbol(theme.glyph("▶ ", "> "));
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected_index));
+    frame.render_stateful_widget(list, chunks[2], &mut list_state);
+
+    // Help text
+    let help_text = vec![Line::from(vec![
+        Span::styled("[↑/↓] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        Span::raw("Navigate  "),
+        Span::styled("[Space] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        Span::raw("Toggle  "),
+        Span::styled("[A] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        Span::raw("All/None  "),
+        Span::styled("[Enter] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        Span::raw("Download  "),
+        Span::styled("[M] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        Span::raw("Audio  "),
+        Span::styled("[Esc] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+        Span::raw("Back"),
+    ])];
+
+    let help = Paragraph::new(help_text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.color));
+    frame.render_widget(help, chunks[3]);
+}
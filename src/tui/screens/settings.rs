@@ -1,4 +1,6 @@
+use crate::cli::config::Config;
 use crate::tui::theme::Theme;
+use crate::tui::widgets::{Spinner, SPINNER_STYLES};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -7,13 +9,31 @@ use ratatui::{
     Frame,
 };
 
+pub const AUDIO_FORMATS: &[&str] = &["mp3", "opus", "flac", "m4a"];
+
+/// Spinner frame durations to cycle through; `None` keeps the style's own speed
+pub const SPINNER_SPEEDS_MS: &[Option<u64>] = &[None, Some(50), Some(80), Some(120), Some(200)];
+
+pub const SETTING_AUDIO_FORMAT: usize = 3;
+pub const SETTING_NOTIFICATIONS: usize = 4;
+pub const SETTING_SPINNER_STYLE: usize = 5;
+pub const SETTING_SPINNER_SPEED: usize = 6;
+pub const SETTING_SAVE: usize = 7;
+
 #[derive(Debug, Clone)]
 pub struct SettingsState {
     pub output_dir: String,
     pub quality: String,
     pub concurrent_downloads: usize,
     pub audio_format: String,
+    pub notifications: bool,
+    pub spinner_style: String,
+    pub spinner_speed_ms: Option<u64>,
+    /// Live preview of the selected spinner
+    pub spinner_preview: Spinner,
     pub selected_index: usize,
+    /// Why the last save failed, shown until the next save
+    pub save_error: Option<String>,
 }
 
 impl SettingsState {
@@ -23,9 +43,73 @@ impl SettingsState {
             quality,
             concurrent_downloads,
             audio_format: "mp3".to_string(),
+            notifications: true,
+            spinner_style: "braille".to_string(),
+            spinner_speed_ms: None,
+            spinner_preview: Spinner::new(),
             selected_index: 0,
+            save_error: None,
         }
     }
+
+    pub fn from_config(config: &Config) -> Self {
+        let mut settings = Self::new(
+            config.output_dir.display().to_string(),
+            config.quality.clone(),
+            config.concurrent_downloads.unwrap_or(3),
+        );
+        settings.audio_format = config.audio_format.clone().unwrap_or_else(|| "mp3".to_string());
+        settings.notifications = config.notifications.unwrap_or(true);
+        settings.spinner_style = config.spinner_style.clone().unwrap_or_else(|| "braille".to_string());
+        settings.spinner_speed_ms = config.spinner_speed_ms;
+        settings.spinner_preview = settings.spinner();
+        settings
+    }
+
+    /// Write the editable fields back into `config`
+    pub fn apply_to(&self, config: &mut Config) {
+        config.audio_format = Some(self.audio_format.clone());
+        config.notifications = Some(self.notifications);
+        config.spinner_style = Some(self.spinner_style.clone());
+        config.spinner_speed_ms = self.spinner_speed_ms;
+    }
+
+    /// Spinner for the selected style and speed
+    pub fn spinner(&self) -> Spinner {
+        Spinner::from_style(&self.spinner_style, self.spinner_speed_ms)
+    }
+
+    pub fn cycle_audio_format(&mut self, forward: bool) {
+        let current = self.audio_format.as_str();
+        self.audio_format = cycle(AUDIO_FORMATS, &current, forward).to_string();
+    }
+
+    pub fn cycle_spinner_style(&mut self, forward: bool) {
+        let current = self.spinner_style.as_str();
+        self.spinner_style = cycle(SPINNER_STYLES, &current, forward).to_string();
+        self.spinner_preview = self.spinner();
+    }
+
+    pub fn cycle_spinner_speed(&mut self, forward: bool) {
+        self.spinner_speed_ms = cycle(SPINNER_SPEEDS_MS, &self.spinner_speed_ms, forward);
+        self.spinner_preview = self.spinner();
+    }
+
+    pub fn toggle_notifications(&mut self) {
+        self.notifications = !self.notifications;
+    }
+}
+
+/// The option after (or before) `current`, wrapping around; unknown values start from the first
+fn cycle<T: PartialEq + Copy>(options: &[T], current: &T, forward: bool) -> T {
+    let index = options.iter().position(|o| o == current).unwrap_or(0);
+    let len = options.len();
+    let next = if forward {
+        (index + 1) % len
+    } else {
+        (index + len - 1) % len
+    };
+    options[next]
 }
 
 pub fn render_settings(
@@ -77,7 +161,23 @@ pub fn render_settings(
         ])),
         ListItem::new(Line::from(vec![
             Span::styled("Audio Format: ", Style::default().fg(theme.color)),
-            Span::raw(&settings.audio_format),
+            Span::raw(format!("◀ {} ▶", settings.audio_format)),
+        ])),
+        ListItem::new(Line::from(vec![
+            Span::styled("Notifications: ", Style::default().fg(theme.color)),
+            Span::raw(if settings.notifications { "On" } else { "Off" }),
+        ])),
+        ListItem::new(Line::from(vec![
+            Span::styled("Spinner Style: ", Style::default().fg(theme.color)),
+            Span::raw(format!("◀ {} ▶  ", settings.spinner_style)),
+            Span::styled(settings.spinner_preview.frame(), Style::default().fg(theme.color)),
+        ])),
+        ListItem::new(Line::from(vec![
+            Span::styled("Spinner Speed: ", Style::default().fg(theme.color)),
+            Span::raw(match settings.spinner_speed_ms {
+                Some(ms) => format!("◀ {} ms ▶", ms),
+                None => "◀ default ▶".to_string(),
+            }),
         ])),
         ListItem::new(Line::from(vec![
             Span::styled("Save Settings", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
@@ -108,16 +208,24 @@ pub fn render_settings(
         Line::from(vec![
             Span::styled("↑/↓", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
             Span::raw(" - Navigate  "),
+            Span::styled("←/→", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw(" - Change  "),
             Span::styled("Enter", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
             Span::raw(" - Edit  "),
             Span::styled("Esc", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
             Span::raw(" - Back"),
         ]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("Note: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
-            Span::raw("Settings will be saved to config file when you select 'Save Settings'"),
-        ]),
+        match settings.save_error {
+            Some(ref error) => Line::from(vec![
+                Span::styled("Save failed: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+                Span::raw(error.as_str()),
+            ]),
+            None => Line::from(vec![
+                Span::styled("Note: ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+                Span::raw("Settings will be saved to config file when you select 'Save Settings'"),
+            ]),
+        },
     ])
     .alignment(Alignment::Center)
     .block(
@@ -127,3 +235,61 @@ pub fn render_settings(
     );
     frame.render_widget(instructions, chunks[2]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_audio_format() {
+        let mut settings = SettingsState::new("./downloads".to_string(), "best".to_string(), 3);
+        assert_eq!(settings.audio_format, "mp3");
+
+        settings.cycle_audio_format(true);
+        assert_eq!(settings.audio_format, "opus");
+        settings.cycle_audio_format(true);
+        settings.cycle_audio_format(true);
+        assert_eq!(settings.audio_format, "m4a");
+        settings.cycle_audio_format(true);
+        assert_eq!(settings.audio_format, "mp3");
+
+        settings.cycle_audio_format(false);
+        assert_eq!(settings.audio_format, "m4a");
+    }
+
+    #[test]
+    fn test_apply_to_config() {
+        let mut settings = SettingsState::from_config(&Config::default());
+        settings.cycle_audio_format(true);
+        settings.toggle_notifications();
+
+        let mut config = Config::default();
+        settings.apply_to(&mut config);
+        assert_eq!(config.audio_format.as_deref(), Some("opus"));
+        assert_eq!(config.notifications, Some(false));
+    }
+
+    #[test]
+    fn test_cycle_spinner_settings() {
+        let mut settings = SettingsState::from_config(&Config::default());
+        assert_eq!(settings.spinner_style, "braille");
+
+        settings.cycle_spinner_style(true);
+        assert_eq!(settings.spinner_style, "dots");
+        assert_eq!(settings.spinner_preview.frame(), ".  ");
+        settings.cycle_spinner_style(false);
+        settings.cycle_spinner_style(false);
+        assert_eq!(settings.spinner_style, "line");
+
+        settings.cycle_spinner_speed(true);
+        assert_eq!(settings.spinner_speed_ms, Some(50));
+        settings.cycle_spinner_speed(false);
+        settings.cycle_spinner_speed(false);
+        assert_eq!(settings.spinner_speed_ms, Some(200));
+
+        let mut config = Config::default();
+        settings.apply_to(&mut config);
+        assert_eq!(config.spinner_style.as_deref(), Some("line"));
+        assert_eq!(config.spinner_speed_ms, Some(200));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::shared::utils::truncate_to_width;
 use crate::tui::{app::DownloadSuccess, theme::Theme};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
@@ -29,7 +30,7 @@ pub fn render_success(frame: &mut Frame, theme: &Theme, info: &DownloadSuccess)
 
     // Success icon
     let icon_text = vec![Line::from(vec![Span::styled(
-        "✓",
+        theme.glyph("✓", "[OK]"),
         Style::default()
             .fg(theme.color)
             .add_modifier(Modifier::BOLD),
@@ -43,6 +44,7 @@ pub fn render_success(frame: &mut Frame, theme: &Theme, info: &DownloadSuccess)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.color))
         .title(" File Information ");
+    let inner_width = info_block.inner(chunks[2]).width as usize;
 
     let info_text = vec![
         Line::from(vec![
@@ -52,7 +54,7 @@ pub fn render_success(frame: &mut Frame, theme: &Theme, info: &DownloadSuccess)
                     .fg(theme.color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw(&info.filename),
+            Span::raw(truncate_to_width(&info.filename, inner_width.saturating_sub("Filename: ".len()))),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -82,7 +84,10 @@ pub fn render_success(frame: &mut Frame, theme: &Theme, info: &DownloadSuccess)
                     .fg(theme.color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw(info.save_location.to_string_lossy().to_string()),
+            Span::raw(truncate_to_width(
+                &info.save_location.to_string_lossy(),
+                inner_width.saturating_sub("Location: ".len()),
+            )),
         ]),
     ];
 
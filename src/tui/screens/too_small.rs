@@ -0,0 +1,63 @@
+use crate::shared::constants::{MIN_TUI_HEIGHT, MIN_TUI_WIDTH};
+use crate::tui::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+/// Whether `area` is too small for the screens' layouts
+pub fn terminal_too_small(area: Rect) -> bool {
+    area.width < MIN_TUI_WIDTH || area.height < MIN_TUI_HEIGHT
+}
+
+/// Shown instead of the current screen until the terminal is resized
+pub fn render_too_small(frame: &mut Frame, theme: &Theme) {
+    let area = frame.area();
+    let message = vec![
+        Line::styled(
+            "Terminal too small, please resize",
+            Style::default().fg(theme.color).add_modifier(Modifier::BOLD),
+        ),
+        Line::from(format!(
+            "{}x{} (need {}x{})",
+            area.width, area.height, MIN_TUI_WIDTH, MIN_TUI_HEIGHT
+        )),
+    ];
+
+    // Vertically center the two lines when there's room
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(2), Constraint::Fill(1)])
+        .split(area);
+
+    let paragraph = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, rows[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_too_small_message() {
+        assert!(terminal_too_small(Rect::new(0, 0, 39, 30)));
+        assert!(terminal_too_small(Rect::new(0, 0, 80, 11)));
+        assert!(!terminal_too_small(Rect::new(0, 0, 40, 12)));
+
+        let mut terminal = Terminal::new(TestBackend::new(36, 6)).unwrap();
+        terminal
+            .draw(|frame| render_too_small(frame, &Theme::default()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Terminal too small"));
+        assert!(text.contains("36x6 (need 40x12)"));
+    }
+}
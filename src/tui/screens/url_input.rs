@@ -1,7 +1,7 @@
 use crate::tui::{app::DownloadHistory, theme::Theme};
 use chrono::Local;
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
@@ -73,10 +73,8 @@ pub fn render_url_input(
 
     // Show cursor
     if !input.is_empty() || cursor_pos > 0 {
-        let cursor_x = chunks[1].x + cursor_pos as u16 + 1;
-        let cursor_y = chunks[1].y + 1;
-        if cursor_x < chunks[1].x + chunks[1].width - 1 {
-            frame.set_cursor_position((cursor_x, cursor_y));
+        if let Some(position) = input_cursor_position(chunks[1], cursor_pos) {
+            frame.set_cursor_position(position);
         }
     }
 
@@ -145,3 +143,31 @@ pub fn render_url_input(
         .style(Style::default().fg(theme.color));
     frame.render_widget(help, chunks[4]);
 }
+
+/// Cursor cell inside the bordered input box, clamped to its last column so a
+/// narrower terminal never places it outside the box
+fn input_cursor_position(input_area: Rect, cursor_pos: usize) -> Option<(u16, u16)> {
+    let inner_width = input_area.width.saturating_sub(2);
+    if inner_width == 0 || input_area.height < 3 {
+        return None;
+    }
+
+    let column = (cursor_pos as u16).min(inner_width - 1);
+    Some((input_area.x + 1 + column, input_area.y + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_cursor_position_is_clamped() {
+        let area = Rect::new(2, 4, 20, 5);
+        assert_eq!(input_cursor_position(area, 0), Some((3, 5)));
+        assert_eq!(input_cursor_position(area, 5), Some((8, 5)));
+        // Past the inner width the cursor sticks to the last column
+        assert_eq!(input_cursor_position(area, 300), Some((20, 5)));
+        assert_eq!(input_cursor_position(Rect::new(0, 0, 2, 5), 3), None);
+        assert_eq!(input_cursor_position(Rect::new(0, 0, 20, 2), 3), None);
+    }
+}
@@ -1,16 +1,63 @@
+use crate::shared::{Result, YtdlError};
 use ratatui::style::Color;
+use serde::Deserialize;
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 pub struct Theme {
     pub color: Color,
+    /// Draw with ASCII only, for terminals that can't render Unicode symbols
+    pub ascii: bool,
 }
 
 impl Theme {
     pub fn new() -> Self {
         Self {
             color: Color::Rgb(160, 160, 160),
+            ascii: false,
         }
     }
+
+    pub fn with_ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// `unicode`, or `ascii` when the theme is ASCII-only
+    pub fn glyph<'a>(&self, unicode: &'a str, ascii: &'a str) -> &'a str {
+        if self.ascii {
+            ascii
+        } else {
+            unicode
+        }
+    }
+}
+
+/// Theme file contents, e.g. `color = "#7aa2f7"` or `color = "cyan"`
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    color: String,
+    #[serde(default)]
+    ascii: bool,
+}
+
+impl Theme {
+    /// Load a TOML theme file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| YtdlError::Config(format!("Failed to read theme {:?}: {}", path, e)))?;
+        let file: ThemeFile = toml::from_str(&content)
+            .map_err(|e| YtdlError::Config(format!("Invalid theme {:?}: {}", path, e)))?;
+        let color = file
+            .color
+            .parse()
+            .map_err(|_| YtdlError::Config(format!("Invalid theme color \"{}\"", file.color)))?;
+
+        Ok(Self {
+            color,
+            ascii: file.ascii,
+        })
+    }
 }
 
 impl Default for Theme {
@@ -18,3 +65,59 @@ impl Default for Theme {
         Self::new()
     }
 }
+
+/// Whether the terminal is likely to render Unicode symbols such as braille spinners
+pub fn terminal_supports_unicode() -> bool {
+    if cfg!(windows) {
+        // Windows Terminal handles Unicode; the legacy console often doesn't
+        return std::env::var_os("WT_SESSION").is_some();
+    }
+
+    let var = |name: &str| std::env::var(name).ok();
+    locale_is_utf8(var("LC_ALL"), var("LC_CTYPE"), var("LANG"))
+}
+
+/// The first set of `LC_ALL`, `LC_CTYPE` and `LANG` decides the charset, as in POSIX
+fn locale_is_utf8(lc_all: Option<String>, lc_ctype: Option<String>, lang: Option<String>) -> bool {
+    [lc_all, lc_ctype, lang]
+        .into_iter()
+        .flatten()
+        .find(|value| !value.is_empty())
+        .map(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_theme_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("theme.toml");
+
+        std::fs::write(&path, "color = \"#7aa2f7\"\nascii = true\n").unwrap();
+        let theme = Theme::load(&path).unwrap();
+        assert_eq!(theme.color, Color::Rgb(0x7a, 0xa2, 0xf7));
+        assert!(theme.ascii);
+
+        std::fs::write(&path, "color = \"cyan\"\n").unwrap();
+        assert_eq!(Theme::load(&path).unwrap().color, Color::Cyan);
+
+        std::fs::write(&path, "color = \"not a color\"\n").unwrap();
+        assert!(Theme::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_locale_is_utf8() {
+        let set = |v: &str| Some(v.to_string());
+        assert!(locale_is_utf8(None, None, set("en_US.UTF-8")));
+        assert!(locale_is_utf8(set(""), None, set("de_DE.utf8")));
+        assert!(!locale_is_utf8(set("C"), None, set("en_US.UTF-8")));
+        assert!(!locale_is_utf8(None, set("POSIX"), None));
+        assert!(!locale_is_utf8(None, None, None));
+    }
+}
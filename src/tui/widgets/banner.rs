@@ -5,26 +5,31 @@ use ratatui::{
     widgets::Paragraph,
 };
 
-pub fn render_banner<'a>(color: Color) -> Paragraph<'a> {
-    let banner = vec![
-        Line::from(vec![Span::styled(
+pub fn render_banner<'a>(color: Color, ascii: bool) -> Paragraph<'a> {
+    let logo = if ascii {
+        [
+            r#"\ / ___ ___  |   "#,
+            r#" |   |  |  \ |   "#,
+            r#" |   |  |__/ |___"#,
+        ]
+    } else {
+        [
             r#"  ┬ ┬┌┬┐╔╦╗╦  "#,
-            Style::default().fg(color),
-        )]),
-        Line::from(vec![Span::styled(
             r#"  └┬┘ │  ║║║  "#,
-            Style::default().fg(color),
-        )]),
-        Line::from(vec![Span::styled(
             r#"   ┴  ┴ ═╩╝╩═╝"#,
-            Style::default().fg(color),
-        )]),
+        ]
+    };
+    let mut banner: Vec<Line> = logo
+        .iter()
+        .map(|line| Line::from(vec![Span::styled(*line, Style::default().fg(color))]))
+        .collect();
+    banner.extend([
         Line::from(""),
         Line::from(vec![Span::styled(
             "YouTube Downloader",
             Style::default().fg(Color::Rgb(160, 160, 160)),
         )]),
-    ];
+    ]);
 
     Paragraph::new(banner).alignment(Alignment::Center)
 }
@@ -0,0 +1,43 @@
+use crate::tui::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Flex, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// A small centered yes/no dialog drawn over the current screen
+pub fn render_confirm(frame: &mut Frame, theme: &Theme, title: &str, message: &str) {
+    let [area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Max(50)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let text = vec![
+        Line::raw(message),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[Y] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw("Yes  "),
+            Span::styled("[N/Esc] ", Style::default().fg(theme.color).add_modifier(Modifier::BOLD)),
+            Span::raw("No"),
+        ]),
+    ];
+
+    let dialog = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", title))
+            .border_style(Style::default().fg(theme.color)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(dialog, area);
+}
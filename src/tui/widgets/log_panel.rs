@@ -0,0 +1,43 @@
+use crate::tui::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Overlay the bottom of the screen with the most recent log lines.
+/// `scroll` is how many lines the view is scrolled up from the newest entry.
+pub fn render_log_panel(frame: &mut Frame, theme: &Theme, lines: &[String], scroll: usize) {
+    let area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(frame.area())[1];
+
+    let visible = area.height.saturating_sub(2) as usize;
+    let end = lines.len().saturating_sub(scroll);
+    let start = end.saturating_sub(visible);
+
+    let text: Vec<Line> = if lines.is_empty() {
+        vec![Line::styled("No log entries yet", Style::default().add_modifier(Modifier::DIM))]
+    } else {
+        lines[start..end].iter().map(|line| Line::raw(line.as_str())).collect()
+    };
+
+    let title = if scroll > 0 {
+        format!(" Logs ({} newer below) - PgUp/PgDn scroll, l close ", scroll)
+    } else {
+        " Logs - PgUp/PgDn scroll, l close ".to_string()
+    };
+
+    let panel = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(theme.color)),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(panel, area);
+}
@@ -1,7 +1,11 @@
 pub mod banner;
+pub mod confirm;
+pub mod log_panel;
 pub mod progress_bar;
 pub mod spinner;
 
 pub use banner::render_banner;
+pub use confirm::render_confirm;
+pub use log_panel::render_log_panel;
 pub use progress_bar::render_progress_bar;
 pub use spinner::*;
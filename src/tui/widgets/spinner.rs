@@ -1,5 +1,8 @@
 use std::time::{Duration, Instant};
 
+/// Spinner styles selectable with `spinner_style`
+pub const SPINNER_STYLES: &[&str] = &["braille", "dots", "line"];
+
 /// Spinner widget for loading animations
 #[derive(Debug, Clone)]
 pub struct Spinner {
@@ -20,13 +23,13 @@ impl Spinner {
         }
     }
 
-    /// Create a spinner with dots
+    /// Create a spinner with plain ASCII dots, for fonts without braille glyphs
     pub fn dots() -> Self {
         Self {
-            frames: vec!["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            frames: vec![".  ", ".. ", "...", " ..", "  .", "   "],
             current_frame: 0,
             last_update: Instant::now(),
-            frame_duration: Duration::from_millis(80),
+            frame_duration: Duration::from_millis(120),
         }
     }
 
@@ -40,6 +43,20 @@ impl Spinner {
         }
     }
 
+    /// Create a spinner from a `SPINNER_STYLES` name, falling back to braille.
+    /// `speed_ms` overrides the style's frame duration.
+    pub fn from_style(style: &str, speed_ms: Option<u64>) -> Self {
+        let mut spinner = match style {
+            "dots" => Self::dots(),
+            "line" => Self::line(),
+            _ => Self::new(),
+        };
+        if let Some(ms) = speed_ms {
+            spinner.frame_duration = Duration::from_millis(ms.max(1));
+        }
+        spinner
+    }
+
     /// Update the spinner and return whether it changed
     pub fn tick(&mut self) -> bool {
         let now = Instant::now();
@@ -140,6 +157,14 @@ impl CheckmarkAnimation {
         }
     }
 
+    /// Checkmark drawn as `[OK]` for ASCII-only terminals
+    pub fn ascii() -> Self {
+        Self {
+            frames: vec!["", "[OK]", "[OK]", "[OK]"],
+            ..Self::new()
+        }
+    }
+
     pub fn start(&mut self) {
         self.current_frame = 0;
         self.completed = false;